@@ -30,6 +30,7 @@ export enum MarketStatus {
   Active = "active",
   Resolved = "resolved",
   Cancelled = "cancelled",
+  Voided = "voided",
 }
 
 export interface CreateMarketParams {
@@ -480,6 +481,7 @@ export class PredictDuelClient {
       .accounts({
         market: marketPda,
         creator: this.provider.wallet.publicKey,
+        payer: this.provider.wallet.publicKey,
         marketVault: marketVaultPda,
         systemProgram: anchor.web3.SystemProgram.programId,
       });
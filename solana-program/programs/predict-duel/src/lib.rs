@@ -1,12 +1,189 @@
 use anchor_lang::prelude::*;
+use solana_sha256_hasher::{hash, hashv};
 
 declare_id!("8aMfhVJxNZeGjgDg38XwdpMqDdrsvM42RPjF67DQ8VVe");
 
+/// Payouts above this amount may be (or must be, if mandated) vested - 10 SOL
+pub const DEFAULT_VESTING_THRESHOLD: u64 = 10_000_000_000;
+/// Default linear vesting period for large payouts - 7 days
+pub const DEFAULT_VESTING_DURATION: i64 = 7 * 24 * 60 * 60;
+/// Longest vesting period a creator can configure - 365 days
+pub const MAX_VESTING_DURATION: i64 = 365 * 24 * 60 * 60;
+/// Pyth pull-oracle receiver program (owner of PriceUpdateV2 accounts)
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+/// Switchboard on-demand program (owner of pull feed accounts)
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM_ID: Pubkey =
+    pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+/// Wormhole core bridge program (owner of verified PostedVAA accounts)
+pub const WORMHOLE_CORE_BRIDGE_PROGRAM_ID: Pubkey =
+    pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+/// Metaplex Bubblegum program (compressed NFTs)
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+/// SPL account compression program used by Bubblegum trees
+pub const ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey =
+    pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS2GVAJz6QY");
+/// SPL noop program Bubblegum logs leaves through
+pub const NOOP_PROGRAM_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+/// Chainlink OCR2 store program (owner of price feed accounts on Solana)
+pub const CHAINLINK_STORE_PROGRAM_ID: Pubkey =
+    pubkey!("HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJWHny");
+/// Jupiter v6 aggregator program, the only DEX route buybacks may CPI into
+pub const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+/// SPL stake pool program; LST-yield markets deposit idle escrow into one of its pools
+pub const STAKE_POOL_PROGRAM_ID: Pubkey = pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+/// Native stake program, used by the stake pool when withdrawing SOL
+pub const STAKE_PROGRAM_ID: Pubkey = pubkey!("Stake11111111111111111111111111111111111111");
+/// Default time bettors have after the deadline to reveal sealed bets - 24 hours
+pub const DEFAULT_REVEAL_WINDOW: i64 = 24 * 60 * 60;
+/// Share of an early-exited stake left in the pool for the remaining bettors - 2%
+pub const CASH_OUT_FEE_BPS: u64 = 200;
+/// Basis point denominator (100%)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+/// Largest extra payout weight a creator can grant to the earliest bets - 100% (2x)
+pub const MAX_EARLY_BONUS_BPS: u16 = 10_000;
+/// Largest payout-weight cut for late bets; they always keep at least 10%
+pub const MAX_LATE_DISCOUNT_BPS: u16 = 9_000;
+/// Longest closing auction a creator can put before the deadline - 1 hour
+pub const MAX_CLOSING_AUCTION: i64 = 60 * 60;
+/// Linked markets' deadlines must fall within this of each other - 1 hour
+pub const HEDGE_DEADLINE_WINDOW: i64 = 60 * 60;
+/// Target slot time, used to turn a market's lifetime into an expected slot count
+pub const SLOT_DURATION_MS: u64 = 400;
+/// Share of the expected slots that must have passed before a deadline counts as reached,
+/// so a skewed cluster clock can't expire a market early - 80%
+pub const MIN_SLOT_PROGRESS_BPS: u64 = 8_000;
+/// Longest fixed odds a book can quote - 100x (decimal odds in bps)
+pub const MAX_FIXED_ODDS_BPS: u32 = 1_000_000;
+/// Largest handicap a spread market can carry - 1000 points (in tenths)
+pub const MAX_SPREAD_X10: u32 = 10_000;
+/// How long after the feed's max age the settling oracle price can still be supplied - 10 minutes.
+/// If none is supplied by then the market is voided instead.
+pub const PRICE_RESOLUTION_WINDOW: i64 = 10 * 60;
+/// Longest a registered feed may take past expiry to publish its settling update - 24 hours
+pub const MAX_FEED_MAX_AGE: i64 = 24 * 60 * 60;
+/// Minimum stake per bet until governance changes it - 0.01 SOL
+pub const DEFAULT_MIN_STAKE: u64 = 10_000_000;
+/// Time a resolution can be challenged before it is final - 24 hours
+pub const DEFAULT_DISPUTE_WINDOW: i64 = 24 * 60 * 60;
+/// Longest dispute window governance can set - 30 days
+pub const MAX_DISPUTE_WINDOW: i64 = 30 * 24 * 60 * 60;
+/// Jurors drawn for each dispute
+pub const JURY_SIZE: usize = 5;
+/// Most stakers the juror pool holds
+pub const MAX_JUROR_POOL: usize = 64;
+/// How long a drawn jury has to vote - 48 hours; an undrawn dispute lapses after the same time
+pub const JURY_VOTING_PERIOD: i64 = 48 * 60 * 60;
+/// Bond posted to open a dispute, returned if the outcome is overturned - 0.1 SOL
+pub const DISPUTE_BOND: u64 = 100_000_000;
+/// Share of stake a juror loses for voting against the majority or not voting - 10%
+pub const JUROR_SLASH_BPS: u64 = 1_000;
+/// Most winners a raffle market can draw
+pub const MAX_RAFFLE_WINNERS: usize = 10;
+/// Largest share of a pool a market can divert into its lottery side pot - 2%
+pub const MAX_SIDE_POT_BPS: u16 = 200;
+/// Most payout leaves a merkle settlement can hold (claim bitmap fits one account)
+pub const MAX_SETTLEMENT_LEAVES: u32 = 65_536;
+/// Most winners settle_batch pays in one transaction
+pub const MAX_SETTLE_BATCH: usize = 10;
+/// On push-payout markets, how long after resolution winners wait for the keeper
+/// before they can claim themselves - 24 hours
+pub const PUSH_CLAIM_FALLBACK: i64 = 24 * 60 * 60;
+/// Largest bonus a booster NFT can add to a payout - 50%
+pub const MAX_BOOSTER_MULTIPLIER_BPS: u16 = 5_000;
+/// Positions a wallet's quest progress can count
+pub const MAX_QUEST_POSITIONS: usize = 20;
+/// Check-in streak length that earns a milestone reward - every 7 days
+pub const CHECK_IN_MILESTONE_DAYS: u32 = 7;
+/// Slots after which an unrevealed raffle randomness commitment can be replaced - about 1 hour
+pub const RANDOMNESS_REVEAL_TIMEOUT_SLOTS: u64 = 9_000;
+/// How long a governance proposal is open for votes - 3 days
+pub const GOVERNANCE_VOTING_PERIOD: i64 = 3 * 24 * 60 * 60;
+/// Share of the governance token supply that must vote for a proposal to pass - 4%
+pub const GOVERNANCE_QUORUM_BPS: u64 = 400;
+/// Share of the governance token supply a wallet must hold to propose - 0.5%
+pub const PROPOSAL_THRESHOLD_BPS: u64 = 50;
+/// Shortest reward epoch the admin can configure - 1 hour
+pub const MIN_REWARD_EPOCH_LENGTH: i64 = 60 * 60;
+/// Fixed-point scale for fees accrued per staked token
+pub const FEE_SHARE_PRECISION: u128 = 1_000_000_000_000;
+/// Default bond posted at market creation - 0.05 SOL
+pub const DEFAULT_CREATION_BOND: u64 = 50_000_000;
+/// Largest creation bond the admin can set - 1 SOL
+pub const MAX_CREATION_BOND: u64 = 1_000_000_000;
+/// Share of a market's escrow that may sit in the stake pool; the rest covers cash-outs - 80%
+pub const MAX_LST_DEPOSIT_BPS: u64 = 8_000;
+/// Recipients a winner can split their payout with
+pub const MAX_PAYOUT_SPLITS: usize = 4;
+/// Individual bets kept per position; later ones fold into the last entry
+pub const MAX_BET_ENTRIES: usize = 8;
+/// Shortest period between markets in a rolling series - 1 hour
+pub const MIN_SERIES_INTERVAL: i64 = 60 * 60;
+/// Limits on MarketExtension fields
+pub const MAX_LANGUAGE_LEN: usize = 8;
+pub const MAX_IMAGE_URI_LEN: usize = 200;
+pub const MAX_RESOLUTION_SOURCES: usize = 4;
+pub const MAX_RESOLUTION_SOURCE_LEN: usize = 100;
+pub const MAX_EXTERNAL_ID_LEN: usize = 64;
+/// Longest avatar/bio metadata URI on a profile
+pub const MAX_PROFILE_URI_LEN: usize = 200;
+/// Longest username handle; handles are 3+ characters of a-z, 0-9 and '_'
+pub const MAX_HANDLE_LEN: usize = 20;
+/// Markets the featured carousel can hold
+pub const MAX_FEATURED_MARKETS: usize = 10;
+/// Default stake a reporter posts to flag a market - 0.01 SOL
+pub const DEFAULT_FLAG_STAKE: u64 = 10_000_000;
+/// Default number of flags that freezes betting pending review
+pub const DEFAULT_FLAG_THRESHOLD: u32 = 5;
+/// Largest market creation fee the admin can set - 1 SOL
+pub const MAX_CREATION_FEE: u64 = 1_000_000_000;
+/// Resolutions later than this after a market becomes resolvable count against the creator - 24 hours
+pub const RESOLUTION_GRACE_PERIOD: i64 = 24 * 60 * 60;
+/// Share of bettor stake that can void a market left unresolved past the grace period - 2/3
+pub const VOID_SUPERMAJORITY_BPS: u64 = 6_667;
+/// Time between halting a market and force-refunding its stakes - 72 hours
+pub const EMERGENCY_REFUND_DELAY: i64 = 72 * 60 * 60;
+/// Premium for insuring a stake against an overturned resolution - 2% of the stake
+pub const INSURANCE_PREMIUM_BPS: u64 = 200;
+/// Vault balance below which a settled market counts as fully paid out - 0.001 SOL
+pub const ARCHIVE_DUST: u64 = 1_000_000;
+/// After this long settled, unclaimed funds are swept and the market can be archived - 180 days
+pub const ARCHIVE_DELAY: i64 = 180 * 24 * 60 * 60;
+/// Window over which bettor volume counts towards fee tiers - 30 days
+pub const FEE_TIER_VOLUME_WINDOW: i64 = 30 * 24 * 60 * 60;
+/// Claim window after resolution before the protocol fee can be collected, so
+/// fee-tier rebates can still be paid from it - 7 days
+pub const FEE_COLLECTION_DELAY: i64 = 7 * 24 * 60 * 60;
+/// How long fee-free credits redeemed from loyalty points stay usable - 30 days
+pub const FEE_CREDIT_DURATION: i64 = 30 * 24 * 60 * 60;
+/// Timelock delay at launch - 2 days
+pub const DEFAULT_TIMELOCK_DELAY: i64 = 2 * 24 * 60 * 60;
+/// Shortest timelock delay the admin can set - 24 hours
+pub const MIN_TIMELOCK_DELAY: i64 = 24 * 60 * 60;
+/// Longest timelock delay the admin can set - 30 days
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60;
+/// Longest wait a profile can set between a recovery request and the rotation - 30 days
+pub const MAX_RECOVERY_DELAY: i64 = 30 * 24 * 60 * 60;
+/// Highest protocol fee that can be set - 10%
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+/// Highest fee takers pay on top of stakes matched against lay offers - 5%
+pub const MAX_TAKER_FEE_BPS: u16 = 500;
+/// Highest share of a bet's protocol fee that can go to the frontend it came through - 50%
+pub const MAX_FRONTEND_FEE_SHARE_BPS: u16 = 5_000;
+/// Highest performance fee a portfolio manager can charge on profits - 30%
+pub const MAX_PERFORMANCE_FEE_BPS: u16 = 3_000;
+/// Share of a market's protocol fee paid to its boosters - 20%
+pub const BOOSTER_FEE_SHARE_BPS: u64 = 2_000;
+/// Number of pool snapshots kept in a market's odds history ring buffer
+pub const ODDS_HISTORY_LEN: usize = 32;
+/// Bets kept in a market's recent-activity feed
+pub const RECENT_BETS_LEN: usize = 16;
+
 #[program]
 pub mod predict_duel {
     use super::*;
 
-    /// Create a new prediction market
+    /// Create a new prediction market. The creator only has to sign, so other programs can
+    /// create markets from a PDA while a funded account pays.
     pub fn create_market(
         ctx: Context<CreateMarket>,
         market_index: u64,
@@ -19,14 +196,7 @@ pub mod predict_duel {
         let market = &mut ctx.accounts.market;
         let clock = Clock::get()?;
 
-        require!(
-            question.len() <= 200,
-            PredictDuelError::QuestionTooLong
-        );
-        require!(
-            stake_amount >= 10_000_000, // Minimum 0.01 SOL (in lamports)
-            PredictDuelError::StakeTooLow
-        );
+        require!(question.len() <= 200, PredictDuelError::QuestionTooLong);
         require!(
             deadline > clock.unix_timestamp,
             PredictDuelError::InvalidDeadline
@@ -43,18 +213,45 @@ pub mod predict_duel {
         market.stake_amount = stake_amount;
         market.deadline = deadline;
         market.market_type = market_type;
-        market.status = MarketStatus::Pending;
-        market.pool_size = 0;
-        market.yes_count = 0;
-        market.no_count = 0;
-        market.yes_pool = 0;
-        market.no_pool = 0;
-        market.total_participants = 0;
-        market.outcome = None;
         market.created_at = clock.unix_timestamp;
+        market.created_slot = clock.slot;
+        market.init_state(clock.unix_timestamp);
+        market.apply_category_params(&ctx.accounts.config, clock.unix_timestamp)?;
+        market.resolver = ctx
+            .accounts
+            .resolver
+            .as_ref()
+            .map(|resolver| resolver.key());
+
+        let creator_profile = &mut ctx.accounts.creator_profile;
+        creator_profile.wallet = ctx.accounts.creator.key();
+        creator_profile.bump = ctx.bumps.creator_profile;
+        register_market(
+            market,
+            &ctx.accounts.config,
+            creator_profile,
+            ctx.accounts.creator_stats.as_deref_mut(),
+            ctx.accounts.category_stats.as_deref_mut(),
+        )?;
+        charge_creation(
+            market,
+            &ctx.accounts.config,
+            creator_profile,
+            ctx.accounts.creator_pass.as_deref().map(|pass| &**pass),
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts
+                .treasury
+                .as_ref()
+                .map(|treasury| treasury.to_account_info()),
+            ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        let odds_history = &mut ctx.accounts.odds_history;
+        odds_history.market = market.key();
+        odds_history.bump = ctx.bumps.odds_history;
 
         msg!("Market created: {}", market.question);
-        
+
         Ok(())
     }
 
@@ -68,19 +265,27 @@ pub mod predict_duel {
         let participant = &mut ctx.accounts.participant;
         let clock = Clock::get()?;
 
-        // Validate market is open for betting
-        require!(
-            market.status == MarketStatus::Pending || market.status == MarketStatus::Active,
-            PredictDuelError::MarketNotActive
-        );
-        require!(
-            clock.unix_timestamp < market.deadline,
-            PredictDuelError::MarketExpired
-        );
-        require!(
-            stake_amount >= 10_000_000, // Minimum 0.01 SOL
-            PredictDuelError::StakeTooLow
-        );
+        market.assert_open_bet(participant, prediction, stake_amount, clock.unix_timestamp)?;
+        market.assert_personhood(
+            &ctx.accounts.bettor.key(),
+            participant,
+            ctx.accounts
+                .personhood_credential
+                .as_deref()
+                .map(|credential| &**credential),
+        )?;
+        verify_attestation(
+            &ctx.accounts.config,
+            &ctx.accounts.bettor.key(),
+            ctx.accounts.instructions.as_deref(),
+            clock.unix_timestamp,
+        )?;
+
+        let user_profile = &mut ctx.accounts.user_profile;
+        user_profile.wallet = ctx.accounts.bettor.key();
+        user_profile.bump = ctx.bumps.user_profile;
+        user_profile.record_bet(&ctx.accounts.config, clock.slot, clock.unix_timestamp)?;
+        user_profile.record_volume(stake_amount, clock.unix_timestamp);
 
         // Transfer SOL from bettor to market vault
         anchor_lang::system_program::transfer(
@@ -94,35 +299,40 @@ pub mod predict_duel {
             stake_amount,
         )?;
 
-        // Initialize or update participant account
-        if participant.market == Pubkey::default() {
+        // Initialize participant account on first entry
+        let is_new = participant.market == Pubkey::default();
+        if is_new {
             participant.market = market.key();
             participant.bettor = ctx.accounts.bettor.key();
             participant.prediction = prediction;
-            participant.stake = stake_amount;
             participant.claimed = false;
             // Store bump - Anchor 0.32.1 uses struct fields
             participant.bump = ctx.bumps.participant;
-            
-            market.total_participants += 1;
-        } else {
-            // Add to existing stake
-            participant.stake += stake_amount;
         }
 
-        // Update market stats
-        market.pool_size += stake_amount;
-        if prediction {
-            market.yes_count += 1;
-            market.yes_pool += stake_amount;
-        } else {
-            market.no_count += 1;
-            market.no_pool += stake_amount;
+        market.credit_bet(
+            participant,
+            is_new,
+            prediction,
+            stake_amount,
+            clock.unix_timestamp,
+        )?;
+        ctx.accounts
+            .odds_history
+            .record(clock.unix_timestamp, market.yes_pool, market.no_pool);
+        if let Some(frontend) = ctx.accounts.frontend.as_ref() {
+            participant.record_frontend(frontend.owner, stake_amount);
         }
-
-        // Activate market if it was pending
-        if market.status == MarketStatus::Pending {
-            market.status = MarketStatus::Active;
+        if let Some(recent_bets) = ctx.accounts.recent_bets.as_mut() {
+            recent_bets.record(
+                participant.bettor,
+                prediction,
+                stake_amount,
+                clock.unix_timestamp,
+            );
+        }
+        if let Some(category_stats) = ctx.accounts.category_stats.as_mut() {
+            market.sync_category_stats(category_stats);
         }
 
         msg!(
@@ -134,94 +344,576 @@ pub mod predict_duel {
         Ok(())
     }
 
-    /// Resolve the market with the final outcome
-    pub fn resolve_market(
-        ctx: Context<ResolveMarket>,
-        outcome: bool, // true = yes, false = no
+    /// Create a Crypto market fully defined by (feed, comparison, strike, expiry); no free text
+    pub fn create_price_market(
+        ctx: Context<CreatePriceMarket>,
+        market_index: u64,
+        comparison: PriceComparison,
+        strike_price: i64,
+        strike_expo: i32,
+        expiry: i64,
+        stake_amount: u64,
     ) -> Result<()> {
         let market = &mut ctx.accounts.market;
+        let oracle_feed = &ctx.accounts.oracle_feed;
         let clock = Clock::get()?;
 
-        // Only creator or designated resolver can resolve
         require!(
-            ctx.accounts.resolver.key() == market.creator,
-            PredictDuelError::UnauthorizedResolver
+            oracle_feed.active && oracle_feed.provider.is_price_feed(),
+            PredictDuelError::InvalidOracleFeed
         );
-
-        // Market must be active and past deadline
         require!(
-            market.status == MarketStatus::Active,
-            PredictDuelError::MarketNotActive
+            strike_expo.unsigned_abs() <= MAX_PRICE_EXPO,
+            PredictDuelError::InvalidStrike
         );
         require!(
-            clock.unix_timestamp >= market.deadline,
+            expiry > clock.unix_timestamp,
+            PredictDuelError::InvalidDeadline
+        );
+
+        market.bump = ctx.bumps.market;
+        market.vault_bump = ctx.bumps.market_vault;
+
+        market.creator = ctx.accounts.creator.key();
+        market.market_index = market_index;
+        market.question = format!(
+            "Will {} be {} {}e{} at {}?",
+            oracle_feed.asset_name,
+            if comparison == PriceComparison::Above {
+                "above"
+            } else {
+                "below"
+            },
+            strike_price,
+            strike_expo,
+            expiry
+        );
+        market.category = MarketCategory::Crypto;
+        market.stake_amount = stake_amount;
+        market.deadline = expiry;
+        market.market_type = MarketType::Public;
+        market.created_at = clock.unix_timestamp;
+        market.created_slot = clock.slot;
+        market.init_state(clock.unix_timestamp);
+        market.apply_category_params(&ctx.accounts.config, clock.unix_timestamp)?;
+        market.oracle_feed = Some(oracle_feed.feed);
+        market.price_condition = Some(PriceCondition {
+            comparison,
+            strike_price,
+            strike_expo,
+        });
+
+        let creator_profile = &mut ctx.accounts.creator_profile;
+        creator_profile.wallet = ctx.accounts.creator.key();
+        creator_profile.bump = ctx.bumps.creator_profile;
+        register_market(
+            market,
+            &ctx.accounts.config,
+            creator_profile,
+            ctx.accounts.creator_stats.as_deref_mut(),
+            ctx.accounts.category_stats.as_deref_mut(),
+        )?;
+        charge_creation(
+            market,
+            &ctx.accounts.config,
+            creator_profile,
+            ctx.accounts.creator_pass.as_deref().map(|pass| &**pass),
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts
+                .treasury
+                .as_ref()
+                .map(|treasury| treasury.to_account_info()),
+            ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        let odds_history = &mut ctx.accounts.odds_history;
+        odds_history.market = market.key();
+        odds_history.bump = ctx.bumps.odds_history;
+
+        msg!("Price market created: {}", market.question);
+
+        Ok(())
+    }
+
+    /// Permissionlessly resolve a price market from its registered oracle feed
+    pub fn resolve_price_market(ctx: Context<ResolveOracleMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let oracle_feed = &ctx.accounts.oracle_feed;
+        let clock = Clock::get()?;
+        market.clear_closing_auction(clock.unix_timestamp)?;
+
+        let condition = market
+            .price_condition
+            .clone()
+            .ok_or(PredictDuelError::NotPriceMarket)?;
+        require!(oracle_feed.active, PredictDuelError::InvalidOracleFeed);
+        require!(
+            clock.slot >= market.min_deadline_slot(),
             PredictDuelError::MarketNotExpired
         );
 
+        let Some(price) = market.settlement_reading(
+            oracle_feed.provider,
+            oracle_feed.max_age,
+            &ctx.accounts.price_feed,
+            clock.unix_timestamp,
+        )?
+        else {
+            market.status = MarketStatus::Voided;
+            market.settled_at = clock.unix_timestamp;
+            if let Some(category_stats) = ctx.accounts.category_stats.as_mut() {
+                market.sync_category_stats(category_stats);
+            }
+            msg!(
+                "No settling price supplied within the resolution window: stakes will be refunded"
+            );
+            return Ok(());
+        };
+
+        let ordering = compare_price(
+            price.price,
+            price.expo,
+            condition.strike_price,
+            condition.strike_expo,
+        )?;
+        let outcome = match condition.comparison {
+            PriceComparison::Above => ordering == std::cmp::Ordering::Greater,
+            PriceComparison::Below => ordering == std::cmp::Ordering::Less,
+        };
+
         market.status = MarketStatus::Resolved;
+        market.resolved_at = clock.unix_timestamp;
+        market.settled_at = clock.unix_timestamp;
         market.outcome = Some(outcome);
+        market.bounty_hunter = ctx.accounts.hunter.as_ref().map(|hunter| hunter.key());
+        market.resolved_by = market.bounty_hunter;
+        if let Some(category_stats) = ctx.accounts.category_stats.as_mut() {
+            market.sync_category_stats(category_stats);
+        }
 
         msg!(
-            "Market resolved: Outcome is {}",
+            "Price market resolved at {}e{}: Outcome is {}",
+            price.price,
+            price.expo,
             if outcome { "YES" } else { "NO" }
         );
 
         Ok(())
     }
 
-    /// Claim winnings after market is resolved
-    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
-        let market = &ctx.accounts.market;
-        let participant = &mut ctx.accounts.participant;
+    /// Bind a Weather market to a weather feed with its rounding and tolerance spec (before any bets)
+    pub fn set_weather_condition(
+        ctx: Context<SetWeatherCondition>,
+        condition: WeatherCondition,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let oracle_feed = &ctx.accounts.oracle_feed;
 
-        // Validate market is resolved
+        market.assert_configurable(&ctx.accounts.creator.key())?;
         require!(
-            market.status == MarketStatus::Resolved,
-            PredictDuelError::MarketNotResolved
+            market.category == MarketCategory::Weather,
+            PredictDuelError::InvalidCategory
         );
         require!(
-            !participant.claimed,
-            PredictDuelError::AlreadyClaimed
+            oracle_feed.active && oracle_feed.provider.is_price_feed(),
+            PredictDuelError::InvalidOracleFeed
+        );
+        require!(
+            market.event.is_none() && market.spread_x10.is_none(),
+            PredictDuelError::OracleMarket
+        );
+        require!(
+            condition.decimals as u32 <= MAX_PRICE_EXPO,
+            PredictDuelError::InvalidStrike
+        );
+        require!(
+            (0..=MAX_FEED_MAX_AGE).contains(&condition.max_reading_age),
+            PredictDuelError::InvalidFeedMaxAge
         );
 
-        let outcome = market.outcome.ok_or(PredictDuelError::NoOutcome)?;
+        market.oracle_feed = Some(oracle_feed.feed);
+        market.weather_condition = Some(condition);
 
-        // Check if participant won
-        let won = participant.prediction == outcome;
-        require!(won, PredictDuelError::NotAWinner);
+        msg!("Weather market bound to feed {}", oracle_feed.asset_name);
 
-        // Calculate payout based on proportional share of winning pool
-        let winning_pool_stake = if outcome {
-            market.yes_pool
-        } else {
-            market.no_pool
+        Ok(())
+    }
+
+    /// Permissionlessly resolve a Weather market from its bound feed
+    pub fn resolve_weather_market(ctx: Context<ResolveOracleMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let oracle_feed = &ctx.accounts.oracle_feed;
+        let clock = Clock::get()?;
+        market.clear_closing_auction(clock.unix_timestamp)?;
+
+        let condition = market
+            .weather_condition
+            .clone()
+            .ok_or(PredictDuelError::NotWeatherMarket)?;
+        require!(oracle_feed.active, PredictDuelError::InvalidOracleFeed);
+        require!(
+            clock.slot >= market.min_deadline_slot(),
+            PredictDuelError::MarketNotExpired
+        );
+
+        let Some(reading) = market.settlement_reading(
+            oracle_feed.provider,
+            condition.reading_max_age(oracle_feed),
+            &ctx.accounts.price_feed,
+            clock.unix_timestamp,
+        )?
+        else {
+            market.status = MarketStatus::Voided;
+            market.settled_at = clock.unix_timestamp;
+            if let Some(category_stats) = ctx.accounts.category_stats.as_mut() {
+                market.sync_category_stats(category_stats);
+            }
+            msg!("No settling reading supplied within the resolution window: stakes will be refunded");
+            return Ok(());
+        };
+
+        let observed = round_reading(
+            reading.price,
+            reading.expo,
+            condition.decimals,
+            condition.rounding,
+        )?;
+        let threshold = condition.threshold as i128;
+
+        // Readings within tolerance of the threshold are too close to call
+        if observed.abs_diff(threshold) <= condition.tolerance as u128 {
+            market.status = MarketStatus::Voided;
+            market.settled_at = clock.unix_timestamp;
+            if let Some(category_stats) = ctx.accounts.category_stats.as_mut() {
+                market.sync_category_stats(category_stats);
+            }
+            msg!(
+                "Reading {} is within tolerance of threshold {}: stakes will be refunded",
+                observed,
+                threshold
+            );
+            return Ok(());
+        }
+
+        let outcome = match condition.comparison {
+            PriceComparison::Above => observed > threshold,
+            PriceComparison::Below => observed < threshold,
         };
 
+        market.status = MarketStatus::Resolved;
+        market.resolved_at = clock.unix_timestamp;
+        market.settled_at = clock.unix_timestamp;
+        market.outcome = Some(outcome);
+        market.bounty_hunter = ctx.accounts.hunter.as_ref().map(|hunter| hunter.key());
+        market.resolved_by = market.bounty_hunter;
+        if let Some(category_stats) = ctx.accounts.category_stats.as_mut() {
+            market.sync_category_stats(category_stats);
+        }
+
+        msg!(
+            "Weather market resolved at {} (x10^-{}): Outcome is {}",
+            observed,
+            condition.decimals,
+            if outcome { "YES" } else { "NO" }
+        );
+
+        Ok(())
+    }
+
+    /// Registered sports reporter publishes the final state of a game (once per game)
+    pub fn report_game_result(
+        ctx: Context<ReportGameResult>,
+        game_id: u64,
+        status: GameStatus,
+        home_score: u32,
+        away_score: u32,
+    ) -> Result<()> {
+        let game_result = &mut ctx.accounts.game_result;
+        let clock = Clock::get()?;
+
         require!(
-            winning_pool_stake > 0,
-            PredictDuelError::MarketNotActive
+            ctx.accounts.oracle_feed.active
+                && ctx.accounts.oracle_feed.provider == OracleProvider::SportsReporter,
+            PredictDuelError::InvalidOracleFeed
         );
 
-        // Payout = (participant_stake / winning_pool_stake) * total_pool
-        // Use u128 to prevent overflow
-        let payout = ((participant.stake as u128)
-            .checked_mul(market.pool_size as u128)
-            .ok_or(PredictDuelError::MarketNotActive)?
-            .checked_div(winning_pool_stake as u128)
-            .ok_or(PredictDuelError::MarketNotActive)?) as u64;
-        
-        // Validate payout is positive
+        game_result.reporter = ctx.accounts.reporter.key();
+        game_result.game_id = game_id;
+        game_result.status = status;
+        game_result.home_score = home_score;
+        game_result.away_score = away_score;
+        game_result.reported_at = clock.unix_timestamp;
+        game_result.bump = ctx.bumps.game_result;
+
+        msg!("Game {} reported: {}-{}", game_id, home_score, away_score);
+
+        Ok(())
+    }
+
+    /// Bind a Sports market to a reporter's game id and the rule deriving its outcome (before any bets)
+    pub fn bind_game(
+        ctx: Context<BindGame>,
+        game_id: u64,
+        condition: EventCondition,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let oracle_feed = &ctx.accounts.oracle_feed;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
         require!(
-            payout > 0,
-            PredictDuelError::MarketNotActive
+            market.category == MarketCategory::Sports,
+            PredictDuelError::InvalidCategory
+        );
+        require!(
+            oracle_feed.active && oracle_feed.provider == OracleProvider::SportsReporter,
+            PredictDuelError::InvalidOracleFeed
+        );
+        require!(
+            market.event.is_none() && market.spread_x10.is_none(),
+            PredictDuelError::OracleMarket
+        );
+
+        market.oracle_feed = Some(oracle_feed.feed);
+        market.game_condition = Some(GameCondition { game_id, condition });
+
+        msg!("Market bound to game {}", game_id);
+
+        Ok(())
+    }
+
+    /// Permissionlessly resolve a game-bound market from the reported result.
+    /// Postponed or cancelled games void the market.
+    pub fn resolve_game_market(ctx: Context<ResolveGameMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let game_result = &ctx.accounts.game_result;
+        let clock = Clock::get()?;
+        market.clear_closing_auction(clock.unix_timestamp)?;
+
+        let binding = market
+            .game_condition
+            .clone()
+            .ok_or(PredictDuelError::NotGameMarket)?;
+        require!(
+            binding.game_id == game_result.game_id
+                && market.oracle_feed == Some(game_result.reporter),
+            PredictDuelError::GameMismatch
         );
-        
-        // Ensure vault has sufficient balance (account for rent exemption)
-        let vault_balance = ctx.accounts.market_vault.lamports();
         require!(
-            vault_balance >= payout,
+            ctx.accounts.oracle_feed.active,
+            PredictDuelError::InvalidOracleFeed
+        );
+        require!(
+            market.status == MarketStatus::Active,
             PredictDuelError::MarketNotActive
         );
+        require!(
+            market.deadline_reached(&clock),
+            PredictDuelError::MarketNotExpired
+        );
+
+        if game_result.status != GameStatus::Final {
+            market.status = MarketStatus::Voided;
+            market.settled_at = clock.unix_timestamp;
+            if let Some(category_stats) = ctx.accounts.category_stats.as_mut() {
+                market.sync_category_stats(category_stats);
+            }
+            msg!(
+                "Game {} did not complete: stakes will be refunded",
+                game_result.game_id
+            );
+            return Ok(());
+        }
+
+        let result = EventResult {
+            primary: game_result.home_score as i64,
+            secondary: game_result.away_score as i64,
+        };
+        match binding.condition.evaluate(&result) {
+            Some(outcome) => {
+                market.status = MarketStatus::Resolved;
+                market.resolved_at = clock.unix_timestamp;
+                market.settled_at = clock.unix_timestamp;
+                market.outcome = Some(outcome);
+                market.bounty_hunter = ctx.accounts.hunter.as_ref().map(|hunter| hunter.key());
+                market.resolved_by = market.bounty_hunter;
+                msg!(
+                    "Game market resolved ({}-{}): Outcome is {}",
+                    game_result.home_score,
+                    game_result.away_score,
+                    if outcome { "YES" } else { "NO" }
+                );
+            }
+            None => {
+                market.status = MarketStatus::Voided;
+                market.settled_at = clock.unix_timestamp;
+                msg!("Game result landed on the line: stakes will be refunded");
+            }
+        }
+        if let Some(category_stats) = ctx.accounts.category_stats.as_mut() {
+            market.sync_category_stats(category_stats);
+        }
+
+        Ok(())
+    }
+
+    /// Resolve the market with the final outcome
+    pub fn resolve_market(
+        ctx: Context<ResolveMarket>,
+        outcome: bool, // true = yes, false = no
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let now = Clock::get()?.unix_timestamp;
+        market.clear_closing_auction(now)?;
+
+        market.assert_resolvable(&ctx.accounts.resolver.key())?;
+        market.resolved_late = !ctx
+            .accounts
+            .creator_profile
+            .record_resolution(market.resolvable_at(), now);
+        market.assert_manual_outcome()?;
+        market.status = MarketStatus::Resolved;
+        market.resolved_at = now;
+        market.settled_at = now;
+        market.outcome = Some(outcome);
+        market.resolved_by = Some(ctx.accounts.resolver.key());
+        market.credit_resolution(
+            &mut ctx.accounts.creator_profile,
+            ctx.accounts
+                .resolver_profile
+                .as_deref_mut()
+                .map(|profile| &mut **profile),
+        )?;
+        if let Some(category_stats) = ctx.accounts.category_stats.as_mut() {
+            market.sync_category_stats(category_stats);
+        }
+
+        msg!(
+            "Market resolved: Outcome is {}",
+            if outcome { "YES" } else { "NO" }
+        );
+
+        Ok(())
+    }
+
+    /// Settle a winner-takes-all duel as a draw: both stakes are returned via refund_stake
+    pub fn resolve_push(ctx: Context<ResolveMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let now = Clock::get()?.unix_timestamp;
+        market.clear_closing_auction(now)?;
+
+        market.assert_resolvable(&ctx.accounts.resolver.key())?;
+        market.resolved_late = !ctx
+            .accounts
+            .creator_profile
+            .record_resolution(market.resolvable_at(), now);
+        market.push_duel(ctx.accounts.resolver.key(), now)?;
+        market.credit_resolution(
+            &mut ctx.accounts.creator_profile,
+            ctx.accounts
+                .resolver_profile
+                .as_deref_mut()
+                .map(|profile| &mut **profile),
+        )?;
+        if let Some(category_stats) = ctx.accounts.category_stats.as_mut() {
+            market.sync_category_stats(category_stats);
+        }
+
+        msg!("Duel pushed: stakes will be refunded");
+
+        Ok(())
+    }
+
+    /// Resolve a spread market from the final score: YES if home covers the spread,
+    /// push (void and refund) if it lands exactly
+    pub fn resolve_spread(
+        ctx: Context<ResolveMarket>,
+        home_score: u32,
+        away_score: u32,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let now = Clock::get()?.unix_timestamp;
+        market.clear_closing_auction(now)?;
+
+        market.assert_resolvable(&ctx.accounts.resolver.key())?;
+        market.resolved_late = !ctx
+            .accounts
+            .creator_profile
+            .record_resolution(market.resolvable_at(), now);
+        let outcome =
+            market.settle_spread(home_score, away_score, ctx.accounts.resolver.key(), now)?;
+        market.credit_resolution(
+            &mut ctx.accounts.creator_profile,
+            ctx.accounts
+                .resolver_profile
+                .as_deref_mut()
+                .map(|profile| &mut **profile),
+        )?;
+        if let Some(category_stats) = ctx.accounts.category_stats.as_mut() {
+            market.sync_category_stats(category_stats);
+        }
+
+        match outcome {
+            Some(outcome) => msg!(
+                "Spread market resolved ({}-{}): Outcome is {}",
+                home_score,
+                away_score,
+                if outcome { "YES" } else { "NO" }
+            ),
+            None => msg!(
+                "Spread pushed ({}-{}): stakes will be refunded",
+                home_score,
+                away_score
+            ),
+        }
+
+        Ok(())
+    }
+
+    /// Claim winnings after market is resolved. Positions with payout splits pass each
+    /// split recipient as a remaining account, in order.
+    pub fn claim_winnings<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimWinnings<'info>>,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let participant = &mut ctx.accounts.participant;
+        let clock = Clock::get()?;
+
+        // Cross-chain and receipt-backed positions are paid out through their own paths
+        participant.assert_settles_locally()?;
+        // Push-payout winners are paid by the keeper unless it hasn't got to them in time
+        require!(
+            market.payout_mode == PayoutMode::Pull
+                || !participant.batch_settleable()
+                || clock.unix_timestamp >= market.resolved_at + PUSH_CLAIM_FALLBACK,
+            PredictDuelError::PushPayoutPending
+        );
+
+        // Validates resolution, claim status and that the participant won
+        let payout = market.claim_payout(
+            &ctx.accounts.config,
+            participant,
+            ctx.accounts
+                .user_profile
+                .as_deref_mut()
+                .map(|user_profile| &mut **user_profile),
+            clock.unix_timestamp,
+        )?;
+
+        assert_claim_co_signed(
+            &ctx.accounts.profile_guard,
+            ctx.accounts.co_signer.as_ref(),
+            payout,
+        )?;
+
+        // Large wins on vesting-mandated markets must go through start_vesting
+        require!(
+            !(market.vesting_mandatory && payout > market.vesting_threshold),
+            PredictDuelError::VestingRequired
+        );
+
+        // Ensure vault has sufficient balance (account for rent exemption)
+        let vault_balance = ctx.accounts.market_vault.lamports();
+        require!(vault_balance >= payout, PredictDuelError::MarketNotActive);
 
         // Transfer winnings from vault to winner
         // Use the vault's bump that Anchor validated (more reliable than stored value)
@@ -233,23 +925,54 @@ pub mod predict_duel {
         ];
         let signer = &[&seeds[..]];
 
+        let remainder = pay_claim_shares(
+            payout,
+            participant,
+            ctx.remaining_accounts,
+            ctx.accounts
+                .user_profile
+                .as_deref_mut()
+                .map(|user_profile| &mut **user_profile),
+            ctx.accounts
+                .charity
+                .as_ref()
+                .map(|charity| charity.to_account_info()),
+            ctx.accounts.market_vault.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            signer,
+        )?;
+
+        // Winners may route the payout away from the hot signing wallet
+        let destination = match &ctx.accounts.destination {
+            Some(destination) => destination.to_account_info(),
+            None => ctx.accounts.winner.to_account_info(),
+        };
+
         anchor_lang::system_program::transfer(
             CpiContext::new_with_signer(
                 ctx.accounts.system_program.to_account_info(),
                 anchor_lang::system_program::Transfer {
                     from: ctx.accounts.market_vault.to_account_info(),
-                    to: ctx.accounts.winner.to_account_info(),
+                    to: destination.clone(),
                 },
                 signer,
             ),
+            remainder,
+        )?;
+
+        pay_boost_bonus(
+            participant,
             payout,
+            ctx.accounts.booster_fund.as_deref_mut(),
+            &destination,
         )?;
 
         participant.claimed = true;
 
         msg!(
-            "Winnings claimed: {} SOL",
-            payout as f64 / 1_000_000_000.0
+            "Winnings claimed: {} SOL to {}",
+            payout as f64 / 1_000_000_000.0,
+            destination.key()
         );
 
         Ok(())
@@ -271,27 +994,140 @@ pub mod predict_duel {
             market.total_participants == 0,
             PredictDuelError::CannotCancel
         );
-
         market.status = MarketStatus::Cancelled;
+        market.settled_at = Clock::get()?.unix_timestamp;
 
         msg!("Market cancelled");
 
         Ok(())
     }
 
-    /// Refund participants if market is cancelled
-    pub fn refund_stake(ctx: Context<RefundStake>) -> Result<()> {
-        let market = &ctx.accounts.market;
-        let participant = &mut ctx.accounts.participant;
+    /// Cancel a market that already has bets, before its deadline, when the question has become
+    /// moot (event postponed, ambiguity discovered). Bettors recover stakes via refund_stake
+    /// or refund_batch; the creation bond is forfeited.
+    pub fn cancel_with_refunds(ctx: Context<CancelMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
 
         require!(
-            market.status == MarketStatus::Cancelled,
-            PredictDuelError::MarketNotCancelled
+            ctx.accounts.creator.key() == market.creator,
+            PredictDuelError::UnauthorizedResolver
+        );
+        market.cancel_with_refunds(clock.unix_timestamp)?;
+
+        emit!(MarketCancelledByCreator {
+            market: market.key(),
+            creator: market.creator,
+            participants: market.total_participants,
+            bond_forfeited: market.creation_bond,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Market cancelled with {} participants: stakes will be refunded, bond forfeited",
+            market.total_participants
+        );
+
+        Ok(())
+    }
+
+    /// Approve calling off a 1v1 Challenge: once the bettors on both sides have approved
+    /// (in one transaction or one after the other) the duel is cancelled and both stakes
+    /// become refundable. Works at any point before resolution.
+    pub fn mutual_cancel(ctx: Context<MutualCancel>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let participant = &ctx.accounts.participant;
+
+        require!(
+            market.market_type == MarketType::Challenge,
+            PredictDuelError::NotChallenge
         );
         require!(
-            !participant.claimed,
-            PredictDuelError::AlreadyClaimed
+            market.status == MarketStatus::Pending || market.status == MarketStatus::Active,
+            PredictDuelError::CannotCancel
+        );
+        // Exactly one bettor on each side
+        require!(
+            market.total_participants == 2 && market.yes_pool > 0 && market.no_pool > 0,
+            PredictDuelError::NotChallenge
+        );
+        require!(
+            participant.stake > 0 && !participant.claimed,
+            PredictDuelError::Unauthorized
+        );
+
+        if participant.prediction {
+            market.yes_cancel_approved = true;
+        } else {
+            market.no_cancel_approved = true;
+        }
+
+        if market.yes_cancel_approved && market.no_cancel_approved {
+            market.status = MarketStatus::Cancelled;
+            market.settled_at = Clock::get()?.unix_timestamp;
+            msg!("Duel called off by both sides: stakes will be refunded");
+        } else {
+            msg!(
+                "Cancellation approved by the {} side",
+                if participant.prediction { "YES" } else { "NO" }
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Vote, weighted by stake, to void a market whose resolution is overdue by more than
+    /// RESOLUTION_GRACE_PERIOD. At VOID_SUPERMAJORITY_BPS of bettor stake the market is voided,
+    /// stakes become refundable and the creator's bond is forfeited.
+    pub fn vote_void(ctx: Context<VoteVoid>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let participant = &mut ctx.accounts.participant;
+        let clock = Clock::get()?;
+
+        require!(
+            market.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        require!(
+            clock.unix_timestamp
+                > market
+                    .resolvable_at()
+                    .saturating_add(RESOLUTION_GRACE_PERIOD),
+            PredictDuelError::ResolutionNotOverdue
+        );
+        require!(
+            participant.stake > 0 && !participant.claimed,
+            PredictDuelError::Unauthorized
         );
+        require!(!participant.voted_void, PredictDuelError::AlreadyVoted);
+
+        participant.voted_void = true;
+        market.void_votes += participant.stake;
+
+        if market.void_vote_passed() {
+            market.status = MarketStatus::Voided;
+            market.settled_at = clock.unix_timestamp;
+            msg!("Market voided by its bettors: stakes will be refunded");
+        } else {
+            msg!(
+                "Void vote recorded: {} of {} SOL",
+                market.void_votes as f64 / 1_000_000_000.0,
+                market.bettor_stake() as f64 / 1_000_000_000.0
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Refund participants if market is cancelled
+    pub fn refund_stake(ctx: Context<RefundStake>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let participant = &mut ctx.accounts.participant;
+
+        require!(market.is_refundable(), PredictDuelError::MarketNotCancelled);
+        require!(!participant.claimed, PredictDuelError::AlreadyClaimed);
+        require!(market.lst_tokens == 0, PredictDuelError::LstNotUnwound);
+        participant.assert_settles_locally()?;
 
         let refund_amount = participant.stake;
 
@@ -326,240 +1162,19067 @@ pub mod predict_duel {
 
         Ok(())
     }
-}
 
-// Account validation structs
-#[derive(Accounts)]
-#[instruction(market_index: u64)]
-pub struct CreateMarket<'info> {
-    #[account(
-        init,
-        payer = creator,
-        space = 8 + 32 + 8 + (4 + 200) + 1 + 8 + 8 + 1 + 1 + 8 + 4 + 4 + 8 + 8 + 4 + 1 + 8 + 1 + 1,
-        seeds = [
-            b"market",
-            creator.key().as_ref(),
-            &market_index.to_le_bytes()
-        ],
-        bump
-    )]
-    pub market: Account<'info, Market>,
-    
-    #[account(mut)]
-    pub creator: Signer<'info>,
-    
-    /// PDA vault that holds all stakes - no data, just lamports
-    /// CHECK: PDA validated via seeds, will be created automatically on first transfer
-    /// PDA is owned by this program (required for PDAs), but only holds lamports
-    #[account(
-        seeds = [
-            b"market_vault",
-            creator.key().as_ref(),
-            &market_index.to_le_bytes()
-        ],
-        bump
-    )]
-    pub market_vault: UncheckedAccount<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    /// Read the time-weighted implied YES probability (returned via return data for CPI callers)
+    pub fn get_implied_twap(ctx: Context<GetImpliedTwap>) -> Result<TwapQuote> {
+        let market = &ctx.accounts.market;
+        let clock = Clock::get()?;
 
-#[derive(Accounts)]
-pub struct PlaceBet<'info> {
-    #[account(mut)]
-    pub market: Account<'info, Market>,
-    
-    #[account(
-        init_if_needed,
-        payer = bettor,
-        space = 8 + 32 + 32 + 1 + 8 + 1 + 1,
-        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
-        bump
-    )]
-    pub participant: Account<'info, Participant>,
-    
-    #[account(mut)]
-    pub bettor: Signer<'info>,
-    
-    /// PDA vault that holds all stakes
-    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
-    #[account(
-        mut,
-        seeds = [
-            b"market_vault",
-            market.creator.as_ref(),
-            &market.market_index.to_le_bytes()
-        ],
-        bump
-    )]
-    pub market_vault: UncheckedAccount<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+        let quote = TwapQuote {
+            twap_yes_prob_bps: market.twap_bps(clock.unix_timestamp),
+            spot_yes_prob_bps: market.implied_yes_prob_bps(),
+            window_start: market.created_at,
+            last_update: market.twap_last_update,
+            staleness_secs: clock.unix_timestamp.saturating_sub(market.twap_last_update),
+        };
 
-#[derive(Accounts)]
-pub struct ResolveMarket<'info> {
-    #[account(mut)]
-    pub market: Account<'info, Market>,
-    
-    pub resolver: Signer<'info>,
-}
+        msg!(
+            "Implied YES TWAP: {} bps (spot {} bps, last update {}s ago)",
+            quote.twap_yes_prob_bps,
+            quote.spot_yes_prob_bps,
+            quote.staleness_secs
+        );
 
-#[derive(Accounts)]
-pub struct ClaimWinnings<'info> {
-    #[account(mut)]
-    pub market: Account<'info, Market>,
-    
-    #[account(
-        mut,
-        seeds = [b"participant", market.key().as_ref(), winner.key().as_ref()],
-        bump
-    )]
-    pub participant: Account<'info, Participant>,
-    
-    #[account(mut)]
-    pub winner: Signer<'info>,
-    
-    /// PDA vault that holds all stakes
-    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
-    #[account(
-        mut,
-        seeds = [
-            b"market_vault",
-            market.creator.as_ref(),
-            &market.market_index.to_le_bytes()
-        ],
-        bump
-    )]
-    pub market_vault: UncheckedAccount<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+        Ok(quote)
+    }
 
-#[derive(Accounts)]
-pub struct CancelMarket<'info> {
-    #[account(mut)]
-    pub market: Account<'info, Market>,
-    
-    pub creator: Signer<'info>,
-}
+    /// Seed both YES and NO pools with creator liquidity before betting opens
+    pub fn seed_liquidity(
+        ctx: Context<SeedLiquidity>,
+        yes_amount: u64,
+        no_amount: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
 
-#[derive(Accounts)]
-pub struct RefundStake<'info> {
-    #[account(mut)]
-    pub market: Account<'info, Market>,
-    
-    #[account(
-        mut,
-        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
-        bump
-    )]
-    pub participant: Account<'info, Participant>,
-    
-    #[account(mut)]
-    pub bettor: Signer<'info>,
-    
-    /// PDA vault that holds all stakes
-    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
-    #[account(
-        mut,
-        seeds = [
-            b"market_vault",
-            market.creator.as_ref(),
-            &market.market_index.to_le_bytes()
-        ],
-        bump
-    )]
-    pub market_vault: UncheckedAccount<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+        // Seeding is only allowed at creation time, before anyone has bet
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            clock.unix_timestamp < market.deadline,
+            PredictDuelError::MarketExpired
+        );
+        require!(
+            market.seed_yes == 0 && market.seed_no == 0,
+            PredictDuelError::AlreadySeeded
+        );
+        require!(
+            market.settlement_mode == SettlementMode::Parimutuel,
+            PredictDuelError::InvalidSettlementMode
+        );
+        require!(
+            yes_amount >= market.min_stake && no_amount >= market.min_stake,
+            PredictDuelError::StakeTooLow
+        );
 
-// State structs
-#[account]
-pub struct Market {
-    pub creator: Pubkey,
-    pub market_index: u64,
-    pub question: String,
-    pub category: MarketCategory,
-    pub stake_amount: u64,
-    pub deadline: i64,
-    pub market_type: MarketType,
-    pub status: MarketStatus,
-    pub pool_size: u64,
-    pub yes_count: u32,
-    pub no_count: u32,
-    pub yes_pool: u64,
-    pub no_pool: u64,
-    pub total_participants: u32,
-    pub outcome: Option<bool>,
-    pub created_at: i64,
-    pub bump: u8,
-    pub vault_bump: u8,
-}
+        let total_seed = yes_amount
+            .checked_add(no_amount)
+            .ok_or(PredictDuelError::MathOverflow)?;
 
-#[account]
-pub struct Participant {
-    pub market: Pubkey,
-    pub bettor: Pubkey,
-    pub prediction: bool,
-    pub stake: u64,
-    pub claimed: bool,
-    pub bump: u8,
-}
+        // Transfer seed from creator to market vault
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.market_vault.to_account_info(),
+                },
+            ),
+            total_seed,
+        )?;
 
-// Enums
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum MarketCategory {
-    Crypto,
-    Weather,
-    Sports,
-    Meme,
-    Local,
-    Other,
-}
+        market.add_seed(yes_amount, no_amount, clock.unix_timestamp);
+        ctx.accounts
+            .odds_history
+            .record(clock.unix_timestamp, market.yes_pool, market.no_pool);
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum MarketType {
-    Public,
-    Challenge,
-}
+        msg!(
+            "Market seeded: {} SOL YES / {} SOL NO",
+            yes_amount as f64 / 1_000_000_000.0,
+            no_amount as f64 / 1_000_000_000.0
+        );
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum MarketStatus {
-    Pending,
-    Active,
+        Ok(())
+    }
+
+    /// Create an event that related markets can attach to and resolve from
+    pub fn create_event(ctx: Context<CreateEvent>, event_index: u64, name: String) -> Result<()> {
+        let event = &mut ctx.accounts.event;
+
+        require!(name.len() <= 64, PredictDuelError::EventNameTooLong);
+
+        event.authority = ctx.accounts.authority.key();
+        event.event_index = event_index;
+        event.name = name;
+        event.market_count = 0;
+        event.resolved_market_count = 0;
+        event.total_volume = 0;
+        event.result = None;
+        event.resolved_at = 0;
+        event.bump = ctx.bumps.event;
+
+        msg!("Event created: {}", event.name);
+
+        Ok(())
+    }
+
+    /// Attach a market to an event; its outcome will be derived from the event result (before any bets)
+    pub fn attach_to_event(ctx: Context<AttachToEvent>, condition: EventCondition) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let event = &mut ctx.accounts.event;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            market.event.is_none() && market.spread_x10.is_none(),
+            PredictDuelError::EventMarket
+        );
+        require!(
+            event.result.is_none(),
+            PredictDuelError::EventAlreadyResolved
+        );
+
+        market.event = Some(event.key());
+        market.event_condition = Some(condition);
+        event.market_count += 1;
+
+        msg!("Market attached to event: {}", event.name);
+
+        Ok(())
+    }
+
+    /// Record the event's single result entry (event authority only)
+    pub fn resolve_event(ctx: Context<ResolveEvent>, primary: i64, secondary: i64) -> Result<()> {
+        let event = &mut ctx.accounts.event;
+        let clock = Clock::get()?;
+
+        require!(
+            event.result.is_none(),
+            PredictDuelError::EventAlreadyResolved
+        );
+
+        event.result = Some(EventResult { primary, secondary });
+        event.resolved_at = clock.unix_timestamp;
+
+        msg!(
+            "Event resolved: {} ({} / {})",
+            event.name,
+            primary,
+            secondary
+        );
+
+        Ok(())
+    }
+
+    /// Permissionlessly settle an attached market from its event's result; the caller is
+    /// recorded as its resolver and the resolution is credited like a hand-resolved one
+    pub fn resolve_from_event(ctx: Context<ResolveFromEvent>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let event = &mut ctx.accounts.event;
+        let clock = Clock::get()?;
+        market.clear_closing_auction(clock.unix_timestamp)?;
+
+        require!(
+            market.event == Some(event.key()),
+            PredictDuelError::EventMismatch
+        );
+        require!(
+            market.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        require!(
+            market.deadline_reached(&clock),
+            PredictDuelError::MarketNotExpired
+        );
+
+        let result = event
+            .result
+            .clone()
+            .ok_or(PredictDuelError::EventNotResolved)?;
+        let condition = market
+            .event_condition
+            .clone()
+            .ok_or(PredictDuelError::EventMismatch)?;
+
+        event.resolved_market_count += 1;
+        event.total_volume += market.pool_size;
+
+        match condition.evaluate(&result) {
+            Some(outcome) => {
+                market.status = MarketStatus::Resolved;
+                market.resolved_at = clock.unix_timestamp;
+                market.settled_at = clock.unix_timestamp;
+                market.outcome = Some(outcome);
+                market.resolved_by = Some(ctx.accounts.resolver.key());
+                market.credit_resolution(
+                    &mut ctx.accounts.creator_profile,
+                    ctx.accounts
+                        .resolver_profile
+                        .as_deref_mut()
+                        .map(|profile| &mut **profile),
+                )?;
+                msg!(
+                    "Market resolved from event: Outcome is {}",
+                    if outcome { "YES" } else { "NO" }
+                );
+            }
+            None => {
+                market.status = MarketStatus::Voided;
+                market.settled_at = clock.unix_timestamp;
+                msg!("Event result landed on the line: stakes will be refunded");
+            }
+        }
+        if let Some(category_stats) = ctx.accounts.category_stats.as_mut() {
+            market.sync_category_stats(category_stats);
+        }
+
+        Ok(())
+    }
+
+    /// Make a Sports market a handicap market: YES = home score + spread beats away score.
+    /// Spread is in tenths of a point, e.g. -35 for -3.5 (before any bets)
+    pub fn set_spread(ctx: Context<SetSpread>, spread_x10: i32) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            market.category == MarketCategory::Sports && market.event.is_none(),
+            PredictDuelError::NotSpreadMarket
+        );
+        require!(
+            spread_x10.unsigned_abs() <= MAX_SPREAD_X10,
+            PredictDuelError::InvalidSpread
+        );
+
+        market.spread_x10 = Some(spread_x10);
+
+        msg!("Spread set to {} tenths of a point", spread_x10);
+
+        Ok(())
+    }
+
+    /// Choose how the pot is settled; winner-takes-all is only for Challenge duels (before any bets)
+    pub fn set_settlement_mode(
+        ctx: Context<SetSettlementMode>,
+        mode: SettlementMode,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        // Raffles need a winner count (see enable_raffle) and books need odds and
+        // collateral (see open_book); a funded book can only be settled as one
+        require!(
+            mode != SettlementMode::Raffle
+                && mode != SettlementMode::FixedOdds
+                && market.book_collateral == 0,
+            PredictDuelError::InvalidSettlementMode
+        );
+        if mode == SettlementMode::WinnerTakesAll {
+            require!(
+                market.market_type == MarketType::Challenge,
+                PredictDuelError::InvalidSettlementMode
+            );
+            // Sealed sides and house seed don't fit a 1v1 pot
+            require!(
+                !market.sealed && market.pool_size == 0,
+                PredictDuelError::InvalidSettlementMode
+            );
+        }
+
+        market.settlement_mode = mode;
+
+        msg!("Settlement mode updated");
+
+        Ok(())
+    }
+
+    /// Act as the book: lock collateral and quote fixed decimal odds (in bps) for each side
+    pub fn open_book(
+        ctx: Context<OpenBook>,
+        yes_odds_bps: u32,
+        no_odds_bps: u32,
+        collateral: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            !market.sealed && market.pool_size == 0,
+            PredictDuelError::InvalidSettlementMode
+        );
+        require!(
+            valid_fixed_odds(yes_odds_bps) && valid_fixed_odds(no_odds_bps),
+            PredictDuelError::InvalidOdds
+        );
+        require!(collateral > 0, PredictDuelError::StakeTooLow);
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.market_vault.to_account_info(),
+                },
+            ),
+            collateral,
+        )?;
+
+        market.settlement_mode = SettlementMode::FixedOdds;
+        market.yes_odds_bps = yes_odds_bps;
+        market.no_odds_bps = no_odds_bps;
+        market.book_collateral = collateral;
+
+        msg!(
+            "Book opened: YES @ {} / NO @ {} bps, {} SOL collateral",
+            yes_odds_bps,
+            no_odds_bps,
+            collateral as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Re-quote the book's odds and optionally add collateral; existing bets keep their price
+    pub fn update_odds(
+        ctx: Context<UpdateOdds>,
+        yes_odds_bps: u32,
+        no_odds_bps: u32,
+        additional_collateral: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.creator.key() == market.creator,
+            PredictDuelError::NotMarketCreator
+        );
+        require!(
+            market.settlement_mode == SettlementMode::FixedOdds,
+            PredictDuelError::InvalidSettlementMode
+        );
+        require!(
+            market.status == MarketStatus::Pending || market.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        require!(
+            clock.unix_timestamp < market.deadline,
+            PredictDuelError::MarketExpired
+        );
+        require!(
+            valid_fixed_odds(yes_odds_bps) && valid_fixed_odds(no_odds_bps),
+            PredictDuelError::InvalidOdds
+        );
+
+        if additional_collateral > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: ctx.accounts.market_vault.to_account_info(),
+                    },
+                ),
+                additional_collateral,
+            )?;
+            market.book_collateral += additional_collateral;
+        }
+
+        market.yes_odds_bps = yes_odds_bps;
+        market.no_odds_bps = no_odds_bps;
+
+        msg!(
+            "Odds updated: YES @ {} / NO @ {} bps",
+            yes_odds_bps,
+            no_odds_bps
+        );
+
+        Ok(())
+    }
+
+    /// Book withdraws what is left after winners' locked payouts (or its collateral if refunded)
+    pub fn settle_book(ctx: Context<SettleBook>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(
+            ctx.accounts.creator.key() == market.creator,
+            PredictDuelError::NotMarketCreator
+        );
+        require!(
+            market.settlement_mode == SettlementMode::FixedOdds,
+            PredictDuelError::InvalidSettlementMode
+        );
+        require!(!market.book_settled, PredictDuelError::AlreadyClaimed);
+
+        let amount = market.book_surplus(Clock::get()?.unix_timestamp)?;
+
+        if amount > 0 {
+            let seeds = &[
+                b"market_vault",
+                market.creator.as_ref(),
+                &market.market_index.to_le_bytes(),
+                &[ctx.bumps.market_vault],
+            ];
+            let signer = &[&seeds[..]];
+
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.market_vault.to_account_info(),
+                        to: ctx.accounts.creator.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+
+        market.book_settled = true;
+
+        msg!(
+            "Book settled: {} SOL returned to creator",
+            amount as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Offer to lay an outcome: escrow a maximum liability that backers can match at fixed odds.
+    /// An offer with `expires_at` stops matching at that time and can then be purged by anyone;
+    /// without one it stands until betting closes.
+    pub fn offer_lay(
+        ctx: Context<OfferLay>,
+        outcome: bool,
+        odds_bps: u32,
+        liability: u64,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let offer = &mut ctx.accounts.lay_offer;
+        let clock = Clock::get()?;
+
+        market.assert_accepting_stake(liability, clock.unix_timestamp)?;
+        require!(valid_fixed_odds(odds_bps), PredictDuelError::InvalidOdds);
+        if let Some(expires_at) = expires_at {
+            require!(
+                expires_at > clock.unix_timestamp && expires_at <= market.betting_closes_at(),
+                PredictDuelError::InvalidOrderExpiry
+            );
+        }
+        verify_attestation(
+            &ctx.accounts.config,
+            &ctx.accounts.layer.key(),
+            ctx.accounts.instructions.as_deref(),
+            clock.unix_timestamp,
+        )?;
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.layer.to_account_info(),
+                    to: ctx.accounts.market_vault.to_account_info(),
+                },
+            ),
+            liability,
+        )?;
+
+        offer.market = market.key();
+        offer.layer = ctx.accounts.layer.key();
+        offer.outcome = outcome;
+        offer.odds_bps = odds_bps;
+        offer.liability = liability;
+        offer.matched_liability = 0;
+        offer.matched_stake = 0;
+        offer.settled = false;
+        offer.taker_fee_bps = ctx.accounts.config.taker_fee_bps;
+        offer.maker_rebate_bps = ctx.accounts.config.maker_rebate_bps;
+        offer.rebates_accrued = 0;
+        offer.expires_at = expires_at;
+        offer.fill_count = 0;
+        offer.bump = ctx.bumps.lay_offer;
+
+        msg!(
+            "Lay offered against {} @ {} bps, liability {} SOL",
+            if outcome { "YES" } else { "NO" },
+            odds_bps,
+            liability as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Back an outcome against a lay offer, matching part of its liability. A stake larger
+    /// than the offer's remaining liability covers is partially filled: only the part that
+    /// fits is taken.
+    pub fn take_lay(ctx: Context<TakeLay>, stake_amount: u64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let offer = &mut ctx.accounts.lay_offer;
+        let position = &mut ctx.accounts.back_position;
+        let clock = Clock::get()?;
+
+        market.assert_accepting_stake(stake_amount, clock.unix_timestamp)?;
+        require!(
+            !offer.is_expired(clock.unix_timestamp, market.betting_closes_at()),
+            PredictDuelError::OrderExpired
+        );
+        verify_attestation(
+            &ctx.accounts.config,
+            &ctx.accounts.backer.key(),
+            ctx.accounts.instructions.as_deref(),
+            clock.unix_timestamp,
+        )?;
+
+        // Fill as much of the stake as the remaining liability covers
+        let odds_profit = (offer.odds_bps as u64 - BPS_DENOMINATOR) as u128;
+        let fillable =
+            (offer.remaining_liability() as u128 * BPS_DENOMINATOR as u128 / odds_profit) as u64;
+        let filled = stake_amount.min(fillable);
+        require!(
+            filled >= market.min_stake,
+            PredictDuelError::InsufficientLayLiability
+        );
+
+        // Layer's liability for this stake: what the backer wins on top of their stake
+        let needed = ((filled as u128)
+            .checked_mul(odds_profit)
+            .ok_or(PredictDuelError::MathOverflow)?
+            / BPS_DENOMINATOR as u128) as u64;
+
+        // Taker fee on top of the stake; the maker's rebate waits in the vault for claim_rebates
+        let taker_fee =
+            (filled as u128 * offer.taker_fee_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        let rebate =
+            (filled as u128 * offer.maker_rebate_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.backer.to_account_info(),
+                    to: ctx.accounts.market_vault.to_account_info(),
+                },
+            ),
+            filled + rebate,
+        )?;
+        if taker_fee > rebate {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.backer.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                taker_fee - rebate,
+            )?;
+        }
+
+        if position.offer == Pubkey::default() {
+            position.offer = offer.key();
+            position.backer = ctx.accounts.backer.key();
+            position.claimed = false;
+            position.bump = ctx.bumps.back_position;
+        }
+        position.stake += filled;
+        position.matched_liability += needed;
+
+        offer.matched_stake += filled;
+        offer.matched_liability += needed;
+        offer.rebates_accrued += rebate;
+        offer.fill_count += 1;
+        market.last_bet_at = clock.unix_timestamp;
+
+        if market.status == MarketStatus::Pending {
+            market.status = MarketStatus::Active;
+        }
+
+        msg!(
+            "Lay matched: {} SOL backed against {} SOL liability",
+            filled as f64 / 1_000_000_000.0,
+            needed as f64 / 1_000_000_000.0
+        );
+
+        emit!(LayFilled {
+            market: market.key(),
+            offer: offer.key(),
+            backer: ctx.accounts.backer.key(),
+            stake: filled,
+            liability: needed,
+            remaining_liability: offer.remaining_liability(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw the unmatched part of a lay offer's liability
+    pub fn cancel_lay(ctx: Context<CancelLay>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let offer = &mut ctx.accounts.lay_offer;
+
+        require!(!offer.settled, PredictDuelError::AlreadyClaimed);
+
+        let amount = offer.remaining_liability();
+        require!(amount > 0, PredictDuelError::NothingToClaim);
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.layer.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        offer.liability = offer.matched_liability;
+
+        msg!(
+            "Lay offer reduced by {} SOL",
+            amount as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Return the unmatched liability of an expired lay offer to its layer (permissionless),
+    /// so keepers can clear stale quotes from the book
+    pub fn purge_expired_lay(ctx: Context<PurgeExpiredLay>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let offer = &mut ctx.accounts.lay_offer;
+
+        require!(
+            offer.is_expired(Clock::get()?.unix_timestamp, market.betting_closes_at()),
+            PredictDuelError::OrderNotExpired
+        );
+        require!(!offer.settled, PredictDuelError::AlreadyClaimed);
+
+        let amount = offer.remaining_liability();
+        require!(amount > 0, PredictDuelError::NothingToClaim);
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.layer.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        offer.liability = offer.matched_liability;
+
+        msg!(
+            "Expired lay offer purged: {} SOL returned to layer",
+            amount as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Settle a lay offer for the layer once the market is resolved or refunded
+    pub fn settle_lay_offer(ctx: Context<SettleLayOffer>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let offer = &mut ctx.accounts.lay_offer;
+
+        let amount = offer.layer_settlement(market, Clock::get()?.unix_timestamp)?;
+
+        if amount > 0 {
+            let seeds = &[
+                b"market_vault",
+                market.creator.as_ref(),
+                &market.market_index.to_le_bytes(),
+                &[ctx.bumps.market_vault],
+            ];
+            let signer = &[&seeds[..]];
+
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.market_vault.to_account_info(),
+                        to: ctx.accounts.layer.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+
+        offer.settled = true;
+
+        msg!(
+            "Lay offer settled: {} SOL to layer",
+            amount as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw the maker rebates a lay offer has accrued from matched stakes
+    pub fn claim_rebates(ctx: Context<ClaimRebates>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let offer = &mut ctx.accounts.lay_offer;
+
+        let amount = offer.rebates_accrued;
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.layer.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        offer.rebates_accrued = 0;
+
+        msg!(
+            "Maker rebates claimed: {} SOL",
+            amount as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Claim a backed position: stake plus matched liability if the backed outcome won
+    pub fn claim_back_position(ctx: Context<ClaimBackPosition>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let offer = &ctx.accounts.lay_offer;
+        let position = &mut ctx.accounts.back_position;
+
+        require!(!position.claimed, PredictDuelError::AlreadyClaimed);
+
+        let amount = position.settlement(offer, market, Clock::get()?.unix_timestamp)?;
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.backer.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        position.claimed = true;
+
+        msg!(
+            "Back position claimed: {} SOL",
+            amount as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Weight payouts towards early bettors: a bet at creation earns `bonus_bps` extra weight,
+    /// decaying linearly to none at the deadline (before any bets)
+    pub fn configure_early_bonus(ctx: Context<ConfigureEarlyBonus>, bonus_bps: u16) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            bonus_bps <= MAX_EARLY_BONUS_BPS,
+            PredictDuelError::InvalidEarlyBonus
+        );
+
+        market.early_bonus_bps = bonus_bps;
+
+        msg!("Early bettor bonus set to {} bps", bonus_bps);
+
+        Ok(())
+    }
+
+    /// Cut the payout weight of bets placed in the final `window_bps` of the market's lifetime
+    /// by `discount_bps`, so late bettors can't free-ride on a near-certain outcome
+    /// (creator only, before any bets)
+    pub fn configure_late_discount(
+        ctx: Context<ConfigureLateDiscount>,
+        window_bps: u16,
+        discount_bps: u16,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            window_bps as u64 <= BPS_DENOMINATOR
+                && discount_bps <= MAX_LATE_DISCOUNT_BPS
+                && (window_bps == 0) == (discount_bps == 0),
+            PredictDuelError::InvalidLateDiscount
+        );
+
+        market.late_window_bps = window_bps;
+        market.late_discount_bps = discount_bps;
+
+        msg!(
+            "Late-entry discount: {} bps off bets in the final {} bps of the market",
+            discount_bps,
+            window_bps
+        );
+
+        Ok(())
+    }
+
+    /// Turn the market into a sealed commit-reveal market (before any bets)
+    pub fn enable_sealed_bets(ctx: Context<EnableSealedBets>, reveal_window: i64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            market.settlement_mode == SettlementMode::Parimutuel,
+            PredictDuelError::InvalidSettlementMode
+        );
+        require!(
+            reveal_window > 0 && reveal_window <= DEFAULT_REVEAL_WINDOW * 7,
+            PredictDuelError::InvalidRevealWindow
+        );
+        require!(
+            market.personhood_mint.is_none() && market.auction_window == 0,
+            PredictDuelError::SealedMarket
+        );
+
+        market.sealed = true;
+        market.reveal_window = reveal_window;
+
+        msg!("Sealed bets enabled: reveal window {}s", reveal_window);
+
+        Ok(())
+    }
+
+    /// Stop betting `cutoff_secs` before the deadline (e.g. at kickoff) while resolution still
+    /// waits for the deadline itself; creator only, before any bets
+    pub fn set_betting_cutoff(ctx: Context<SetBettingCutoff>, cutoff_secs: i64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            cutoff_secs >= 0
+                && Clock::get()?.unix_timestamp
+                    < market.deadline - cutoff_secs - market.auction_window,
+            PredictDuelError::InvalidBettingCutoff
+        );
+
+        market.betting_cutoff_secs = cutoff_secs;
+
+        msg!("Betting closes {}s before the deadline", cutoff_secs);
+
+        Ok(())
+    }
+
+    /// Run a closing auction over the last `window` seconds before betting closes (before any
+    /// bets): bets placed then all get the same payout weight and only reach the pools,
+    /// and so the odds, once the auction clears
+    pub fn set_closing_auction(ctx: Context<SetClosingAuction>, window: i64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            market.settlement_mode == SettlementMode::Parimutuel,
+            PredictDuelError::InvalidSettlementMode
+        );
+        require!(!market.sealed, PredictDuelError::SealedMarket);
+        require!(
+            window > 0
+                && window <= MAX_CLOSING_AUCTION
+                && Clock::get()?.unix_timestamp < market.betting_closes_at() - window,
+            PredictDuelError::InvalidAuctionWindow
+        );
+
+        market.auction_window = window;
+
+        msg!("Closing auction set for the final {}s", window);
+
+        Ok(())
+    }
+
+    /// Clear a market's closing auction once betting has closed (permissionless);
+    /// resolution clears it too
+    pub fn clear_closing_auction(ctx: Context<ClearClosingAuction>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            now >= market.betting_closes_at(),
+            PredictDuelError::MarketNotExpired
+        );
+        let (yes, no) = (market.auction_yes, market.auction_no);
+        market.clear_closing_auction(now)?;
+
+        msg!(
+            "Closing auction cleared: {} SOL on YES, {} SOL on NO",
+            yes as f64 / 1_000_000_000.0,
+            no as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Commit a hidden bet on a sealed market: the stake is public, the side is not
+    pub fn commit_bet(
+        ctx: Context<CommitBet>,
+        commitment: [u8; 32],
+        stake_amount: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let participant = &mut ctx.accounts.participant;
+        let clock = Clock::get()?;
+
+        market.assert_accepting_commitment(stake_amount, clock.unix_timestamp)?;
+        verify_attestation(
+            &ctx.accounts.config,
+            &ctx.accounts.bettor.key(),
+            ctx.accounts.instructions.as_deref(),
+            clock.unix_timestamp,
+        )?;
+
+        let user_profile = &mut ctx.accounts.user_profile;
+        user_profile.wallet = ctx.accounts.bettor.key();
+        user_profile.bump = ctx.bumps.user_profile;
+        user_profile.record_bet(&ctx.accounts.config, clock.slot, clock.unix_timestamp)?;
+        user_profile.record_volume(stake_amount, clock.unix_timestamp);
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.bettor.to_account_info(),
+                    to: ctx.accounts.market_vault.to_account_info(),
+                },
+            ),
+            stake_amount,
+        )?;
+
+        participant.market = market.key();
+        participant.bettor = ctx.accounts.bettor.key();
+        participant.prediction = false;
+        participant.stake = stake_amount;
+        participant.claimed = false;
+        participant.bump = ctx.bumps.participant;
+        participant.commitment = commitment;
+        participant.revealed = false;
+        participant.weight = market.entry_weight(stake_amount, clock.unix_timestamp)?;
+        participant.last_entry_at = clock.unix_timestamp;
+
+        // Stake joins the pot now; it only joins a side once revealed
+        market.pool_size += stake_amount;
+        market.total_participants += 1;
+        market.last_bet_at = clock.unix_timestamp;
+
+        if market.status == MarketStatus::Pending {
+            market.status = MarketStatus::Active;
+        }
+
+        msg!(
+            "Sealed bet committed: {} SOL",
+            stake_amount as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Reveal a sealed bet after the deadline; unrevealed stakes are forfeited to winners
+    pub fn reveal_bet(ctx: Context<RevealBet>, prediction: bool, salt: [u8; 32]) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let participant = &mut ctx.accounts.participant;
+        let clock = Clock::get()?;
+
+        require!(market.sealed, PredictDuelError::NotSealedMarket);
+        require!(
+            market.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        require!(
+            clock.unix_timestamp >= market.deadline,
+            PredictDuelError::MarketNotExpired
+        );
+        require!(
+            clock.unix_timestamp < market.resolvable_at(),
+            PredictDuelError::RevealWindowClosed
+        );
+        require!(!participant.revealed, PredictDuelError::AlreadyRevealed);
+
+        let expected = bet_commitment(&market.key(), &participant.bettor, prediction, &salt);
+        require!(
+            expected == participant.commitment,
+            PredictDuelError::CommitmentMismatch
+        );
+
+        participant.prediction = prediction;
+        participant.revealed = true;
+
+        market.accrue_twap(clock.unix_timestamp);
+        if prediction {
+            market.yes_count += 1;
+            market.yes_pool += participant.stake;
+            market.yes_weight += participant.weight;
+        } else {
+            market.no_count += 1;
+            market.no_pool += participant.stake;
+            market.no_weight += participant.weight;
+        }
+        market.sync_implied_prob();
+
+        msg!(
+            "Sealed bet revealed: {} SOL on {}",
+            participant.stake as f64 / 1_000_000_000.0,
+            if prediction { "YES" } else { "NO" }
+        );
+
+        Ok(())
+    }
+
+    /// Exit a position before the deadline, leaving a small fee in the pool
+    pub fn cash_out(ctx: Context<CashOut>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let participant = &ctx.accounts.participant;
+
+        let clock = Clock::get()?;
+        let refund_amount = market.remove_position(participant, &clock)?;
+        ctx.accounts
+            .odds_history
+            .record(clock.unix_timestamp, market.yes_pool, market.no_pool);
+        assert_claim_co_signed(
+            &ctx.accounts.profile_guard,
+            ctx.accounts.co_signer.as_ref(),
+            refund_amount,
+        )?;
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.bettor.to_account_info(),
+                },
+                signer,
+            ),
+            refund_amount,
+        )?;
+
+        msg!("Cashed out: {} SOL", refund_amount as f64 / 1_000_000_000.0);
+
+        Ok(())
+    }
+
+    /// Register a stop-loss style exit that a keeper can trigger on implied probability.
+    /// Keepers can't produce the bettor's co-signature, so an order that may pay out above
+    /// the profile's co-sign threshold is co-signed here instead.
+    pub fn register_exit(
+        ctx: Context<RegisterExit>,
+        trigger_yes_prob_bps: u16,
+        trigger_above: bool,
+    ) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let exit_order = &mut ctx.accounts.exit_order;
+
+        require!(
+            market.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        require!(
+            trigger_yes_prob_bps > 0 && (trigger_yes_prob_bps as u64) < BPS_DENOMINATOR,
+            PredictDuelError::InvalidExitTrigger
+        );
+
+        exit_order.market = market.key();
+        exit_order.participant = ctx.accounts.participant.key();
+        exit_order.bettor = ctx.accounts.bettor.key();
+        exit_order.trigger_yes_prob_bps = trigger_yes_prob_bps;
+        exit_order.trigger_above = trigger_above;
+        exit_order.bump = ctx.bumps.exit_order;
+        exit_order.co_signed = ctx.accounts.co_signer.is_some();
+        if exit_order.co_signed {
+            assert_claim_co_signed(
+                &ctx.accounts.profile_guard,
+                ctx.accounts.co_signer.as_ref(),
+                u64::MAX,
+            )?;
+        }
+
+        msg!(
+            "Exit registered: when implied YES probability is {} {} bps",
+            if trigger_above { "above" } else { "below" },
+            trigger_yes_prob_bps
+        );
+
+        Ok(())
+    }
+
+    /// Cancel a registered exit order and reclaim its rent
+    pub fn cancel_exit(_ctx: Context<CancelExit>) -> Result<()> {
+        msg!("Exit order cancelled");
+
+        Ok(())
+    }
+
+    /// Keeper-triggered cash-out once the registered exit condition is met
+    pub fn execute_exit(ctx: Context<ExecuteExit>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let participant = &ctx.accounts.participant;
+        let exit_order = &ctx.accounts.exit_order;
+
+        let implied_yes = market.implied_yes_prob_bps();
+        let triggered = if exit_order.trigger_above {
+            implied_yes >= exit_order.trigger_yes_prob_bps
+        } else {
+            implied_yes <= exit_order.trigger_yes_prob_bps
+        };
+        require!(triggered, PredictDuelError::ExitNotTriggered);
+
+        let clock = Clock::get()?;
+        let refund_amount = market.remove_position(participant, &clock)?;
+        ctx.accounts
+            .odds_history
+            .record(clock.unix_timestamp, market.yes_pool, market.no_pool);
+        if !exit_order.co_signed {
+            assert_claim_co_signed(&ctx.accounts.profile_guard, None, refund_amount)?;
+        }
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.bettor.to_account_info(),
+                },
+                signer,
+            ),
+            refund_amount,
+        )?;
+
+        msg!(
+            "Exit executed at {} bps: {} SOL returned",
+            implied_yes,
+            refund_amount as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Configure the vesting schedule applied to large payouts (before any bets); a zero
+    /// duration turns vesting off and pays every win as a lump sum
+    pub fn configure_vesting(
+        ctx: Context<ConfigureVesting>,
+        threshold: u64,
+        duration: i64,
+        mandatory: bool,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            (0..=MAX_VESTING_DURATION).contains(&duration) && (duration > 0 || !mandatory),
+            PredictDuelError::InvalidVestingDuration
+        );
+
+        market.vesting_threshold = threshold;
+        market.vesting_duration = duration;
+        market.vesting_mandatory = mandatory;
+
+        msg!(
+            "Vesting configured: payouts above {} SOL vest over {}s (mandatory: {})",
+            threshold as f64 / 1_000_000_000.0,
+            duration,
+            mandatory
+        );
+
+        Ok(())
+    }
+
+    /// Convert a large winning payout into a linear vesting schedule instead of a lump sum.
+    /// Splits, donations and any boost bonus are paid as claim_winnings pays them; only the
+    /// winner's own share vests. Split recipients are passed as remaining accounts, in order.
+    pub fn start_vesting<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StartVesting<'info>>,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let participant = &mut ctx.accounts.participant;
+        let vesting = &mut ctx.accounts.vesting;
+        let clock = Clock::get()?;
+
+        participant.assert_settles_locally()?;
+
+        let payout = market.claim_payout(
+            &ctx.accounts.config,
+            participant,
+            ctx.accounts
+                .user_profile
+                .as_deref_mut()
+                .map(|user_profile| &mut **user_profile),
+            clock.unix_timestamp,
+        )?;
+        market.assert_vestable(payout)?;
+        assert_claim_co_signed(
+            &ctx.accounts.profile_guard,
+            ctx.accounts.co_signer.as_ref(),
+            payout,
+        )?;
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        let remainder = pay_claim_shares(
+            payout,
+            participant,
+            ctx.remaining_accounts,
+            ctx.accounts
+                .user_profile
+                .as_deref_mut()
+                .map(|user_profile| &mut **user_profile),
+            ctx.accounts
+                .charity
+                .as_ref()
+                .map(|charity| charity.to_account_info()),
+            ctx.accounts.market_vault.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            signer,
+        )?;
+        // The bonus comes from the booster fund, not the vault, so there is nothing to vest
+        pay_boost_bonus(
+            participant,
+            payout,
+            ctx.accounts.booster_fund.as_deref_mut(),
+            &ctx.accounts.winner.to_account_info(),
+        )?;
+
+        vesting.market = market.key();
+        vesting.beneficiary = ctx.accounts.winner.key();
+        vesting.total_amount = remainder;
+        vesting.released_amount = 0;
+        vesting.start_ts = clock.unix_timestamp;
+        vesting.duration = market.vesting_duration;
+        vesting.bump = ctx.bumps.vesting;
+
+        // The position is settled into the schedule; claim_winnings can no longer pay it out
+        participant.claimed = true;
+        market.vesting_outstanding += remainder;
+
+        msg!(
+            "Vesting started: {} SOL over {}s",
+            remainder as f64 / 1_000_000_000.0,
+            vesting.duration
+        );
+
+        Ok(())
+    }
+
+    /// Release the currently vested portion of a payout
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let vesting = &mut ctx.accounts.vesting;
+        let clock = Clock::get()?;
+
+        let amount = vesting.releasable(clock.unix_timestamp)?;
+        require!(amount > 0, PredictDuelError::NothingToClaim);
+        assert_claim_co_signed(
+            &ctx.accounts.profile_guard,
+            ctx.accounts.co_signer.as_ref(),
+            amount,
+        )?;
+
+        let vault_balance = ctx.accounts.market_vault.lamports();
+        require!(vault_balance >= amount, PredictDuelError::MarketNotActive);
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.beneficiary.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        vesting.released_amount += amount;
+        market.vesting_outstanding -= amount;
+
+        msg!(
+            "Vested winnings claimed: {} SOL ({} of {} released)",
+            amount as f64 / 1_000_000_000.0,
+            vesting.released_amount,
+            vesting.total_amount
+        );
+
+        Ok(())
+    }
+
+    /// Claim the creator's seed back: winning-side share if resolved, full seed if cancelled
+    pub fn claim_seed(ctx: Context<ClaimSeed>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(
+            ctx.accounts.creator.key() == market.creator,
+            PredictDuelError::NotMarketCreator
+        );
+        require!(!market.seed_claimed, PredictDuelError::AlreadyClaimed);
+
+        let amount = market.seed_payout(Clock::get()?.unix_timestamp)?;
+
+        require!(amount > 0, PredictDuelError::NotAWinner);
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.creator.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        market.seed_claimed = true;
+
+        msg!("Seed claimed: {} SOL", amount as f64 / 1_000_000_000.0);
+
+        Ok(())
+    }
+
+    /// Initialize the global config; only the program's upgrade authority can become admin
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        config.admin = ctx.accounts.admin.key();
+        config.bump = ctx.bumps.config;
+        config.moderator = ctx.accounts.admin.key();
+        config.protocol_fee_bps = 0;
+        config.treasury = ctx.accounts.admin.key();
+        config.min_stake = DEFAULT_MIN_STAKE;
+        config.dispute_window = DEFAULT_DISPUTE_WINDOW;
+        config.governance_mint = Pubkey::default();
+        config.proposal_count = 0;
+        config.pending_admin = Pubkey::default();
+        // Changing it later is itself a timelocked action
+        config.timelock_delay = DEFAULT_TIMELOCK_DELAY;
+        config.queued_count = 0;
+        config.attestation_verifier = Pubkey::default();
+        config.attestation_max_age = 0;
+        config.max_bets_per_slot = 0;
+        config.max_bets_per_minute = 0;
+        config.fee_tiers = [FeeTier::default(); FEE_TIER_COUNT];
+        config.min_creator_score_bps = 0;
+        config.low_score_max_pool = 0;
+        config.creation_bond = DEFAULT_CREATION_BOND;
+        config.creation_fee = 0;
+        config.creator_pass_mint = None;
+        config.fee_waiver_score_bps = 0;
+        config.flag_stake = DEFAULT_FLAG_STAKE;
+        config.flag_threshold = DEFAULT_FLAG_THRESHOLD;
+        config.category_params = [CategoryParams::default(); CATEGORY_COUNT];
+        config.lst_stake_pool = Pubkey::default();
+        config.charity = Pubkey::default();
+        config.taker_fee_bps = 0;
+        config.maker_rebate_bps = 0;
+        config.frontend_fee_share_bps = 0;
+        config.claim_delay = DEFAULT_DISPUTE_WINDOW;
+
+        msg!("Config initialized with admin {}", config.admin);
+
+        Ok(())
+    }
+
+    /// Register an oracle feed account under a human-readable asset name (admin only), with the
+    /// max age its settling update may have past expiry (0 = the provider default)
+    pub fn register_oracle_feed(
+        ctx: Context<RegisterOracleFeed>,
+        provider: OracleProvider,
+        asset_name: String,
+        max_age: i64,
+    ) -> Result<()> {
+        let oracle_feed = &mut ctx.accounts.oracle_feed;
+        let clock = Clock::get()?;
+
+        require!(asset_name.len() <= 32, PredictDuelError::AssetNameTooLong);
+        let max_age = provider.feed_max_age(max_age)?;
+        require!(
+            *ctx.accounts.feed.owner == provider.program_id(),
+            PredictDuelError::InvalidOracleFeed
+        );
+
+        oracle_feed.feed = ctx.accounts.feed.key();
+        oracle_feed.provider = provider;
+        oracle_feed.asset_name = asset_name;
+        oracle_feed.active = true;
+        oracle_feed.added_at = clock.unix_timestamp;
+        oracle_feed.max_age = max_age;
+        oracle_feed.bump = ctx.bumps.oracle_feed;
+
+        msg!(
+            "Oracle feed registered: {} -> {}",
+            oracle_feed.asset_name,
+            oracle_feed.feed
+        );
+
+        Ok(())
+    }
+
+    /// Enable or retire a registered feed (admin only); retired feeds can't back new markets
+    pub fn set_oracle_feed_active(ctx: Context<SetOracleFeedActive>, active: bool) -> Result<()> {
+        let oracle_feed = &mut ctx.accounts.oracle_feed;
+
+        oracle_feed.active = active;
+
+        msg!(
+            "Oracle feed {} {}",
+            oracle_feed.asset_name,
+            if active { "enabled" } else { "disabled" }
+        );
+
+        Ok(())
+    }
+
+    /// Set how long past expiry a feed's settling update may be published (admin only),
+    /// to match its heartbeat; 0 restores the provider default
+    pub fn set_oracle_feed_max_age(ctx: Context<SetOracleFeedMaxAge>, max_age: i64) -> Result<()> {
+        let oracle_feed = &mut ctx.accounts.oracle_feed;
+        oracle_feed.max_age = oracle_feed.provider.feed_max_age(max_age)?;
+
+        msg!(
+            "Oracle feed {} max age set to {}s",
+            oracle_feed.asset_name,
+            oracle_feed.max_age
+        );
+
+        Ok(())
+    }
+
+    /// Bind a market to a registered oracle feed for automated resolution (before any bets)
+    pub fn set_market_oracle(ctx: Context<SetMarketOracle>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let oracle_feed = &ctx.accounts.oracle_feed;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(oracle_feed.active, PredictDuelError::InvalidOracleFeed);
+        // Oracle-resolved markets are bound through their own condition
+        require!(!market.is_oracle_resolved(), PredictDuelError::OracleMarket);
+
+        market.oracle_feed = Some(oracle_feed.feed);
+
+        msg!("Market bound to oracle feed {}", oracle_feed.asset_name);
+
+        Ok(())
+    }
+
+    /// Trust a deposit contract on another chain as a source of cross-chain bets (admin only)
+    pub fn register_foreign_emitter(
+        ctx: Context<RegisterForeignEmitter>,
+        chain: u16,
+        address: [u8; 32],
+    ) -> Result<()> {
+        let foreign_emitter = &mut ctx.accounts.foreign_emitter;
+
+        foreign_emitter.chain = chain;
+        foreign_emitter.address = address;
+        foreign_emitter.bump = ctx.bumps.foreign_emitter;
+
+        msg!("Foreign emitter registered for chain {}", chain);
+
+        Ok(())
+    }
+
+    /// Credit a position from a verified Wormhole VAA describing a deposit on another chain.
+    /// The stake is paid from the bridge vault, which holds liquidity backing remote deposits.
+    pub fn receive_cross_chain_bet(
+        ctx: Context<ReceiveCrossChainBet>,
+        emitter_chain: u16,
+        sequence: u64,
+        bettor: Pubkey,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let participant = &mut ctx.accounts.participant;
+        let clock = Clock::get()?;
+
+        let vaa = read_posted_vaa(&ctx.accounts.posted_vaa)?;
+        require!(
+            vaa.emitter_chain == emitter_chain
+                && vaa.emitter_address == ctx.accounts.foreign_emitter.address
+                && vaa.sequence == sequence,
+            PredictDuelError::InvalidVaa
+        );
+        let bet = CrossChainBet::parse(&vaa.payload)?;
+        require!(
+            bet.market == market.key() && bet.bettor == bettor,
+            PredictDuelError::InvalidVaa
+        );
+
+        market.assert_open_bet(
+            participant,
+            bet.prediction,
+            bet.stake_amount,
+            clock.unix_timestamp,
+        )?;
+        // Relayed bets carry no personhood credential
+        market.assert_personhood(&participant.bettor, participant, None)?;
+        verify_attestation(
+            &ctx.accounts.config,
+            &bettor,
+            ctx.accounts.instructions.as_deref(),
+            clock.unix_timestamp,
+        )?;
+
+        let bridge_vault_seeds: &[&[u8]] = &[b"bridge_vault", &[ctx.bumps.bridge_vault]];
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.bridge_vault.to_account_info(),
+                    to: ctx.accounts.market_vault.to_account_info(),
+                },
+                &[bridge_vault_seeds],
+            ),
+            bet.stake_amount,
+        )?;
+
+        let is_new = participant.market == Pubkey::default();
+        if is_new {
+            participant.market = market.key();
+            participant.bettor = bettor;
+            participant.prediction = bet.prediction;
+            participant.claimed = false;
+            participant.bump = ctx.bumps.participant;
+            participant.origin_chain = emitter_chain;
+            participant.origin_address = bet.recipient;
+        }
+        // Payouts route back to a single origin address
+        require!(
+            participant.origin_chain == emitter_chain
+                && participant.origin_address == bet.recipient,
+            PredictDuelError::InvalidVaa
+        );
+
+        market.credit_bet(
+            participant,
+            is_new,
+            bet.prediction,
+            bet.stake_amount,
+            clock.unix_timestamp,
+        )?;
+        ctx.accounts
+            .odds_history
+            .record(clock.unix_timestamp, market.yes_pool, market.no_pool);
+        if let Some(recent_bets) = ctx.accounts.recent_bets.as_mut() {
+            recent_bets.record(
+                participant.bettor,
+                bet.prediction,
+                bet.stake_amount,
+                clock.unix_timestamp,
+            );
+        }
+
+        let receipt = &mut ctx.accounts.vaa_receipt;
+        receipt.emitter_chain = emitter_chain;
+        receipt.sequence = sequence;
+        receipt.received_at = clock.unix_timestamp;
+        receipt.bump = ctx.bumps.vaa_receipt;
+
+        msg!(
+            "Cross-chain bet from chain {}: {} SOL on {}",
+            emitter_chain,
+            bet.stake_amount as f64 / 1_000_000_000.0,
+            if bet.prediction { "YES" } else { "NO" }
+        );
+
+        Ok(())
+    }
+
+    /// Settle a cross-chain position by publishing a Wormhole message that releases its
+    /// winnings (or refund) on the origin chain. Funds return to the bridge vault.
+    pub fn claim_cross_chain(ctx: Context<ClaimCrossChain>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let participant = &ctx.accounts.participant;
+
+        require!(
+            participant.origin_chain != 0,
+            PredictDuelError::NotCrossChainPosition
+        );
+        let amount = if market.is_refundable() {
+            require!(!participant.claimed, PredictDuelError::AlreadyClaimed);
+            participant.stake
+        } else {
+            // Validates resolution, claim status and that the participant won
+            market.winner_payout(participant, Clock::get()?.unix_timestamp)?
+        };
+
+        let market_key = market.key();
+        let vault_seeds: &[&[u8]] = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.bridge_vault.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            amount,
+        )?;
+
+        // The core bridge charges its message fee up front
+        let fee = {
+            let data = ctx.accounts.wormhole_bridge.try_borrow_data()?;
+            let bytes = data
+                .get(WORMHOLE_BRIDGE_FEE_OFFSET..WORMHOLE_BRIDGE_FEE_OFFSET + 8)
+                .ok_or(PredictDuelError::InvalidVaa)?;
+            u64::from_le_bytes(bytes.try_into().unwrap())
+        };
+        if fee > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.relayer.to_account_info(),
+                        to: ctx.accounts.wormhole_fee_collector.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+
+        let participant_key = participant.key();
+        let payload = cross_chain_payout_payload(&market_key, &participant.origin_address, amount);
+        let ix = wormhole_post_message_ix(ctx.accounts, 0, payload);
+        let emitter_seeds: &[&[u8]] = &[b"emitter", &[ctx.bumps.wormhole_emitter]];
+        let message_seeds: &[&[u8]] = &[
+            b"payout_message",
+            participant_key.as_ref(),
+            &[ctx.bumps.wormhole_message],
+        ];
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.wormhole_bridge.to_account_info(),
+                ctx.accounts.wormhole_message.to_account_info(),
+                ctx.accounts.wormhole_emitter.to_account_info(),
+                ctx.accounts.wormhole_sequence.to_account_info(),
+                ctx.accounts.relayer.to_account_info(),
+                ctx.accounts.wormhole_fee_collector.to_account_info(),
+                ctx.accounts.clock.to_account_info(),
+                ctx.accounts.rent.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[emitter_seeds, message_seeds],
+        )?;
+
+        ctx.accounts.participant.claimed = true;
+
+        msg!(
+            "Cross-chain payout of {} SOL sent to chain {}",
+            amount as f64 / 1_000_000_000.0,
+            ctx.accounts.participant.origin_chain
+        );
+
+        Ok(())
+    }
+
+    /// Set the Bubblegum tree position receipts are minted into (admin only).
+    /// The tree's delegate must be this program's receipt_authority PDA.
+    pub fn set_receipt_tree(ctx: Context<SetReceiptTree>, base_uri: String) -> Result<()> {
+        let receipt_tree = &mut ctx.accounts.receipt_tree;
+
+        require!(
+            base_uri.len() <= MAX_RECEIPT_BASE_URI_LEN,
+            PredictDuelError::ReceiptUriTooLong
+        );
+
+        receipt_tree.merkle_tree = ctx.accounts.merkle_tree.key();
+        receipt_tree.base_uri = base_uri;
+        receipt_tree.bump = ctx.bumps.receipt_tree;
+
+        msg!("Receipt tree set: {}", receipt_tree.merkle_tree);
+
+        Ok(())
+    }
+
+    /// Mint a compressed NFT receipt for a position; whoever holds it can claim the position
+    pub fn mint_position_receipt(ctx: Context<MintPositionReceipt>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let participant = &ctx.accounts.participant;
+
+        require!(
+            participant.receipt_nonce.is_none(),
+            PredictDuelError::ReceiptHeld
+        );
+        require!(
+            participant.origin_chain == 0,
+            PredictDuelError::CrossChainPosition
+        );
+        // A sealed position's side is secret until revealed
+        require!(!market.sealed, PredictDuelError::SealedMarket);
+        require!(
+            market.status == MarketStatus::Pending || market.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+
+        // mint_v1 assigns the tree's current mint count as the leaf nonce (and index)
+        let nonce = {
+            let data = ctx.accounts.tree_config.try_borrow_data()?;
+            let bytes = data
+                .get(BUBBLEGUM_NUM_MINTED_OFFSET..BUBBLEGUM_NUM_MINTED_OFFSET + 8)
+                .ok_or(PredictDuelError::InvalidReceipt)?;
+            u64::from_le_bytes(bytes.try_into().unwrap())
+        };
+
+        let name = format!(
+            "{} {} SOL",
+            if participant.prediction { "YES" } else { "NO" },
+            participant.stake as f64 / 1_000_000_000.0
+        );
+        let uri = format!(
+            "{}{}",
+            ctx.accounts.receipt_tree.base_uri,
+            participant.key()
+        );
+        let ix = bubblegum_mint_v1_ix(
+            ctx.accounts.tree_config.key(),
+            ctx.accounts.merkle_tree.key(),
+            ctx.accounts.bettor.key(),
+            ctx.accounts.receipt_authority.key(),
+            receipt_metadata(&name, RECEIPT_SYMBOL, &uri),
+        );
+        let authority_seeds: &[&[u8]] = &[b"receipt_authority", &[ctx.bumps.receipt_authority]];
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.tree_config.to_account_info(),
+                ctx.accounts.bettor.to_account_info(),
+                ctx.accounts.merkle_tree.to_account_info(),
+                ctx.accounts.receipt_authority.to_account_info(),
+                ctx.accounts.log_wrapper.to_account_info(),
+                ctx.accounts.compression_program.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[authority_seeds],
+        )?;
+
+        ctx.accounts.participant.receipt_nonce = Some(nonce);
+
+        msg!("Position receipt minted: {} (leaf {})", name, nonce);
+
+        Ok(())
+    }
+
+    /// Burn a position receipt to claim its winnings (or refund) as the current holder
+    pub fn claim_with_receipt<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimWithReceipt<'info>>,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+    ) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let participant = &ctx.accounts.participant;
+
+        let nonce = participant
+            .receipt_nonce
+            .ok_or(PredictDuelError::InvalidReceipt)?;
+        let payout = if market.is_refundable() {
+            require!(!participant.claimed, PredictDuelError::AlreadyClaimed);
+            participant.stake
+        } else {
+            // Validates resolution, claim status and that the participant won
+            let payout = market.winner_payout(participant, Clock::get()?.unix_timestamp)?;
+            require!(
+                !(market.vesting_mandatory && payout > market.vesting_threshold),
+                PredictDuelError::VestingRequired
+            );
+            payout
+        };
+        assert_claim_co_signed(
+            &ctx.accounts.profile_guard,
+            ctx.accounts.co_signer.as_ref(),
+            payout,
+        )?;
+
+        // Bubblegum only burns the leaf at this nonce if the holder owns (or is delegate of) it
+        let ix = bubblegum_burn_ix(
+            ctx.accounts,
+            ctx.remaining_accounts,
+            root,
+            data_hash,
+            creator_hash,
+            nonce,
+        );
+        let mut infos = vec![
+            ctx.accounts.tree_config.to_account_info(),
+            ctx.accounts.holder.to_account_info(),
+            ctx.accounts.leaf_delegate.to_account_info(),
+            ctx.accounts.merkle_tree.to_account_info(),
+            ctx.accounts.log_wrapper.to_account_info(),
+            ctx.accounts.compression_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ];
+        infos.extend(ctx.remaining_accounts.iter().cloned());
+        anchor_lang::solana_program::program::invoke(&ix, &infos)?;
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.holder.to_account_info(),
+                },
+                signer,
+            ),
+            payout,
+        )?;
+
+        let participant = &mut ctx.accounts.participant;
+        participant.claimed = true;
+        participant.receipt_nonce = None;
+
+        msg!("Receipt redeemed: {} SOL", payout as f64 / 1_000_000_000.0);
+
+        Ok(())
+    }
+
+    /// Feature or unfeature a market (admin only)
+    pub fn set_featured(ctx: Context<SetFeatured>, featured: bool) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.featured = featured;
+
+        msg!("Market featured: {}", featured);
+
+        Ok(())
+    }
+
+    /// Mint a commemorative trophy cNFT to a winner of a featured market after they claim
+    pub fn claim_trophy(ctx: Context<ClaimTrophy>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let participant = &ctx.accounts.participant;
+
+        require!(market.featured, PredictDuelError::MarketNotFeatured);
+        require!(
+            market.status == MarketStatus::Resolved,
+            PredictDuelError::MarketNotResolved
+        );
+        let outcome = market.outcome.ok_or(PredictDuelError::NoOutcome)?;
+        require!(
+            participant.prediction == outcome && (!market.sealed || participant.revealed),
+            PredictDuelError::NotAWinner
+        );
+        require!(participant.claimed, PredictDuelError::NothingToClaim);
+        require!(
+            !participant.trophy_minted,
+            PredictDuelError::TrophyAlreadyMinted
+        );
+
+        // Final decimal odds of the winning side, in bps
+        let winning_pool = if outcome {
+            market.yes_pool
+        } else {
+            market.no_pool
+        };
+        let odds_bps = (market.pool_size as u128 * BPS_DENOMINATOR as u128)
+            .checked_div(winning_pool as u128)
+            .ok_or(PredictDuelError::MathOverflow)?;
+
+        let name = truncate_nft_name(&market.question);
+        let uri = format!(
+            "{}{}?side={}&odds_bps={}",
+            ctx.accounts.receipt_tree.base_uri,
+            participant.key(),
+            if outcome { "YES" } else { "NO" },
+            odds_bps
+        );
+        let ix = bubblegum_mint_v1_ix(
+            ctx.accounts.tree_config.key(),
+            ctx.accounts.merkle_tree.key(),
+            ctx.accounts.winner.key(),
+            ctx.accounts.receipt_authority.key(),
+            receipt_metadata(name, TROPHY_SYMBOL, &uri),
+        );
+        let authority_seeds: &[&[u8]] = &[b"receipt_authority", &[ctx.bumps.receipt_authority]];
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.tree_config.to_account_info(),
+                ctx.accounts.winner.to_account_info(),
+                ctx.accounts.merkle_tree.to_account_info(),
+                ctx.accounts.receipt_authority.to_account_info(),
+                ctx.accounts.log_wrapper.to_account_info(),
+                ctx.accounts.compression_program.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[authority_seeds],
+        )?;
+
+        ctx.accounts.participant.trophy_minted = true;
+
+        msg!("Trophy minted at {} bps odds", odds_bps);
+
+        Ok(())
+    }
+
+    /// Set the moderator key (admin only)
+    pub fn set_moderator(ctx: Context<SetModerator>, moderator: Pubkey) -> Result<()> {
+        ctx.accounts.config.moderator = moderator;
+
+        msg!("Moderator set to {}", moderator);
+
+        Ok(())
+    }
+
+    /// Anchor a merkle root of off-chain comments so frontends can prove comment integrity
+    pub fn update_discussion_root(
+        ctx: Context<UpdateDiscussionRoot>,
+        root: [u8; 32],
+        comment_count: u32,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let authority = ctx.accounts.authority.key();
+        let clock = Clock::get()?;
+
+        require!(
+            authority == market.creator || authority == ctx.accounts.config.moderator,
+            PredictDuelError::Unauthorized
+        );
+
+        market.discussion_root = root;
+        market.discussion_count = comment_count;
+        market.discussion_updated_at = clock.unix_timestamp;
+
+        msg!("Discussion root updated: {} comments", comment_count);
+
+        Ok(())
+    }
+
+    /// Stake SOL on a market to boost its discovery ranking
+    pub fn boost_market(ctx: Context<BoostMarket>, amount: u64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(amount > 0, PredictDuelError::StakeTooLow);
+        market.assert_boostable(&clock)?;
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.booster.to_account_info(),
+                    to: ctx.accounts.boost.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let boost = &mut ctx.accounts.boost;
+        if boost.market == Pubkey::default() {
+            boost.market = market.key();
+            boost.bump = ctx.bumps.boost;
+        }
+        boost.total_staked += amount;
+
+        let position = &mut ctx.accounts.boost_position;
+        if position.market == Pubkey::default() {
+            position.market = market.key();
+            position.booster = ctx.accounts.booster.key();
+            position.bump = ctx.bumps.boost_position;
+        }
+        position.amount += amount;
+        market.boost_outstanding += amount;
+
+        msg!(
+            "Market boosted: {} SOL (total {} SOL)",
+            amount as f64 / 1_000_000_000.0,
+            boost.total_staked as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Send a resolved market's protocol fee to the treasury, sharing a cut with its boosters
+    pub fn collect_protocol_fee(ctx: Context<CollectProtocolFee>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(
+            market.status == MarketStatus::Resolved,
+            PredictDuelError::MarketNotResolved
+        );
+        require!(!market.fee_collected, PredictDuelError::AlreadyClaimed);
+        require!(
+            Clock::get()?.unix_timestamp >= market.resolvable_at() + FEE_COLLECTION_DELAY,
+            PredictDuelError::FeeCollectionTooEarly
+        );
+
+        let fee = market.protocol_fee() - market.fee_rebated;
+        let booster_cut = match &ctx.accounts.boost {
+            Some(boost) if boost.total_staked > 0 => {
+                (fee as u128 * BOOSTER_FEE_SHARE_BPS as u128 / BPS_DENOMINATOR as u128) as u64
+            }
+            _ => 0,
+        };
+        let treasury_cut = fee - booster_cut;
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        if treasury_cut > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.market_vault.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                    signer,
+                ),
+                treasury_cut,
+            )?;
+        }
+        if let Some(boost) = &mut ctx.accounts.boost {
+            if booster_cut > 0 {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: ctx.accounts.market_vault.to_account_info(),
+                            to: boost.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    booster_cut,
+                )?;
+                boost.fee_share += booster_cut;
+            }
+        }
+
+        market.fee_collected = true;
+
+        msg!(
+            "Protocol fee collected: {} SOL ({} SOL to boosters)",
+            fee as f64 / 1_000_000_000.0,
+            booster_cut as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw a boost stake plus its share of booster fees once the market has settled
+    pub fn withdraw_boost(ctx: Context<WithdrawBoost>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let boost = &ctx.accounts.boost;
+        let position = &ctx.accounts.boost_position;
+
+        let settled = market.is_refundable()
+            || (market.status == MarketStatus::Resolved
+                && (market.fee_collected || market.protocol_fee() == 0));
+        require!(settled, PredictDuelError::MarketNotResolved);
+        require!(!position.claimed, PredictDuelError::AlreadyClaimed);
+
+        let fee_share = (boost.fee_share as u128 * position.amount as u128)
+            .checked_div(boost.total_staked as u128)
+            .ok_or(PredictDuelError::MathOverflow)? as u64;
+        let amount = position.amount + fee_share;
+        let position_amount = position.amount;
+
+        ctx.accounts.boost.sub_lamports(amount)?;
+        ctx.accounts.booster.add_lamports(amount)?;
+        ctx.accounts.boost_position.claimed = true;
+        let market = &mut ctx.accounts.market;
+        market.boost_outstanding = market.boost_outstanding.saturating_sub(position_amount);
+
+        msg!(
+            "Boost withdrawn: {} SOL ({} SOL fee share)",
+            amount as f64 / 1_000_000_000.0,
+            fee_share as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Authorize mirroring a leader's bets from an escrowed budget, capped per bet and in total
+    pub fn create_copy_config(
+        ctx: Context<CreateCopyConfig>,
+        max_per_bet: u64,
+        max_total: u64,
+        deposit: u64,
+    ) -> Result<()> {
+        require!(
+            max_per_bet >= DEFAULT_MIN_STAKE && max_per_bet <= max_total,
+            PredictDuelError::InvalidCopyCaps
+        );
+        require!(
+            ctx.accounts.follower.key() != ctx.accounts.leader.key(),
+            PredictDuelError::InvalidCopyCaps
+        );
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.follower.to_account_info(),
+                    to: ctx.accounts.copy_config.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+
+        let copy_config = &mut ctx.accounts.copy_config;
+        copy_config.follower = ctx.accounts.follower.key();
+        copy_config.leader = ctx.accounts.leader.key();
+        copy_config.max_per_bet = max_per_bet;
+        copy_config.max_total = max_total;
+        copy_config.total_copied = 0;
+        copy_config.balance = deposit;
+        copy_config.bump = ctx.bumps.copy_config;
+
+        msg!(
+            "Copying {}: up to {} SOL per bet, {} SOL total",
+            copy_config.leader,
+            max_per_bet as f64 / 1_000_000_000.0,
+            max_total as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Keeper mirrors a leader's position into the follower's, funded from the copy escrow
+    pub fn copy_bet(ctx: Context<CopyBet>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let leader_position = &ctx.accounts.leader_position;
+        let participant = &mut ctx.accounts.participant;
+        let copy_config = &mut ctx.accounts.copy_config;
+        let clock = Clock::get()?;
+
+        // One mirrored entry per market; the follower's position must be fresh
+        require!(
+            participant.market == Pubkey::default(),
+            PredictDuelError::AlreadyCopied
+        );
+
+        let remaining = copy_config.max_total - copy_config.total_copied;
+        let stake_amount = leader_position
+            .stake
+            .min(copy_config.max_per_bet)
+            .min(remaining)
+            .min(copy_config.balance);
+        let prediction = leader_position.prediction;
+
+        market.assert_open_bet(participant, prediction, stake_amount, clock.unix_timestamp)?;
+        market.assert_personhood(&participant.bettor, participant, None)?;
+        verify_attestation(
+            &ctx.accounts.config,
+            &copy_config.follower,
+            ctx.accounts.instructions.as_deref(),
+            clock.unix_timestamp,
+        )?;
+
+        copy_config.to_account_info().sub_lamports(stake_amount)?;
+        ctx.accounts.market_vault.add_lamports(stake_amount)?;
+        copy_config.balance -= stake_amount;
+        copy_config.total_copied += stake_amount;
+
+        participant.market = market.key();
+        participant.bettor = copy_config.follower;
+        participant.prediction = prediction;
+        participant.claimed = false;
+        participant.bump = ctx.bumps.participant;
+
+        market.credit_bet(
+            participant,
+            true,
+            prediction,
+            stake_amount,
+            clock.unix_timestamp,
+        )?;
+        ctx.accounts
+            .odds_history
+            .record(clock.unix_timestamp, market.yes_pool, market.no_pool);
+        if let Some(recent_bets) = ctx.accounts.recent_bets.as_mut() {
+            recent_bets.record(
+                participant.bettor,
+                prediction,
+                stake_amount,
+                clock.unix_timestamp,
+            );
+        }
+
+        msg!(
+            "Copied bet: {} SOL on {}",
+            stake_amount as f64 / 1_000_000_000.0,
+            if prediction { "YES" } else { "NO" }
+        );
+
+        Ok(())
+    }
+
+    /// Stop copying and withdraw the unspent escrow
+    pub fn close_copy_config(ctx: Context<CloseCopyConfig>) -> Result<()> {
+        msg!(
+            "Stopped copying {}: {} SOL returned",
+            ctx.accounts.copy_config.leader,
+            ctx.accounts.copy_config.balance as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Open a managed sub-account: the manager can bet its funds but never withdraw them
+    pub fn open_managed_account(
+        ctx: Context<OpenManagedAccount>,
+        performance_fee_bps: u16,
+        deposit: u64,
+    ) -> Result<()> {
+        require!(
+            performance_fee_bps <= MAX_PERFORMANCE_FEE_BPS,
+            PredictDuelError::InvalidPerformanceFee
+        );
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.managed_account.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+
+        let managed_account = &mut ctx.accounts.managed_account;
+        managed_account.owner = ctx.accounts.owner.key();
+        managed_account.manager = ctx.accounts.manager.key();
+        managed_account.performance_fee_bps = performance_fee_bps;
+        managed_account.balance = deposit;
+        managed_account.fees_paid = 0;
+        managed_account.bump = ctx.bumps.managed_account;
+
+        msg!(
+            "Managed account opened for manager {} with {} SOL",
+            managed_account.manager,
+            deposit as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Owner adds funds to a managed account
+    pub fn deposit_managed(ctx: Context<OwnManagedAccount>, amount: u64) -> Result<()> {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.managed_account.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        ctx.accounts.managed_account.balance += amount;
+
+        msg!("Managed deposit: {} SOL", amount as f64 / 1_000_000_000.0);
+
+        Ok(())
+    }
+
+    /// Owner withdraws idle funds; only the owner can ever move funds out
+    pub fn withdraw_managed(ctx: Context<OwnManagedAccount>, amount: u64) -> Result<()> {
+        let managed_account = &mut ctx.accounts.managed_account;
+
+        require!(
+            amount <= managed_account.balance,
+            PredictDuelError::InsufficientManagedBalance
+        );
+
+        managed_account.balance -= amount;
+        managed_account.to_account_info().sub_lamports(amount)?;
+        ctx.accounts.owner.add_lamports(amount)?;
+
+        msg!(
+            "Managed withdrawal: {} SOL",
+            amount as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Manager places a bet with the managed account's funds; the position belongs to the account
+    pub fn managed_bet(
+        ctx: Context<ManagedBet>,
+        prediction: bool,
+        stake_amount: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let participant = &mut ctx.accounts.participant;
+        let managed_account = &mut ctx.accounts.managed_account;
+        let clock = Clock::get()?;
+
+        require!(
+            stake_amount <= managed_account.balance,
+            PredictDuelError::InsufficientManagedBalance
+        );
+        market.assert_open_bet(participant, prediction, stake_amount, clock.unix_timestamp)?;
+        market.assert_personhood(&participant.bettor, participant, None)?;
+        verify_attestation(
+            &ctx.accounts.config,
+            &managed_account.owner,
+            ctx.accounts.instructions.as_deref(),
+            clock.unix_timestamp,
+        )?;
+
+        managed_account.balance -= stake_amount;
+        managed_account
+            .to_account_info()
+            .sub_lamports(stake_amount)?;
+        ctx.accounts.market_vault.add_lamports(stake_amount)?;
+
+        let is_new = participant.market == Pubkey::default();
+        if is_new {
+            participant.market = market.key();
+            participant.bettor = managed_account.key();
+            participant.prediction = prediction;
+            participant.claimed = false;
+            participant.bump = ctx.bumps.participant;
+        }
+
+        market.credit_bet(
+            participant,
+            is_new,
+            prediction,
+            stake_amount,
+            clock.unix_timestamp,
+        )?;
+        ctx.accounts
+            .odds_history
+            .record(clock.unix_timestamp, market.yes_pool, market.no_pool);
+        if let Some(recent_bets) = ctx.accounts.recent_bets.as_mut() {
+            recent_bets.record(
+                participant.bettor,
+                prediction,
+                stake_amount,
+                clock.unix_timestamp,
+            );
+        }
+
+        msg!(
+            "Managed bet placed: {} SOL on {}",
+            stake_amount as f64 / 1_000_000_000.0,
+            if prediction { "YES" } else { "NO" }
+        );
+
+        Ok(())
+    }
+
+    /// Settle a managed position back into the account, paying the manager's fee on profit
+    pub fn settle_managed_position(ctx: Context<SettleManagedPosition>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let participant = &ctx.accounts.participant;
+
+        let payout = if market.is_refundable() {
+            require!(!participant.claimed, PredictDuelError::AlreadyClaimed);
+            participant.stake
+        } else {
+            // Validates resolution, claim status and that the position won
+            market.winner_payout(participant, Clock::get()?.unix_timestamp)?
+        };
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.managed_account.to_account_info(),
+                },
+                signer,
+            ),
+            payout,
+        )?;
+
+        let managed_account = &mut ctx.accounts.managed_account;
+        let profit = payout.saturating_sub(participant.stake);
+        let fee = (profit as u128 * managed_account.performance_fee_bps as u128
+            / BPS_DENOMINATOR as u128) as u64;
+        if fee > 0 {
+            managed_account.to_account_info().sub_lamports(fee)?;
+            ctx.accounts.manager.add_lamports(fee)?;
+        }
+        managed_account.balance += payout - fee;
+        managed_account.fees_paid += fee;
+
+        ctx.accounts.participant.claimed = true;
+
+        msg!(
+            "Managed position settled: {} SOL ({} SOL performance fee)",
+            payout as f64 / 1_000_000_000.0,
+            fee as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Start a syndicate: a shared pot for one market whose captain places a single group bet
+    pub fn create_syndicate(ctx: Context<CreateSyndicate>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let syndicate = &mut ctx.accounts.syndicate;
+
+        require!(
+            market.status == MarketStatus::Pending || market.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+
+        syndicate.market = market.key();
+        syndicate.captain = ctx.accounts.captain.key();
+        syndicate.total_deposits = 0;
+        syndicate.bet_placed = false;
+        syndicate.settled = false;
+        syndicate.settled_amount = 0;
+        syndicate.bump = ctx.bumps.syndicate;
+
+        msg!("Syndicate created by {}", syndicate.captain);
+
+        Ok(())
+    }
+
+    /// Deposit into a syndicate's pot before its bet is placed
+    pub fn join_syndicate(ctx: Context<JoinSyndicate>, amount: u64) -> Result<()> {
+        require!(
+            !ctx.accounts.syndicate.bet_placed,
+            PredictDuelError::SyndicateBetPlaced
+        );
+        require!(amount > 0, PredictDuelError::StakeTooLow);
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.member.to_account_info(),
+                    to: ctx.accounts.syndicate.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let syndicate = &mut ctx.accounts.syndicate;
+        syndicate.total_deposits += amount;
+
+        let membership = &mut ctx.accounts.membership;
+        if membership.syndicate == Pubkey::default() {
+            membership.syndicate = syndicate.key();
+            membership.member = ctx.accounts.member.key();
+            membership.bump = ctx.bumps.membership;
+        }
+        membership.deposit += amount;
+
+        msg!(
+            "Joined syndicate: {} SOL (pot {} SOL)",
+            amount as f64 / 1_000_000_000.0,
+            syndicate.total_deposits as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw a deposit while the syndicate's bet hasn't been placed
+    pub fn leave_syndicate(ctx: Context<LeaveSyndicate>) -> Result<()> {
+        let syndicate = &mut ctx.accounts.syndicate;
+        let membership = &mut ctx.accounts.membership;
+
+        require!(!syndicate.bet_placed, PredictDuelError::SyndicateBetPlaced);
+
+        let amount = membership.deposit;
+        syndicate.total_deposits -= amount;
+        membership.deposit = 0;
+        syndicate.to_account_info().sub_lamports(amount)?;
+        ctx.accounts.member.add_lamports(amount)?;
+
+        msg!(
+            "Left syndicate: {} SOL returned",
+            amount as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Captain stakes the whole pot on one side; the position belongs to the syndicate
+    pub fn syndicate_bet(ctx: Context<SyndicateBet>, prediction: bool) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let participant = &mut ctx.accounts.participant;
+        let syndicate = &mut ctx.accounts.syndicate;
+        let clock = Clock::get()?;
+
+        require!(!syndicate.bet_placed, PredictDuelError::SyndicateBetPlaced);
+        let stake_amount = syndicate.total_deposits;
+        market.assert_open_bet(participant, prediction, stake_amount, clock.unix_timestamp)?;
+        market.assert_personhood(&participant.bettor, participant, None)?;
+        verify_attestation(
+            &ctx.accounts.config,
+            &ctx.accounts.captain.key(),
+            ctx.accounts.instructions.as_deref(),
+            clock.unix_timestamp,
+        )?;
+
+        syndicate.to_account_info().sub_lamports(stake_amount)?;
+        ctx.accounts.market_vault.add_lamports(stake_amount)?;
+        syndicate.bet_placed = true;
+
+        participant.market = market.key();
+        participant.bettor = syndicate.key();
+        participant.prediction = prediction;
+        participant.claimed = false;
+        participant.bump = ctx.bumps.participant;
+
+        market.credit_bet(
+            participant,
+            true,
+            prediction,
+            stake_amount,
+            clock.unix_timestamp,
+        )?;
+        ctx.accounts
+            .odds_history
+            .record(clock.unix_timestamp, market.yes_pool, market.no_pool);
+        if let Some(recent_bets) = ctx.accounts.recent_bets.as_mut() {
+            recent_bets.record(
+                participant.bettor,
+                prediction,
+                stake_amount,
+                clock.unix_timestamp,
+            );
+        }
+
+        msg!(
+            "Syndicate bet placed: {} SOL on {}",
+            stake_amount as f64 / 1_000_000_000.0,
+            if prediction { "YES" } else { "NO" }
+        );
+
+        Ok(())
+    }
+
+    /// Pull the syndicate position's payout (or refund) into the pot once the market settles
+    pub fn settle_syndicate(ctx: Context<SettleSyndicate>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let participant = &ctx.accounts.participant;
+
+        require!(
+            !ctx.accounts.syndicate.settled,
+            PredictDuelError::AlreadyClaimed
+        );
+
+        let amount = market.pooled_settlement(participant, Clock::get()?.unix_timestamp)?;
+
+        if amount > 0 {
+            let seeds = &[
+                b"market_vault",
+                market.creator.as_ref(),
+                &market.market_index.to_le_bytes(),
+                &[ctx.bumps.market_vault],
+            ];
+            let signer = &[&seeds[..]];
+
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.market_vault.to_account_info(),
+                        to: ctx.accounts.syndicate.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+
+        ctx.accounts.participant.claimed = true;
+        let syndicate = &mut ctx.accounts.syndicate;
+        syndicate.settled = true;
+        syndicate.settled_amount = amount;
+
+        msg!(
+            "Syndicate settled: {} SOL to share",
+            amount as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Claim a member's pro-rata share of the settled pot
+    pub fn claim_syndicate_share(ctx: Context<LeaveSyndicate>) -> Result<()> {
+        let syndicate = &ctx.accounts.syndicate;
+        let membership = &mut ctx.accounts.membership;
+
+        require!(syndicate.settled, PredictDuelError::MarketNotResolved);
+        require!(!membership.claimed, PredictDuelError::AlreadyClaimed);
+
+        let share = (syndicate.settled_amount as u128 * membership.deposit as u128)
+            .checked_div(syndicate.total_deposits as u128)
+            .ok_or(PredictDuelError::MathOverflow)? as u64;
+
+        membership.claimed = true;
+        if share > 0 {
+            ctx.accounts.syndicate.sub_lamports(share)?;
+            ctx.accounts.member.add_lamports(share)?;
+        }
+
+        msg!(
+            "Syndicate share claimed: {} SOL",
+            share as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Hand parameter control to holders of a governance token (admin only, one-way)
+    pub fn set_governance_mint(ctx: Context<SetGovernanceMint>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            config.governance_mint == Pubkey::default(),
+            PredictDuelError::GovernanceActive
+        );
+
+        config.governance_mint = ctx.accounts.governance_mint.key();
+
+        msg!("Governance enabled with mint {}", config.governance_mint);
+
+        Ok(())
+    }
+
+    /// Propose a config parameter change, open for token-weighted voting. The proposer must
+    /// hold PROPOSAL_THRESHOLD_BPS of the governance token supply.
+    pub fn create_proposal(ctx: Context<CreateProposal>, change: GovernanceChange) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let proposal = &mut ctx.accounts.proposal;
+        let clock = Clock::get()?;
+
+        require!(
+            config.governance_mint != Pubkey::default(),
+            PredictDuelError::GovernanceInactive
+        );
+        require!(
+            ctx.accounts.proposer_token_account.amount
+                >= Proposal::threshold(ctx.accounts.governance_mint.supply),
+            PredictDuelError::ProposalThresholdNotMet
+        );
+        change.validate(config)?;
+
+        proposal.index = config.proposal_count;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.change = change;
+        proposal.yes_votes = 0;
+        proposal.no_votes = 0;
+        proposal.voting_ends_at = clock.unix_timestamp + GOVERNANCE_VOTING_PERIOD;
+        proposal.executed = false;
+        proposal.bump = ctx.bumps.proposal;
+
+        config.proposal_count += 1;
+
+        msg!("Proposal {} created", proposal.index);
+
+        Ok(())
+    }
+
+    /// Vote by locking governance tokens in the proposal's escrow until voting ends
+    pub fn cast_vote(ctx: Context<CastVote>, support: bool, amount: u64) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let clock = Clock::get()?;
+
+        require!(
+            clock.unix_timestamp < proposal.voting_ends_at,
+            PredictDuelError::VotingClosed
+        );
+        require!(amount > 0, PredictDuelError::StakeTooLow);
+
+        anchor_spl::token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Transfer {
+                    from: ctx.accounts.voter_token_account.to_account_info(),
+                    to: ctx.accounts.vote_escrow.to_account_info(),
+                    authority: ctx.accounts.voter.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let vote_record = &mut ctx.accounts.vote_record;
+        require!(
+            vote_record.amount == 0 || vote_record.support == support,
+            PredictDuelError::SideMismatch
+        );
+        vote_record.proposal = proposal.key();
+        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.support = support;
+        vote_record.amount += amount;
+        vote_record.bump = ctx.bumps.vote_record;
+
+        if support {
+            proposal.yes_votes += amount;
+        } else {
+            proposal.no_votes += amount;
+        }
+
+        msg!(
+            "Vote cast: {} tokens {}",
+            amount,
+            if support { "for" } else { "against" }
+        );
+
+        Ok(())
+    }
+
+    /// Return a voter's locked tokens once voting has ended
+    pub fn withdraw_vote(ctx: Context<WithdrawVote>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let clock = Clock::get()?;
+
+        require!(
+            clock.unix_timestamp >= proposal.voting_ends_at,
+            PredictDuelError::VotingOpen
+        );
+
+        let amount = ctx.accounts.vote_record.amount;
+        let index_bytes = proposal.index.to_le_bytes();
+        let seeds: &[&[u8]] = &[b"proposal", &index_bytes, &[proposal.bump]];
+        anchor_spl::token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Transfer {
+                    from: ctx.accounts.vote_escrow.to_account_info(),
+                    to: ctx.accounts.voter_token_account.to_account_info(),
+                    authority: ctx.accounts.proposal.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        msg!("Vote tokens withdrawn: {}", amount);
+
+        Ok(())
+    }
+
+    /// Queue the change of a proposal that passed, with quorum, once its voting period is
+    /// over (permissionless; the caller pays rent). It applies through
+    /// execute_governance_action after the timelock delay, so users have time to react.
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let proposal = &mut ctx.accounts.proposal;
+        let clock = Clock::get()?;
+
+        require!(
+            clock.unix_timestamp >= proposal.voting_ends_at,
+            PredictDuelError::VotingOpen
+        );
+        require!(!proposal.executed, PredictDuelError::ProposalExecuted);
+        proposal.assert_passed(ctx.accounts.governance_mint.supply)?;
+        // Bounds can depend on config that changed while the vote ran
+        let action = TimelockAction::Governance(proposal.change);
+        action.validate(config)?;
+
+        let queued = &mut ctx.accounts.queued_action;
+        queued.index = config.queued_count;
+        queued.action = action;
+        queued.eta = clock.unix_timestamp + config.timelock_delay;
+        queued.bump = ctx.bumps.queued_action;
+
+        config.queued_count += 1;
+        proposal.executed = true;
+
+        msg!(
+            "Proposal {} queued as action {}, executable at {}",
+            proposal.index,
+            queued.index,
+            queued.eta
+        );
+
+        Ok(())
+    }
+
+    /// Apply a governance change whose timelock delay has passed (permissionless; the
+    /// caller reclaims the queued action's rent)
+    pub fn execute_governance_action(ctx: Context<ExecuteGovernanceAction>) -> Result<()> {
+        let queued = &ctx.accounts.queued_action;
+        let clock = Clock::get()?;
+
+        require!(
+            clock.unix_timestamp >= queued.eta,
+            PredictDuelError::TimelockNotElapsed
+        );
+        let TimelockAction::Governance(change) = queued.action else {
+            return err!(PredictDuelError::NotGovernanceAction);
+        };
+        change.validate(&ctx.accounts.config)?;
+        change.apply(
+            &mut ctx.accounts.config,
+            ctx.accounts.distributor.as_deref_mut(),
+        )?;
+
+        msg!("Governance action {} executed", queued.index);
+
+        Ok(())
+    }
+
+    /// Start reward emissions in an SPL token (admin only); the vault is funded by plain transfers
+    pub fn configure_rewards(
+        ctx: Context<ConfigureRewards>,
+        emission_per_epoch: u64,
+        epoch_length: i64,
+    ) -> Result<()> {
+        let rewards = &mut ctx.accounts.rewards;
+        let clock = Clock::get()?;
+
+        require!(
+            epoch_length >= MIN_REWARD_EPOCH_LENGTH,
+            PredictDuelError::InvalidEpochLength
+        );
+
+        rewards.reward_mint = ctx.accounts.reward_mint.key();
+        rewards.emission_per_epoch = emission_per_epoch;
+        rewards.epoch_length = epoch_length;
+        rewards.start_time = clock.unix_timestamp;
+        rewards.bump = ctx.bumps.rewards;
+        rewards.check_in_reward = 0;
+
+        msg!(
+            "Rewards configured: {} per epoch of {}s",
+            emission_per_epoch,
+            epoch_length
+        );
+
+        Ok(())
+    }
+
+    /// Credit a position's stake not yet counted to the current reward epoch
+    pub fn record_volume(ctx: Context<RecordVolume>, epoch: u64) -> Result<()> {
+        let rewards = &ctx.accounts.rewards;
+        let participant = &mut ctx.accounts.participant;
+        let clock = Clock::get()?;
+
+        require!(
+            epoch == rewards.epoch_at(clock.unix_timestamp),
+            PredictDuelError::EpochMismatch
+        );
+
+        let volume = participant
+            .stake
+            .saturating_sub(participant.volume_recorded);
+        require!(volume > 0, PredictDuelError::NoNewVolume);
+        participant.volume_recorded = participant.stake;
+
+        let reward_epoch = &mut ctx.accounts.reward_epoch;
+        reward_epoch.epoch = epoch;
+        reward_epoch.total_volume += volume;
+        reward_epoch.bump = ctx.bumps.reward_epoch;
+
+        let user_volume = &mut ctx.accounts.user_volume;
+        user_volume.bettor = participant.bettor;
+        user_volume.epoch = epoch;
+        user_volume.volume += volume;
+        user_volume.bump = ctx.bumps.user_volume;
+
+        msg!(
+            "Recorded {} SOL of volume in epoch {}",
+            volume as f64 / 1_000_000_000.0,
+            epoch
+        );
+
+        Ok(())
+    }
+
+    /// Claim a bettor's share of a finished epoch's emissions, pro rata to volume
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, epoch: u64) -> Result<()> {
+        let rewards = &ctx.accounts.rewards;
+        let user_volume = &mut ctx.accounts.user_volume;
+        let clock = Clock::get()?;
+
+        require!(
+            epoch < rewards.epoch_at(clock.unix_timestamp),
+            PredictDuelError::EpochNotEnded
+        );
+        require!(!user_volume.claimed, PredictDuelError::AlreadyClaimed);
+
+        let amount = ((rewards.emission_per_epoch as u128)
+            .checked_mul(user_volume.volume as u128)
+            .ok_or(PredictDuelError::MathOverflow)?
+            / ctx.accounts.reward_epoch.total_volume as u128) as u64;
+        require!(amount > 0, PredictDuelError::NothingToClaim);
+        user_volume.claimed = true;
+
+        let seeds: &[&[u8]] = &[b"rewards", &[rewards.bump]];
+        anchor_spl::token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Transfer {
+                    from: ctx.accounts.rewards_vault.to_account_info(),
+                    to: ctx.accounts.bettor_token_account.to_account_info(),
+                    authority: ctx.accounts.rewards.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        msg!("Claimed {} reward tokens for epoch {}", amount, epoch);
+
+        Ok(())
+    }
+
+    /// Create the fee-sharing pool for platform token stakers (admin only); point the
+    /// treasury at the pool PDA to route protocol fees to stakers
+    pub fn init_staking_pool(ctx: Context<InitStakingPool>, epoch_length: i64) -> Result<()> {
+        let pool = &mut ctx.accounts.staking_pool;
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.config.governance_mint != Pubkey::default(),
+            PredictDuelError::GovernanceInactive
+        );
+        require!(
+            epoch_length >= MIN_REWARD_EPOCH_LENGTH,
+            PredictDuelError::InvalidEpochLength
+        );
+
+        pool.stake_mint = ctx.accounts.config.governance_mint;
+        pool.total_staked = 0;
+        pool.acc_fee_per_share = 0;
+        pool.owed = 0;
+        pool.epoch_length = epoch_length;
+        pool.epoch_started_at = clock.unix_timestamp;
+        pool.current_epoch = 0;
+        pool.bump = ctx.bumps.staking_pool;
+
+        msg!("Staking pool created with {}s epochs", epoch_length);
+
+        Ok(())
+    }
+
+    /// Lock platform tokens to earn a share of protocol fees from the next epoch close
+    pub fn stake_tokens(ctx: Context<StakeTokens>, amount: u64) -> Result<()> {
+        let pool = &mut ctx.accounts.staking_pool;
+        let position = &mut ctx.accounts.stake_position;
+
+        require!(amount > 0, PredictDuelError::StakeTooLow);
+
+        anchor_spl::token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Transfer {
+                    from: ctx.accounts.staker_token_account.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.staker.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        position.accrue(pool.acc_fee_per_share)?;
+        position.staker = ctx.accounts.staker.key();
+        position.amount += amount;
+        position.reward_debt = position.amount as u128 * pool.acc_fee_per_share;
+        position.staked_epoch = pool.current_epoch;
+        position.bump = ctx.bumps.stake_position;
+        pool.total_staked += amount;
+
+        msg!("Staked {} platform tokens", amount);
+
+        Ok(())
+    }
+
+    /// Unlock platform tokens once a full epoch has passed since the last stake
+    pub fn unstake_tokens(ctx: Context<UnstakeTokens>, amount: u64) -> Result<()> {
+        let pool = &mut ctx.accounts.staking_pool;
+        let position = &mut ctx.accounts.stake_position;
+
+        require!(
+            amount > 0 && amount <= position.amount,
+            PredictDuelError::InsufficientStake
+        );
+        // Stakers must sit through a whole epoch, so stake can't be parked just for a close
+        require!(
+            pool.current_epoch > position.staked_epoch + 1,
+            PredictDuelError::StakeLocked
+        );
+        require!(position.jury_votes == 0, PredictDuelError::JurorLocked);
+
+        position.accrue(pool.acc_fee_per_share)?;
+        position.amount -= amount;
+        position.reward_debt = position.amount as u128 * pool.acc_fee_per_share;
+        pool.total_staked -= amount;
+
+        let seeds: &[&[u8]] = &[b"staking_pool", &[pool.bump]];
+        anchor_spl::token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Transfer {
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    to: ctx.accounts.staker_token_account.to_account_info(),
+                    authority: ctx.accounts.staking_pool.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        msg!("Unstaked {} platform tokens", amount);
+
+        Ok(())
+    }
+
+    /// Close the current fee epoch, splitting fees received since the last close across stakers
+    pub fn close_fee_epoch(ctx: Context<CloseFeeEpoch>) -> Result<()> {
+        let clock = Clock::get()?;
+        let pool_info = ctx.accounts.staking_pool.to_account_info();
+        let rent_floor = Rent::get()?.minimum_balance(pool_info.data_len());
+        let pool = &mut ctx.accounts.staking_pool;
+
+        require!(
+            clock.unix_timestamp >= pool.epoch_started_at + pool.epoch_length,
+            PredictDuelError::EpochNotEnded
+        );
+
+        // Anything above rent and unclaimed shares arrived as fees this epoch;
+        // with no stakers it rolls into the next epoch
+        let fees = pool_info
+            .lamports()
+            .saturating_sub(rent_floor)
+            .saturating_sub(pool.owed);
+        let distributed = if pool.total_staked > 0 { fees } else { 0 };
+        if distributed > 0 {
+            pool.acc_fee_per_share +=
+                distributed as u128 * FEE_SHARE_PRECISION / pool.total_staked as u128;
+            pool.owed += distributed;
+        }
+
+        let fee_epoch = &mut ctx.accounts.fee_epoch;
+        fee_epoch.epoch = pool.current_epoch;
+        fee_epoch.fees = distributed;
+        fee_epoch.total_staked = pool.total_staked;
+        fee_epoch.closed_at = clock.unix_timestamp;
+        fee_epoch.bump = ctx.bumps.fee_epoch;
+
+        pool.current_epoch += 1;
+        pool.epoch_started_at = clock.unix_timestamp;
+
+        msg!(
+            "Fee epoch {} closed: {} SOL to stakers",
+            fee_epoch.epoch,
+            distributed as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw a staker's accrued share of protocol fees
+    pub fn claim_fee_share(ctx: Context<ClaimFeeShare>) -> Result<()> {
+        let pool = &mut ctx.accounts.staking_pool;
+        let position = &mut ctx.accounts.stake_position;
+
+        position.accrue(pool.acc_fee_per_share)?;
+        position.reward_debt = position.amount as u128 * pool.acc_fee_per_share;
+
+        let amount = position.unclaimed;
+        require!(amount > 0, PredictDuelError::NothingToClaim);
+        position.unclaimed = 0;
+        pool.owed = pool.owed.saturating_sub(amount);
+
+        pool.sub_lamports(amount)?;
+        ctx.accounts.staker.add_lamports(amount)?;
+
+        msg!("Fee share claimed: {} SOL", amount as f64 / 1_000_000_000.0);
+
+        Ok(())
+    }
+
+    /// Create the buyback vault (admin only); point the treasury at the buyback PDA
+    /// to route protocol fees into buy-and-burn
+    pub fn init_buyback(
+        ctx: Context<InitBuyback>,
+        max_per_epoch: u64,
+        epoch_length: i64,
+    ) -> Result<()> {
+        let buyback = &mut ctx.accounts.buyback;
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.config.governance_mint != Pubkey::default(),
+            PredictDuelError::GovernanceInactive
+        );
+        require!(
+            epoch_length >= MIN_REWARD_EPOCH_LENGTH,
+            PredictDuelError::InvalidEpochLength
+        );
+
+        buyback.platform_mint = ctx.accounts.config.governance_mint;
+        buyback.max_per_epoch = max_per_epoch;
+        buyback.epoch_length = epoch_length;
+        buyback.epoch_started_at = clock.unix_timestamp;
+        buyback.spent_this_epoch = 0;
+        buyback.total_spent = 0;
+        buyback.total_burned = 0;
+        buyback.bump = ctx.bumps.buyback;
+
+        msg!(
+            "Buyback created: up to {} SOL per {}s epoch",
+            max_per_epoch as f64 / 1_000_000_000.0,
+            epoch_length
+        );
+
+        Ok(())
+    }
+
+    /// Swap accumulated SOL fees for the platform token through a Jupiter route and burn
+    /// what was bought (admin only). Route accounts are passed as remaining accounts.
+    pub fn execute_buyback<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteBuyback<'info>>,
+        amount: u64,
+        min_tokens_out: u64,
+        route_data: Vec<u8>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let buyback_info = ctx.accounts.buyback.to_account_info();
+        let rent_floor = Rent::get()?.minimum_balance(buyback_info.data_len());
+        let buyback = &mut ctx.accounts.buyback;
+
+        if clock.unix_timestamp >= buyback.epoch_started_at + buyback.epoch_length {
+            buyback.epoch_started_at = clock.unix_timestamp;
+            buyback.spent_this_epoch = 0;
+        }
+        require!(
+            amount > 0 && buyback.spent_this_epoch + amount <= buyback.max_per_epoch,
+            PredictDuelError::BuybackCapExceeded
+        );
+        require!(
+            buyback_info.lamports().saturating_sub(rent_floor) >= amount,
+            PredictDuelError::InsufficientBuybackFunds
+        );
+
+        // Wrap the SOL being spent
+        buyback.sub_lamports(amount)?;
+        ctx.accounts.buyback_wsol.add_lamports(amount)?;
+        anchor_spl::token::sync_native(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token::SyncNative {
+                account: ctx.accounts.buyback_wsol.to_account_info(),
+            },
+        ))?;
+
+        let seeds: &[&[u8]] = &[b"buyback", &[buyback.bump]];
+        let balance_before = ctx.accounts.buyback_tokens.amount;
+        let route = anchor_lang::solana_program::instruction::Instruction {
+            program_id: JUPITER_PROGRAM_ID,
+            accounts: ctx
+                .remaining_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.key() == buyback_info.key(),
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: route_data,
+        };
+        let mut infos = ctx.remaining_accounts.to_vec();
+        infos.push(ctx.accounts.jupiter_program.to_account_info());
+        anchor_lang::solana_program::program::invoke_signed(&route, &infos, &[seeds])?;
+
+        ctx.accounts.buyback_tokens.reload()?;
+        let bought = ctx
+            .accounts
+            .buyback_tokens
+            .amount
+            .saturating_sub(balance_before);
+        require!(
+            bought >= min_tokens_out && bought > 0,
+            PredictDuelError::BuybackSlippage
+        );
+
+        anchor_spl::token::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Burn {
+                    mint: ctx.accounts.platform_mint.to_account_info(),
+                    from: ctx.accounts.buyback_tokens.to_account_info(),
+                    authority: buyback_info.clone(),
+                },
+                &[seeds],
+            ),
+            bought,
+        )?;
+
+        let buyback = &mut ctx.accounts.buyback;
+        buyback.spent_this_epoch += amount;
+        buyback.total_spent += amount;
+        buyback.total_burned += bought;
+
+        emit!(BuybackExecuted {
+            sol_spent: amount,
+            tokens_burned: bought,
+            spent_this_epoch: buyback.spent_this_epoch,
+            total_burned: buyback.total_burned,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Buyback: {} SOL for {} tokens burned",
+            amount as f64 / 1_000_000_000.0,
+            bought
+        );
+
+        Ok(())
+    }
+
+    /// Accept an admin rotation executed through the timelock; proves the new authority
+    /// can actually sign
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        config.admin = config.pending_admin;
+        config.pending_admin = Pubkey::default();
+
+        msg!("Admin is now {}", config.admin);
+
+        Ok(())
+    }
+
+    /// Create the program-owned treasury PDA (admin only); point the config treasury
+    /// at it to keep protocol fees behind the timelock
+    pub fn init_treasury(ctx: Context<InitTreasury>) -> Result<()> {
+        ctx.accounts.treasury.bump = ctx.bumps.treasury;
+
+        msg!("Treasury created at {}", ctx.accounts.treasury.key());
+
+        Ok(())
+    }
+
+    /// Queue a sensitive admin action, executable once the config's timelock delay has
+    /// passed (admin only). The admin may be a multisig vault PDA signing through its
+    /// program's CPI; a separate payer funds rent.
+    pub fn queue_action(ctx: Context<QueueAction>, action: TimelockAction) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let queued = &mut ctx.accounts.queued_action;
+        let clock = Clock::get()?;
+
+        action.validate(config)?;
+
+        queued.index = config.queued_count;
+        queued.action = action;
+        queued.eta = clock.unix_timestamp + config.timelock_delay;
+        queued.bump = ctx.bumps.queued_action;
+
+        config.queued_count += 1;
+
+        msg!(
+            "Action {} queued, executable at {}",
+            queued.index,
+            queued.eta
+        );
+
+        Ok(())
+    }
+
+    /// Execute a queued action whose delay has passed (admin only)
+    pub fn execute_queued(ctx: Context<ExecuteQueued>) -> Result<()> {
+        let queued = &ctx.accounts.queued_action;
+        let clock = Clock::get()?;
+
+        require!(
+            clock.unix_timestamp >= queued.eta,
+            PredictDuelError::TimelockNotElapsed
+        );
+        // Conditions may have changed while queued (e.g. governance went live)
+        queued.action.validate(&ctx.accounts.config)?;
+
+        let config = &mut ctx.accounts.config;
+        match queued.action {
+            TimelockAction::SetProtocolFee {
+                protocol_fee_bps,
+                treasury,
+            } => {
+                config.protocol_fee_bps = protocol_fee_bps;
+                config.treasury = treasury;
+                msg!("Protocol fee set to {} bps", protocol_fee_bps);
+            }
+            TimelockAction::WithdrawTreasury { amount, recipient } => {
+                let treasury = ctx
+                    .accounts
+                    .treasury
+                    .as_ref()
+                    .ok_or(PredictDuelError::TimelockAccountMismatch)?;
+                let recipient_info = ctx
+                    .accounts
+                    .recipient
+                    .as_ref()
+                    .filter(|account| account.key() == recipient)
+                    .ok_or(PredictDuelError::TimelockAccountMismatch)?;
+
+                let treasury_info = treasury.to_account_info();
+                let rent_floor = Rent::get()?.minimum_balance(treasury_info.data_len());
+                require!(
+                    treasury_info.lamports().saturating_sub(rent_floor) >= amount,
+                    PredictDuelError::InsufficientTreasuryFunds
+                );
+
+                treasury.sub_lamports(amount)?;
+                recipient_info.add_lamports(amount)?;
+                msg!(
+                    "Withdrew {} SOL from treasury to {}",
+                    amount as f64 / 1_000_000_000.0,
+                    recipient
+                );
+            }
+            TimelockAction::RotateAdmin { new_admin } => {
+                config.pending_admin = new_admin;
+                msg!("Admin rotation to {} pending acceptance", new_admin);
+            }
+            TimelockAction::SetTimelockDelay { delay } => {
+                config.timelock_delay = delay;
+                msg!("Timelock delay set to {}s", delay);
+            }
+            TimelockAction::SetCategoryParams { category, params } => {
+                config.category_params[category as usize] = params;
+                msg!("Category {} parameters updated", category as usize);
+            }
+            TimelockAction::SetCreationFee {
+                creation_fee,
+                creator_pass_mint,
+                fee_waiver_score_bps,
+            } => {
+                config.creation_fee = creation_fee;
+                config.creator_pass_mint = creator_pass_mint;
+                config.fee_waiver_score_bps = fee_waiver_score_bps;
+                msg!(
+                    "Creation fee set to {} SOL",
+                    creation_fee as f64 / 1_000_000_000.0
+                );
+            }
+            TimelockAction::SetOrderBookFees {
+                taker_fee_bps,
+                maker_rebate_bps,
+            } => {
+                // Offers keep the rates in force when they were posted
+                config.taker_fee_bps = taker_fee_bps;
+                config.maker_rebate_bps = maker_rebate_bps;
+                msg!(
+                    "Order book fees: {} bps taker, {} bps maker rebate",
+                    taker_fee_bps,
+                    maker_rebate_bps
+                );
+            }
+            TimelockAction::SetFrontendFeeShare { share_bps } => {
+                config.frontend_fee_share_bps = share_bps;
+                msg!("Frontend fee share set to {} bps", share_bps);
+            }
+            // Governance changes run through execute_governance_action, not at the admin's discretion
+            TimelockAction::Governance(_) => return err!(PredictDuelError::Unauthorized),
+        }
+
+        Ok(())
+    }
+
+    /// Drop a queued action before it executes (admin only). Passed governance changes
+    /// can't be vetoed.
+    pub fn cancel_queued(ctx: Context<CancelQueued>) -> Result<()> {
+        require!(
+            !matches!(
+                ctx.accounts.queued_action.action,
+                TimelockAction::Governance(_)
+            ),
+            PredictDuelError::Unauthorized
+        );
+
+        msg!(
+            "Queued action {} cancelled",
+            ctx.accounts.queued_action.index
+        );
+
+        Ok(())
+    }
+
+    /// Freeze a broken market (admin only): betting, resolution and claims stop, and
+    /// stakes become force-refundable once EMERGENCY_REFUND_DELAY has passed
+    pub fn emergency_halt_market(ctx: Context<EmergencyHaltMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(
+            market.status == MarketStatus::Pending || market.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+
+        market.status = MarketStatus::Halted;
+        market.halted_at = clock.unix_timestamp;
+        if let Some(creator_profile) = &mut ctx.accounts.creator_profile {
+            creator_profile.markets_disputed += 1;
+        }
+
+        emit!(MarketHalted {
+            market: market.key(),
+            admin: ctx.accounts.admin.key(),
+            halted_at: market.halted_at,
+            refundable_at: market.halted_at + EMERGENCY_REFUND_DELAY,
+        });
+
+        msg!(
+            "Market halted; emergency refunds open at {}",
+            market.halted_at + EMERGENCY_REFUND_DELAY
+        );
+
+        Ok(())
+    }
+
+    /// Refund the stakes of a halted market once its delay has passed (admin only).
+    /// Pass (participant, bettor) pairs as remaining accounts; may be called in batches.
+    /// The market is cancelled, so positions settled elsewhere (cross-chain, receipts)
+    /// use their normal refund paths.
+    pub fn emergency_refund_all<'info>(
+        ctx: Context<'_, '_, 'info, 'info, EmergencyRefundAll<'info>>,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(
+            market.halted_at != 0
+                && (market.status == MarketStatus::Halted
+                    || market.status == MarketStatus::Cancelled),
+            PredictDuelError::MarketNotHalted
+        );
+        require!(
+            clock.unix_timestamp >= market.halted_at + EMERGENCY_REFUND_DELAY,
+            PredictDuelError::TimelockNotElapsed
+        );
+        require!(
+            ctx.remaining_accounts.len().is_multiple_of(2),
+            PredictDuelError::InvalidRefundBatch
+        );
+        require!(market.lst_tokens == 0, PredictDuelError::LstNotUnwound);
+        market.status = MarketStatus::Cancelled;
+        market.settled_at = clock.unix_timestamp;
+
+        let market_key = market.key();
+        let index_bytes = market.market_index.to_le_bytes();
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &index_bytes,
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        let mut refunded = 0u32;
+        let mut total = 0u64;
+        for pair in ctx.remaining_accounts.chunks(2) {
+            let mut participant = Account::<Participant>::try_from(&pair[0])?;
+            require!(
+                participant.market == market_key && participant.bettor == pair[1].key(),
+                PredictDuelError::InvalidRefundBatch
+            );
+            if participant.claimed || participant.assert_settles_locally().is_err() {
+                continue;
+            }
+
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.market_vault.to_account_info(),
+                        to: pair[1].clone(),
+                    },
+                    signer,
+                ),
+                participant.stake,
+            )?;
+
+            participant.claimed = true;
+            participant.exit(&crate::ID)?;
+            refunded += 1;
+            total += participant.stake;
+        }
+
+        emit!(EmergencyRefund {
+            market: market_key,
+            admin: ctx.accounts.admin.key(),
+            refunded,
+            amount: total,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Emergency refund: {} SOL to {} bettors",
+            total as f64 / 1_000_000_000.0,
+            refunded
+        );
+
+        Ok(())
+    }
+
+    /// Block an address from creating markets or betting (admin only)
+    pub fn block_address(ctx: Context<BlockAddress>, address: Pubkey) -> Result<()> {
+        let entry = &mut ctx.accounts.blocklist_entry;
+        let clock = Clock::get()?;
+
+        entry.address = address;
+        entry.blocked_at = clock.unix_timestamp;
+        entry.bump = ctx.bumps.blocklist_entry;
+
+        emit!(AddressBlocked {
+            address,
+            admin: ctx.accounts.admin.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Address blocked: {}", address);
+
+        Ok(())
+    }
+
+    /// Remove an address from the blocklist (admin only)
+    pub fn unblock_address(ctx: Context<UnblockAddress>) -> Result<()> {
+        let address = ctx.accounts.blocklist_entry.address;
+
+        emit!(AddressUnblocked {
+            address,
+            admin: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Address unblocked: {}", address);
+
+        Ok(())
+    }
+
+    /// Require bets to carry a recent attestation from `verifier` (admin only);
+    /// the default pubkey turns the gate off
+    pub fn set_attestation_verifier(
+        ctx: Context<SetAttestationVerifier>,
+        verifier: Pubkey,
+        max_age: i64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            verifier == Pubkey::default() || (max_age > 0 && max_age <= MAX_ATTESTATION_AGE),
+            PredictDuelError::InvalidAttestation
+        );
+
+        config.attestation_verifier = verifier;
+        config.attestation_max_age = max_age;
+
+        msg!("Attestation verifier set to {}", verifier);
+
+        Ok(())
+    }
+
+    /// Cap how often a single wallet can bet (admin only); 0 disables a cap
+    pub fn set_rate_limits(
+        ctx: Context<SetRateLimits>,
+        max_bets_per_slot: u16,
+        max_bets_per_minute: u16,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        config.max_bets_per_slot = max_bets_per_slot;
+        config.max_bets_per_minute = max_bets_per_minute;
+
+        msg!(
+            "Rate limits: {} per slot, {} per minute",
+            max_bets_per_slot,
+            max_bets_per_minute
+        );
+
+        Ok(())
+    }
+
+    /// Restrict betting to holders of a personhood credential (e.g. a soulbound token
+    /// mint), one bet per person; creator only, before any bets
+    pub fn set_personhood_gate(
+        ctx: Context<SetPersonhoodGate>,
+        mint: Option<Pubkey>,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        // Sealed bets go through commit_bet, which can't check credentials
+        require!(!market.sealed, PredictDuelError::SealedMarket);
+
+        market.personhood_mint = mint;
+
+        msg!("Personhood gate set");
+
+        Ok(())
+    }
+
+    /// Set the volume-based fee tiers (admin only); tiers only ever discount a market's fee
+    pub fn set_fee_tiers(
+        ctx: Context<SetFeeTiers>,
+        fee_tiers: [FeeTier; FEE_TIER_COUNT],
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            fee_tiers
+                .iter()
+                .all(|tier| tier.fee_bps <= MAX_PROTOCOL_FEE_BPS),
+            PredictDuelError::InvalidProtocolFee
+        );
+
+        config.fee_tiers = fee_tiers;
+
+        msg!("Fee tiers updated");
+
+        Ok(())
+    }
+
+    /// Set the reputation floor below which creators face a pool cap and must name a
+    /// third-party resolver (admin only)
+    pub fn set_creator_limits(
+        ctx: Context<SetCreatorLimits>,
+        min_creator_score_bps: u16,
+        low_score_max_pool: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            min_creator_score_bps as u64 <= BPS_DENOMINATOR,
+            PredictDuelError::InvalidCreatorScore
+        );
+
+        config.min_creator_score_bps = min_creator_score_bps;
+        config.low_score_max_pool = low_score_max_pool;
+
+        msg!(
+            "Creator limits: score below {} bps capped at {} SOL",
+            min_creator_score_bps,
+            low_score_max_pool as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Set the bond creators post with each new market (admin only)
+    pub fn set_creation_bond(ctx: Context<SetCreationBond>, creation_bond: u64) -> Result<()> {
+        require!(
+            creation_bond <= MAX_CREATION_BOND,
+            PredictDuelError::InvalidCreationBond
+        );
+
+        ctx.accounts.config.creation_bond = creation_bond;
+
+        msg!(
+            "Creation bond set to {} SOL",
+            creation_bond as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Return the creation bond to the creator once the market settled without a forfeit
+    pub fn reclaim_bond(ctx: Context<ReclaimBond>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(
+            matches!(
+                market.status,
+                MarketStatus::Resolved | MarketStatus::Voided | MarketStatus::Cancelled
+            ),
+            PredictDuelError::MarketNotResolved
+        );
+        require!(!market.bond_forfeited(), PredictDuelError::BondForfeited);
+        require!(market.creation_bond > 0, PredictDuelError::NoBond);
+
+        let bond = market.creation_bond;
+        market.creation_bond = 0;
+        market.sub_lamports(bond)?;
+        ctx.accounts.creator.add_lamports(bond)?;
+
+        msg!(
+            "Creation bond returned: {} SOL",
+            bond as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Send a forfeited creation bond to the treasury (permissionless)
+    pub fn forfeit_bond(ctx: Context<ForfeitBond>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(market.bond_forfeited(), PredictDuelError::BondNotForfeited);
+        require!(market.creation_bond > 0, PredictDuelError::NoBond);
+
+        let bond = market.creation_bond;
+        market.creation_bond = 0;
+        market.sub_lamports(bond)?;
+        ctx.accounts.treasury.add_lamports(bond)?;
+
+        msg!(
+            "Creation bond forfeited: {} SOL",
+            bond as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Flag a market as ambiguous or rule-breaking by staking config.flag_stake;
+    /// reaching the flag threshold freezes betting until the moderator reviews it
+    pub fn flag_market(ctx: Context<FlagMarket>, reason: FlagReason) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let flags = &mut ctx.accounts.flags;
+        let config = &ctx.accounts.config;
+
+        require!(
+            market.status == MarketStatus::Pending || market.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        require!(
+            flags.review == FlagReview::Pending,
+            PredictDuelError::FlagsReviewed
+        );
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.reporter.to_account_info(),
+                    to: ctx.accounts.flag_record.to_account_info(),
+                },
+            ),
+            config.flag_stake,
+        )?;
+
+        let flag_record = &mut ctx.accounts.flag_record;
+        flag_record.market = market.key();
+        flag_record.reporter = ctx.accounts.reporter.key();
+        flag_record.reason = reason;
+        flag_record.stake = config.flag_stake;
+        flag_record.bump = ctx.bumps.flag_record;
+
+        flags.market = market.key();
+        flags.bump = ctx.bumps.flags;
+        flags.flag_count += 1;
+        flags.total_staked += config.flag_stake;
+
+        if config.flag_threshold > 0 && flags.flag_count >= config.flag_threshold && !market.frozen
+        {
+            market.frozen = true;
+            msg!("Market frozen pending moderator review");
+        }
+
+        msg!("Market flagged ({} flags)", flags.flag_count);
+
+        Ok(())
+    }
+
+    /// Rule on a market's flags (moderator only). Upheld flags are refunded and the
+    /// market stays frozen; dismissed flags are slashed and betting resumes.
+    pub fn review_flags(ctx: Context<ReviewFlags>, upheld: bool) -> Result<()> {
+        let flags = &mut ctx.accounts.flags;
+
+        require!(
+            flags.review == FlagReview::Pending,
+            PredictDuelError::FlagsReviewed
+        );
+
+        if upheld {
+            flags.review = FlagReview::Upheld;
+        } else {
+            flags.review = FlagReview::Dismissed;
+            // A delisted market stays frozen
+            let market = &mut ctx.accounts.market;
+            market.frozen = market.delisted;
+        }
+
+        msg!("Flags {}", if upheld { "upheld" } else { "dismissed" });
+
+        Ok(())
+    }
+
+    /// Close a flag: the stake is returned unless the flags were dismissed, in which
+    /// case it goes to the treasury. Unreviewed flags settle once the market does.
+    pub fn withdraw_flag(ctx: Context<WithdrawFlag>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let flag_record = &ctx.accounts.flag_record;
+
+        match ctx.accounts.flags.review {
+            FlagReview::Pending => require!(
+                matches!(
+                    market.status,
+                    MarketStatus::Resolved | MarketStatus::Voided | MarketStatus::Cancelled
+                ),
+                PredictDuelError::FlagsPending
+            ),
+            FlagReview::Upheld => {}
+            FlagReview::Dismissed => {
+                flag_record.sub_lamports(flag_record.stake)?;
+                ctx.accounts.treasury.add_lamports(flag_record.stake)?;
+                msg!(
+                    "Flag stake slashed: {} SOL",
+                    flag_record.stake as f64 / 1_000_000_000.0
+                );
+            }
+        }
+
+        ctx.accounts.flags.total_staked -= flag_record.stake;
+
+        Ok(())
+    }
+
+    /// Set the flag stake and the freeze threshold (admin only; threshold 0 disables freezing)
+    pub fn set_flag_params(
+        ctx: Context<SetFlagParams>,
+        flag_stake: u64,
+        flag_threshold: u32,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        config.flag_stake = flag_stake;
+        config.flag_threshold = flag_threshold;
+
+        msg!(
+            "Flags: {} SOL stake, freeze at {}",
+            flag_stake as f64 / 1_000_000_000.0,
+            flag_threshold
+        );
+
+        Ok(())
+    }
+
+    /// Hide a market from listings and freeze betting (moderator only)
+    pub fn delist_market(ctx: Context<ModerateMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(!market.delisted, PredictDuelError::AlreadyDelisted);
+
+        market.delisted = true;
+        market.frozen = true;
+
+        emit!(MarketDelisted {
+            market: market.key(),
+            moderator: ctx.accounts.moderator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Market delisted");
+
+        Ok(())
+    }
+
+    /// Undo a delisting and reopen betting (moderator only)
+    pub fn relist_market(ctx: Context<ModerateMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(market.delisted, PredictDuelError::NotDelisted);
+
+        market.delisted = false;
+        market.frozen = false;
+
+        emit!(MarketRelisted {
+            market: market.key(),
+            moderator: ctx.accounts.moderator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Market relisted");
+
+        Ok(())
+    }
+
+    /// Void an abusive market so every bettor can reclaim their stake via refund_stake
+    /// (moderator only); counts as a dispute against the creator and forfeits their bond
+    pub fn moderator_void_market(ctx: Context<ModeratorVoidMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(
+            market.status == MarketStatus::Pending || market.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        market.status = MarketStatus::Voided;
+        market.settled_at = Clock::get()?.unix_timestamp;
+        market.force_voided = true;
+        market.delisted = true;
+        if let Some(creator_profile) = &mut ctx.accounts.creator_profile {
+            creator_profile.markets_disputed += 1;
+        }
+
+        emit!(MarketVoidedByModerator {
+            market: market.key(),
+            moderator: ctx.accounts.moderator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Market voided by moderator: stakes will be refunded");
+
+        Ok(())
+    }
+
+    /// Create the featured-markets registry (admin only)
+    pub fn init_featured(ctx: Context<InitFeatured>) -> Result<()> {
+        ctx.accounts.featured.bump = ctx.bumps.featured;
+
+        msg!("Featured registry created");
+
+        Ok(())
+    }
+
+    /// Replace the featured carousel with `markets`, in display order (admin or moderator).
+    /// Pass each market account as a remaining account, in the same order.
+    pub fn set_featured_markets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetFeaturedMarkets<'info>>,
+        markets: Vec<Pubkey>,
+    ) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let config = &ctx.accounts.config;
+
+        require!(
+            authority == config.admin || authority == config.moderator,
+            PredictDuelError::Unauthorized
+        );
+        require!(
+            markets.len() <= MAX_FEATURED_MARKETS,
+            PredictDuelError::TooManyFeatured
+        );
+        require!(
+            ctx.remaining_accounts.len() == markets.len(),
+            PredictDuelError::InvalidFeaturedList
+        );
+
+        for (index, (key, account)) in markets.iter().zip(ctx.remaining_accounts).enumerate() {
+            let market = Account::<Market>::try_from(account)?;
+            require!(
+                account.key() == *key && !markets[..index].contains(key),
+                PredictDuelError::InvalidFeaturedList
+            );
+            require!(!market.delisted, PredictDuelError::AlreadyDelisted);
+        }
+
+        let featured = &mut ctx.accounts.featured;
+        featured.markets = markets;
+        featured.updated_at = Clock::get()?.unix_timestamp;
+
+        msg!("Featured markets updated ({})", featured.markets.len());
+
+        Ok(())
+    }
+
+    /// Create or replace a market's metadata extension (creator only, before any bets).
+    /// Can be sent in the same transaction as create_market.
+    pub fn set_market_extension(
+        ctx: Context<SetMarketExtension>,
+        language: String,
+        image_uri: String,
+        resolution_sources: Vec<String>,
+        external_id: String,
+    ) -> Result<()> {
+        ctx.accounts
+            .market
+            .assert_configurable(&ctx.accounts.creator.key())?;
+
+        require!(
+            language.len() <= MAX_LANGUAGE_LEN
+                && image_uri.len() <= MAX_IMAGE_URI_LEN
+                && external_id.len() <= MAX_EXTERNAL_ID_LEN,
+            PredictDuelError::ExtensionFieldTooLong
+        );
+        require!(
+            resolution_sources.len() <= MAX_RESOLUTION_SOURCES
+                && resolution_sources
+                    .iter()
+                    .all(|source| source.len() <= MAX_RESOLUTION_SOURCE_LEN),
+            PredictDuelError::ExtensionFieldTooLong
+        );
+
+        let extension = &mut ctx.accounts.extension;
+        extension.market = ctx.accounts.market.key();
+        extension.language = language;
+        extension.image_uri = image_uri;
+        extension.resolution_sources = resolution_sources;
+        extension.external_id = external_id;
+        extension.bump = ctx.bumps.extension;
+
+        msg!("Market extension set");
+
+        Ok(())
+    }
+
+    /// Mark a market as part of a rolling series that keepers roll over every
+    /// `interval` seconds (creator only, before any bets)
+    pub fn set_series(ctx: Context<SetSeries>, interval: i64, migrate_seed: bool) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            interval == 0 || interval >= MIN_SERIES_INTERVAL,
+            PredictDuelError::InvalidSeriesInterval
+        );
+
+        market.series_interval = interval;
+        market.series_migrate_seed = migrate_seed;
+
+        msg!("Series interval set to {}s", interval);
+
+        Ok(())
+    }
+
+    /// Prepay the creation fees and bonds of a creator's series rollovers
+    pub fn fund_series(ctx: Context<FundSeries>, amount: u64) -> Result<()> {
+        let series_fund = &mut ctx.accounts.series_fund;
+        series_fund.creator = ctx.accounts.creator.key();
+        series_fund.bump = ctx.bumps.series_fund;
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: series_fund.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Series fund topped up with {} lamports", amount);
+
+        Ok(())
+    }
+
+    /// Close a creator's series fund and reclaim what rollovers haven't spent
+    pub fn close_series_fund(_ctx: Context<CloseSeriesFund>) -> Result<()> {
+        msg!("Series fund closed");
+
+        Ok(())
+    }
+
+    /// Create the next market of a settled series (permissionless; the keeper pays rent).
+    /// The next market takes index + 1 under the same creator and inherits the question,
+    /// stake, resolver and pool cap; oracle conditions are not carried over. Its creation
+    /// fee and bond come out of the creator's series fund. A migrated seed waits for the
+    /// resolved market's claim lock.
+    pub fn rollover(ctx: Context<Rollover>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(
+            market.series_interval > 0,
+            PredictDuelError::NotSeriesMarket
+        );
+        require!(!market.rolled_over, PredictDuelError::AlreadyRolledOver);
+        require!(
+            market.status == MarketStatus::Resolved || market.status == MarketStatus::Voided,
+            PredictDuelError::MarketNotResolved
+        );
+
+        // Skip any periods missed while the market awaited resolution
+        let periods = (clock.unix_timestamp - market.deadline).max(0) / market.series_interval + 1;
+        let deadline = periods
+            .checked_mul(market.series_interval)
+            .and_then(|offset| market.deadline.checked_add(offset))
+            .ok_or(PredictDuelError::MathOverflow)?;
+
+        let next = &mut ctx.accounts.next_market;
+        next.bump = ctx.bumps.next_market;
+        next.vault_bump = ctx.bumps.next_market_vault;
+        next.creator = market.creator;
+        next.market_index = market.market_index + 1;
+        next.question = market.question.clone();
+        next.category = market.category;
+        next.stake_amount = market.stake_amount;
+        next.deadline = deadline;
+        next.market_type = market.market_type.clone();
+        next.created_at = clock.unix_timestamp;
+        next.created_slot = clock.slot;
+        next.init_state(clock.unix_timestamp);
+        next.apply_category_params(&ctx.accounts.config, clock.unix_timestamp)?;
+        next.resolver = market.resolver;
+        next.max_pool = market.max_pool;
+        next.personhood_mint = market.personhood_mint;
+        next.series_interval = market.series_interval;
+        next.series_migrate_seed = market.series_migrate_seed;
+
+        let creator_profile = &mut ctx.accounts.creator_profile;
+        register_market(
+            next,
+            &ctx.accounts.config,
+            creator_profile,
+            ctx.accounts.creator_stats.as_deref_mut(),
+            ctx.accounts.category_stats.as_deref_mut(),
+        )?;
+        charge_prepaid_creation(
+            next,
+            &ctx.accounts.config,
+            creator_profile,
+            ctx.accounts.creator_pass.as_deref().map(|pass| &**pass),
+            &ctx.accounts.series_fund.to_account_info(),
+            ctx.accounts
+                .treasury
+                .as_ref()
+                .map(|treasury| treasury.to_account_info()),
+        )?;
+
+        let mut migrated = 0;
+        if market.series_migrate_seed
+            && !market.seed_claimed
+            && market.seed_yes + market.seed_no > 0
+        {
+            migrated = market.seed_payout(clock.unix_timestamp)?;
+            market.seed_claimed = true;
+        }
+        if migrated > 0 {
+            let seeds = &[
+                b"market_vault",
+                market.creator.as_ref(),
+                &market.market_index.to_le_bytes(),
+                &[ctx.bumps.market_vault],
+            ];
+            let signer = &[&seeds[..]];
+
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.market_vault.to_account_info(),
+                        to: ctx.accounts.next_market_vault.to_account_info(),
+                    },
+                    signer,
+                ),
+                migrated,
+            )?;
+
+            // Keep the sponsor's YES/NO split
+            let seed_yes = (migrated as u128 * market.seed_yes as u128
+                / (market.seed_yes + market.seed_no) as u128) as u64;
+            next.add_seed(seed_yes, migrated - seed_yes, clock.unix_timestamp);
+        }
+
+        let odds_history = &mut ctx.accounts.next_odds_history;
+        odds_history.market = next.key();
+        odds_history.bump = ctx.bumps.next_odds_history;
+        odds_history.record(clock.unix_timestamp, next.yes_pool, next.no_pool);
+
+        market.rolled_over = true;
+
+        emit!(MarketRolledOver {
+            previous: market.key(),
+            next: next.key(),
+            deadline,
+            migrated_seed: migrated,
+        });
+
+        msg!("Series rolled over to market {}", next.market_index);
+
+        Ok(())
+    }
+
+    /// Save a reusable market definition for schedules to create markets from
+    pub fn create_template(
+        ctx: Context<CreateTemplate>,
+        template_id: u64,
+        question: String,
+        category: MarketCategory,
+        stake_amount: u64,
+        market_type: MarketType,
+        duration: i64,
+    ) -> Result<()> {
+        require!(question.len() <= 200, PredictDuelError::QuestionTooLong);
+        require!(duration > 0, PredictDuelError::InvalidDeadline);
+
+        let template = &mut ctx.accounts.template;
+        template.creator = ctx.accounts.creator.key();
+        template.template_id = template_id;
+        template.question = question;
+        template.category = category;
+        template.stake_amount = stake_amount;
+        template.market_type = market_type;
+        template.duration = duration;
+        template.resolver = ctx
+            .accounts
+            .resolver
+            .as_ref()
+            .map(|resolver| resolver.key());
+        template.bump = ctx.bumps.template;
+
+        msg!("Template {} created", template_id);
+
+        Ok(())
+    }
+
+    /// Schedule `runs` markets from a template, one every `interval` seconds starting at
+    /// `first_run`. Markets use indices from `start_index` up, which the creator must
+    /// keep free. `prepay` lamports go onto the schedule to cover each run's creation fee
+    /// and bond.
+    pub fn create_schedule(
+        ctx: Context<CreateSchedule>,
+        start_index: u64,
+        interval: i64,
+        first_run: i64,
+        runs: u32,
+        prepay: u64,
+    ) -> Result<()> {
+        require!(
+            interval >= MIN_SERIES_INTERVAL,
+            PredictDuelError::InvalidSeriesInterval
+        );
+        require!(runs > 0, PredictDuelError::InvalidSchedule);
+
+        let schedule = &mut ctx.accounts.schedule;
+        schedule.template = ctx.accounts.template.key();
+        schedule.creator = ctx.accounts.creator.key();
+        schedule.interval = interval;
+        schedule.next_run = first_run;
+        schedule.next_market_index = start_index;
+        schedule.runs_remaining = runs;
+        schedule.bump = ctx.bumps.schedule;
+
+        if prepay > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: schedule.to_account_info(),
+                    },
+                ),
+                prepay,
+            )?;
+        }
+
+        msg!("Schedule created: {} runs every {}s", runs, interval);
+
+        Ok(())
+    }
+
+    /// Create the schedule's next market once its run time arrives (permissionless; the
+    /// keeper pays rent). The creation fee and bond come out of the lamports the creator
+    /// prepaid onto the schedule.
+    pub fn execute_schedule(ctx: Context<ExecuteSchedule>) -> Result<()> {
+        let schedule = &mut ctx.accounts.schedule;
+        let template = &ctx.accounts.template;
+        let clock = Clock::get()?;
+
+        require!(
+            schedule.runs_remaining > 0,
+            PredictDuelError::ScheduleFinished
+        );
+        require!(
+            clock.unix_timestamp >= schedule.next_run,
+            PredictDuelError::ScheduleNotDue
+        );
+
+        // Skip runs whose market would already be past its deadline
+        let deadline = schedule.next_run + template.duration;
+        if deadline <= clock.unix_timestamp {
+            let skipped = (clock.unix_timestamp - deadline) / schedule.interval + 1;
+            schedule.next_run += skipped * schedule.interval;
+        }
+        let deadline = schedule.next_run + template.duration;
+
+        let market = &mut ctx.accounts.market;
+        market.bump = ctx.bumps.market;
+        market.vault_bump = ctx.bumps.market_vault;
+        market.creator = schedule.creator;
+        market.market_index = schedule.next_market_index;
+        market.question = template.question.clone();
+        market.category = template.category;
+        market.stake_amount = template.stake_amount;
+        market.deadline = deadline;
+        market.market_type = template.market_type.clone();
+        market.created_at = clock.unix_timestamp;
+        market.created_slot = clock.slot;
+        market.init_state(clock.unix_timestamp);
+        market.apply_category_params(&ctx.accounts.config, clock.unix_timestamp)?;
+        market.resolver = template.resolver;
+
+        let creator_profile = &mut ctx.accounts.creator_profile;
+        creator_profile.wallet = schedule.creator;
+        creator_profile.bump = ctx.bumps.creator_profile;
+        register_market(
+            market,
+            &ctx.accounts.config,
+            creator_profile,
+            ctx.accounts.creator_stats.as_deref_mut(),
+            ctx.accounts.category_stats.as_deref_mut(),
+        )?;
+        charge_prepaid_creation(
+            market,
+            &ctx.accounts.config,
+            creator_profile,
+            ctx.accounts.creator_pass.as_deref().map(|pass| &**pass),
+            &schedule.to_account_info(),
+            ctx.accounts
+                .treasury
+                .as_ref()
+                .map(|treasury| treasury.to_account_info()),
+        )?;
+
+        let odds_history = &mut ctx.accounts.odds_history;
+        odds_history.market = market.key();
+        odds_history.bump = ctx.bumps.odds_history;
+
+        schedule.next_run += schedule.interval;
+        schedule.next_market_index += 1;
+        schedule.runs_remaining -= 1;
+
+        msg!(
+            "Scheduled market {} created: {}",
+            market.market_index,
+            market.question
+        );
+
+        Ok(())
+    }
+
+    /// Add lamports to a schedule's prepaid creation fees and bonds (creator only)
+    pub fn fund_schedule(ctx: Context<FundSchedule>, amount: u64) -> Result<()> {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.schedule.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Schedule funded with {} lamports", amount);
+
+        Ok(())
+    }
+
+    /// Stop a schedule and reclaim its rent and unspent prepayment (creator only)
+    pub fn cancel_schedule(_ctx: Context<CancelSchedule>) -> Result<()> {
+        msg!("Schedule cancelled");
+
+        Ok(())
+    }
+
+    /// Set the SPL stake pool LST-yield markets deposit into (admin only)
+    pub fn set_lst_stake_pool(ctx: Context<SetLstStakePool>, stake_pool: Pubkey) -> Result<()> {
+        ctx.accounts.config.lst_stake_pool = stake_pool;
+
+        msg!("LST stake pool set to {}", stake_pool);
+
+        Ok(())
+    }
+
+    /// Let keepers stake this market's idle escrow until settlement (creator only, before
+    /// any bets). Yield goes to the winners or, if not, the treasury.
+    pub fn enable_lst_yield(ctx: Context<EnableLstYield>, yield_to_winners: bool) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            ctx.accounts.config.lst_stake_pool != Pubkey::default(),
+            PredictDuelError::LstDisabled
+        );
+
+        market.lst_enabled = true;
+        market.lst_yield_to_winners = yield_to_winners;
+
+        msg!("LST yield enabled");
+
+        Ok(())
+    }
+
+    /// Deposit escrowed SOL into the stake pool while betting is open (permissionless).
+    /// At most MAX_LST_DEPOSIT_BPS of the escrow is deposited.
+    pub fn deposit_to_lst(ctx: Context<DepositToLst>, amount: u64) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(market.lst_enabled, PredictDuelError::LstDisabled);
+        require!(
+            market.status == MarketStatus::Pending || market.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        require!(
+            clock.unix_timestamp < market.deadline,
+            PredictDuelError::MarketExpired
+        );
+
+        let escrow = ctx.accounts.market_vault.lamports() + market.lst_deposited;
+        let cap = (escrow as u128 * MAX_LST_DEPOSIT_BPS as u128 / BPS_DENOMINATOR as u128) as u64;
+        require!(
+            amount > 0 && market.lst_deposited + amount <= cap,
+            PredictDuelError::LstDepositCapExceeded
+        );
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let tokens_before = ctx.accounts.market_lst.amount;
+        anchor_lang::solana_program::program::invoke_signed(
+            &stake_pool_deposit_sol_ix(ctx.accounts, amount),
+            &[
+                ctx.accounts.stake_pool.to_account_info(),
+                ctx.accounts.stake_pool_withdraw_authority.to_account_info(),
+                ctx.accounts.reserve_stake.to_account_info(),
+                ctx.accounts.market_vault.to_account_info(),
+                ctx.accounts.market_lst.to_account_info(),
+                ctx.accounts.manager_fee_account.to_account_info(),
+                ctx.accounts.pool_mint.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.stake_pool_program.to_account_info(),
+            ],
+            &[&seeds[..]],
+        )?;
+
+        ctx.accounts.market_lst.reload()?;
+        let minted = ctx.accounts.market_lst.amount - tokens_before;
+
+        let market = &mut ctx.accounts.market;
+        market.lst_deposited += amount;
+        market.lst_tokens = ctx.accounts.market_lst.amount;
+
+        msg!(
+            "Staked {} SOL for {} pool tokens",
+            amount as f64 / 1_000_000_000.0,
+            minted
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw a settled (or halted) market's pool tokens back to SOL (permissionless).
+    /// Yield goes to the winners' pool on resolved opted-in markets and to the treasury
+    /// otherwise; a shortfall reduces the winners' pool or, on refunded markets, is
+    /// covered from the creator's bond.
+    pub fn unwind_lst(ctx: Context<UnwindLst>) -> Result<()> {
+        let market = &ctx.accounts.market;
+
+        require!(market.lst_tokens > 0, PredictDuelError::NoLstPosition);
+        require!(
+            matches!(
+                market.status,
+                MarketStatus::Resolved
+                    | MarketStatus::Voided
+                    | MarketStatus::Cancelled
+                    | MarketStatus::Halted
+            ),
+            PredictDuelError::MarketNotResolved
+        );
+
+        let creator = market.creator;
+        let index_bytes = market.market_index.to_le_bytes();
+        let seeds = &[
+            b"market_vault",
+            creator.as_ref(),
+            &index_bytes,
+            &[ctx.bumps.market_vault],
+        ];
+        let vault_before = ctx.accounts.market_vault.lamports();
+        anchor_lang::solana_program::program::invoke_signed(
+            &stake_pool_withdraw_sol_ix(ctx.accounts, ctx.accounts.market_lst.amount),
+            &[
+                ctx.accounts.stake_pool.to_account_info(),
+                ctx.accounts.stake_pool_withdraw_authority.to_account_info(),
+                ctx.accounts.market_vault.to_account_info(),
+                ctx.accounts.market_lst.to_account_info(),
+                ctx.accounts.reserve_stake.to_account_info(),
+                ctx.accounts.manager_fee_account.to_account_info(),
+                ctx.accounts.pool_mint.to_account_info(),
+                ctx.accounts.clock.to_account_info(),
+                ctx.accounts.stake_history.to_account_info(),
+                ctx.accounts.stake_program.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.stake_pool_program.to_account_info(),
+            ],
+            &[&seeds[..]],
+        )?;
+        let returned = ctx.accounts.market_vault.lamports() - vault_before;
+
+        let market = &mut ctx.accounts.market;
+        let deposited = market.lst_deposited;
+        market.lst_deposited = 0;
+        market.lst_tokens = 0;
+
+        if returned >= deposited {
+            let earned = returned - deposited;
+            if market.status == MarketStatus::Resolved && market.lst_yield_to_winners {
+                market.pool_size += earned;
+            } else if earned > 0 {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: ctx.accounts.market_vault.to_account_info(),
+                            to: ctx.accounts.treasury.to_account_info(),
+                        },
+                        &[&seeds[..]],
+                    ),
+                    earned,
+                )?;
+            }
+            msg!("LST unwound: {} SOL yield", earned as f64 / 1_000_000_000.0);
+        } else {
+            let shortfall = deposited - returned;
+            if market.status == MarketStatus::Resolved {
+                market.pool_size = market.pool_size.saturating_sub(shortfall);
+            } else {
+                let covered = shortfall.min(market.creation_bond);
+                market.creation_bond -= covered;
+                market.sub_lamports(covered)?;
+                ctx.accounts.market_vault.add_lamports(covered)?;
+            }
+            msg!(
+                "LST unwound: {} SOL shortfall",
+                shortfall as f64 / 1_000_000_000.0
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Split a position's future payout with other wallets (bettor only, before claiming).
+    /// Applied by claim_winnings; shares are in bps of the payout and the rest stays with
+    /// the winner.
+    pub fn set_payout_split(ctx: Context<SetPayoutSplit>, splits: Vec<PayoutSplit>) -> Result<()> {
+        let participant = &mut ctx.accounts.participant;
+
+        require!(!participant.claimed, PredictDuelError::AlreadyClaimed);
+        require!(
+            splits.len() <= MAX_PAYOUT_SPLITS
+                && splits.iter().all(|split| split.bps > 0)
+                && splits.iter().map(|split| split.bps as u64).sum::<u64>() <= BPS_DENOMINATOR,
+            PredictDuelError::InvalidPayoutSplit
+        );
+
+        participant.payout_splits = splits;
+
+        msg!(
+            "Payout split across {} recipients",
+            participant.payout_splits.len()
+        );
+
+        Ok(())
+    }
+
+    /// Set the charity that receives opted-in winnings donations (admin only)
+    pub fn set_charity(ctx: Context<SetCharity>, charity: Pubkey) -> Result<()> {
+        ctx.accounts.config.charity = charity;
+
+        msg!("Charity set to {}", charity);
+
+        Ok(())
+    }
+
+    /// Opt in to donating `charity_bps` of future winnings to the configured charity;
+    /// 0 opts out
+    pub fn set_charity_bps(ctx: Context<SetCharityBps>, charity_bps: u16) -> Result<()> {
+        require!(
+            charity_bps as u64 <= BPS_DENOMINATOR,
+            PredictDuelError::InvalidCharityBps
+        );
+        require!(
+            charity_bps == 0 || ctx.accounts.config.charity != Pubkey::default(),
+            PredictDuelError::CharityRequired
+        );
+
+        let user_profile = &mut ctx.accounts.user_profile;
+        user_profile.wallet = ctx.accounts.user.key();
+        user_profile.bump = ctx.bumps.user_profile;
+        user_profile.charity_bps = charity_bps;
+
+        msg!("Charity share set to {} bps", charity_bps);
+
+        Ok(())
+    }
+
+    /// Add to an oracle market's information bounty (creator or any sponsor). The bounty
+    /// goes to whoever first resolves the market from its oracle.
+    pub fn fund_bounty(ctx: Context<FundBounty>, amount: u64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let bounty = &mut ctx.accounts.bounty;
+
+        require!(
+            market.is_oracle_resolved(),
+            PredictDuelError::NotOracleMarket
+        );
+        require!(
+            market.status == MarketStatus::Pending || market.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        require!(amount > 0, PredictDuelError::StakeTooLow);
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.sponsor.to_account_info(),
+                    to: bounty.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        bounty.market = market.key();
+        bounty.amount += amount;
+        market.bounty_outstanding += amount;
+        bounty.bump = ctx.bumps.bounty;
+
+        msg!(
+            "Bounty raised to {} SOL",
+            bounty.amount as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Pay out a settled market's bounty (permissionless): to the bounty hunter, or back
+    /// to the creator if the market ended without an oracle resolution
+    pub fn claim_bounty(ctx: Context<ClaimBounty>) -> Result<()> {
+        let market = &ctx.accounts.market;
+
+        let recipient = match (&market.status, market.bounty_hunter) {
+            (MarketStatus::Resolved, Some(hunter)) => hunter,
+            (
+                MarketStatus::Resolved
+                | MarketStatus::Voided
+                | MarketStatus::Cancelled
+                | MarketStatus::Halted,
+                _,
+            ) => market.creator,
+            _ => return err!(PredictDuelError::MarketNotResolved),
+        };
+        require!(
+            ctx.accounts.recipient.key() == recipient,
+            PredictDuelError::InvalidBountyRecipient
+        );
+
+        ctx.accounts.market.bounty_outstanding = 0;
+
+        msg!(
+            "Bounty of {} SOL paid to {}",
+            ctx.accounts.bounty.amount as f64 / 1_000_000_000.0,
+            recipient
+        );
+
+        Ok(())
+    }
+
+    /// Enter the pool of stakers that dispute juries are drawn from
+    pub fn join_juror_pool(ctx: Context<JoinJurorPool>) -> Result<()> {
+        let juror_pool = &mut ctx.accounts.juror_pool;
+        let juror = ctx.accounts.juror.key();
+
+        require!(
+            ctx.accounts.stake_position.amount > 0,
+            PredictDuelError::InsufficientStake
+        );
+        require!(
+            !juror_pool.jurors.contains(&juror),
+            PredictDuelError::AlreadyJuror
+        );
+        require!(
+            juror_pool.jurors.len() < MAX_JUROR_POOL,
+            PredictDuelError::JurorPoolFull
+        );
+
+        juror_pool.jurors.push(juror);
+        juror_pool.bump = ctx.bumps.juror_pool;
+
+        msg!("Juror joined: {} in pool", juror_pool.jurors.len());
+
+        Ok(())
+    }
+
+    /// Leave the juror pool; juries already drawn are unaffected
+    pub fn leave_juror_pool(ctx: Context<LeaveJurorPool>) -> Result<()> {
+        let juror_pool = &mut ctx.accounts.juror_pool;
+
+        let index = juror_pool
+            .jurors
+            .iter()
+            .position(|juror| *juror == ctx.accounts.juror.key())
+            .ok_or(PredictDuelError::NotJuror)?;
+        juror_pool.jurors.swap_remove(index);
+
+        msg!("Juror left: {} in pool", juror_pool.jurors.len());
+
+        Ok(())
+    }
+
+    /// Challenge a market's resolution within the dispute window by posting DISPUTE_BOND.
+    /// Claims are paused and a jury is drawn from the Switchboard randomness account
+    /// committed alongside this instruction.
+    pub fn open_dispute(ctx: Context<OpenDispute>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(
+            market.status == MarketStatus::Resolved,
+            PredictDuelError::MarketNotResolved
+        );
+        require!(
+            clock.unix_timestamp <= market.resolved_at + ctx.accounts.config.dispute_window
+                && clock.unix_timestamp < market.claims_open_at(),
+            PredictDuelError::DisputeWindowClosed
+        );
+        require!(
+            ctx.accounts.juror_pool.jurors.len() >= JURY_SIZE,
+            PredictDuelError::JurorPoolTooSmall
+        );
+        assert_randomness_committed(&ctx.accounts.randomness, clock.slot)?;
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.disputer.to_account_info(),
+                    to: ctx.accounts.dispute.to_account_info(),
+                },
+            ),
+            DISPUTE_BOND,
+        )?;
+
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.market = market.key();
+        dispute.disputer = ctx.accounts.disputer.key();
+        dispute.bond = DISPUTE_BOND;
+        dispute.randomness = ctx.accounts.randomness.key();
+        dispute.opened_at = clock.unix_timestamp;
+        dispute.voting_ends_at = 0;
+        dispute.panel = Vec::new();
+        dispute.status = DisputeStatus::AwaitingJury;
+        dispute.bump = ctx.bumps.dispute;
+
+        market.disputed = true;
+        market.was_disputed = true;
+
+        msg!("Resolution disputed: jury pending");
+
+        Ok(())
+    }
+
+    /// Draw the jury from the revealed randomness (permissionless; must run in the reveal slot).
+    /// The disputer is never drawn.
+    pub fn draw_jury(ctx: Context<DrawJury>) -> Result<()> {
+        let dispute = &mut ctx.accounts.dispute;
+        let clock = Clock::get()?;
+
+        require!(
+            dispute.status == DisputeStatus::AwaitingJury,
+            PredictDuelError::InvalidDisputeStatus
+        );
+        let value = read_randomness(&ctx.accounts.randomness, clock.slot)?;
+
+        let mut candidates: Vec<Pubkey> = ctx
+            .accounts
+            .juror_pool
+            .jurors
+            .iter()
+            .copied()
+            .filter(|juror| *juror != dispute.disputer)
+            .collect();
+        require!(
+            candidates.len() >= JURY_SIZE,
+            PredictDuelError::JurorPoolTooSmall
+        );
+
+        // Partial Fisher-Yates shuffle: the first JURY_SIZE candidates form the panel
+        for i in 0..JURY_SIZE {
+            let j = i + random_index(&value, i as u64, candidates.len() - i);
+            candidates.swap(i, j);
+        }
+        dispute.panel = candidates[..JURY_SIZE]
+            .iter()
+            .map(|juror| JurorSeat {
+                juror: *juror,
+                vote: None,
+                weight: 0,
+            })
+            .collect();
+        dispute.voting_ends_at = clock.unix_timestamp + JURY_VOTING_PERIOD;
+        dispute.status = DisputeStatus::Voting;
+
+        msg!("Jury drawn: voting closes at {}", dispute.voting_ends_at);
+
+        Ok(())
+    }
+
+    /// Vote on a dispute's outcome as a drawn juror; the vote is weighted by, and locks,
+    /// the juror's current stake until the verdict
+    pub fn cast_jury_vote(ctx: Context<CastJuryVote>, outcome: bool) -> Result<()> {
+        let dispute = &mut ctx.accounts.dispute;
+        let position = &mut ctx.accounts.stake_position;
+        let clock = Clock::get()?;
+
+        require!(
+            dispute.status == DisputeStatus::Voting,
+            PredictDuelError::InvalidDisputeStatus
+        );
+        require!(
+            clock.unix_timestamp < dispute.voting_ends_at,
+            PredictDuelError::VotingClosed
+        );
+        require!(position.amount > 0, PredictDuelError::InsufficientStake);
+
+        let seat = dispute
+            .panel
+            .iter_mut()
+            .find(|seat| seat.juror == ctx.accounts.juror.key())
+            .ok_or(PredictDuelError::NotOnJury)?;
+        require!(seat.vote.is_none(), PredictDuelError::AlreadyVoted);
+
+        seat.vote = Some(outcome);
+        seat.weight = position.amount;
+        position.jury_votes += 1;
+
+        msg!(
+            "Jury vote cast: {} with {} staked",
+            if outcome { "YES" } else { "NO" },
+            position.amount
+        );
+
+        Ok(())
+    }
+
+    /// Close a dispute once voting ends or every juror has voted (permissionless). A stake
+    /// majority for the other outcome overturns the resolution and refunds the bond; otherwise
+    /// the bond goes to the treasury. Jurors outside the majority are slashed in its favour.
+    /// Voting disputes take the panel's stake positions as remaining accounts, in seat order.
+    /// A dispute whose jury was never drawn lapses after JURY_VOTING_PERIOD with the bond refunded.
+    pub fn finalize_dispute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeDispute<'info>>,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let dispute = &mut ctx.accounts.dispute;
+        let clock = Clock::get()?;
+
+        let status = match dispute.status {
+            DisputeStatus::AwaitingJury => {
+                require!(
+                    clock.unix_timestamp >= dispute.opened_at + JURY_VOTING_PERIOD,
+                    PredictDuelError::VotingOpen
+                );
+                DisputeStatus::Lapsed
+            }
+            DisputeStatus::Voting => {
+                require!(
+                    clock.unix_timestamp >= dispute.voting_ends_at
+                        || dispute.panel.iter().all(|seat| seat.vote.is_some()),
+                    PredictDuelError::VotingOpen
+                );
+                let majority = dispute.majority();
+                settle_jurors(
+                    &dispute.panel,
+                    majority,
+                    &ctx.accounts.staking_pool,
+                    ctx.remaining_accounts,
+                )?;
+                match majority {
+                    Some(outcome) if market.outcome != Some(outcome) => {
+                        market.outcome = Some(outcome);
+                        market.overturned = true;
+                        DisputeStatus::Overturned
+                    }
+                    _ => DisputeStatus::Upheld,
+                }
+            }
+            _ => return err!(PredictDuelError::InvalidDisputeStatus),
+        };
+
+        let bond = dispute.bond;
+        dispute.bond = 0;
+        dispute.status = status;
+        dispute.sub_lamports(bond)?;
+        if status == DisputeStatus::Upheld {
+            ctx.accounts.treasury.add_lamports(bond)?;
+        } else {
+            ctx.accounts.disputer.add_lamports(bond)?;
+        }
+        market.disputed = false;
+        market.settled_at = clock.unix_timestamp;
+        // A lapsed dispute never reached a verdict, so it doesn't count against the resolver
+        if status != DisputeStatus::Lapsed {
+            let resolver_profile = &mut ctx.accounts.resolver_profile;
+            resolver_profile.resolutions_disputed += 1;
+            if status == DisputeStatus::Overturned {
+                resolver_profile.resolutions_overturned += 1;
+            }
+        }
+
+        emit!(DisputeFinalized {
+            market: market.key(),
+            status,
+            outcome: market.outcome,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Dispute finalized: outcome is {}",
+            if market.outcome == Some(true) {
+                "YES"
+            } else {
+                "NO"
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Turn the market into a raffle (before any bets): after resolution, `winner_count`
+    /// correct predictors are drawn at random, one ticket per bettor, and split the whole pot
+    pub fn enable_raffle(ctx: Context<EnableRaffle>, winner_count: u8) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            market.settlement_mode == SettlementMode::Parimutuel
+                && !market.sealed
+                && market.pool_size == 0,
+            PredictDuelError::InvalidSettlementMode
+        );
+        require!(
+            winner_count > 0 && winner_count as usize <= MAX_RAFFLE_WINNERS,
+            PredictDuelError::InvalidRaffleWinners
+        );
+
+        market.settlement_mode = SettlementMode::Raffle;
+        market.raffle_winner_count = winner_count;
+
+        msg!("Raffle enabled: {} winners", winner_count);
+
+        Ok(())
+    }
+
+    /// Bind a freshly committed Switchboard randomness account to a resolved raffle or
+    /// side-pot market once its claims open (permissionless). An unrevealed commitment can
+    /// be replaced after RANDOMNESS_REVEAL_TIMEOUT_SLOTS.
+    pub fn commit_raffle_draw(ctx: Context<CommitRaffleDraw>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        market.assert_raffle_drawable(clock.unix_timestamp)?;
+        require!(
+            market.raffle_randomness.is_none()
+                || clock.slot >= market.raffle_committed_slot + RANDOMNESS_REVEAL_TIMEOUT_SLOTS,
+            PredictDuelError::RandomnessPending
+        );
+        assert_randomness_committed(&ctx.accounts.randomness, clock.slot)?;
+
+        market.raffle_randomness = Some(ctx.accounts.randomness.key());
+        market.raffle_committed_slot = clock.slot;
+
+        msg!("Raffle draw committed");
+
+        Ok(())
+    }
+
+    /// Draw the raffle winners, or the single side-pot winner, from the revealed randomness
+    /// (permissionless; must run in the reveal slot)
+    pub fn draw_raffle(ctx: Context<DrawRaffle>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(
+            market.raffle_randomness == Some(ctx.accounts.randomness.key()),
+            PredictDuelError::InvalidRandomness
+        );
+        market.assert_raffle_drawable(clock.unix_timestamp)?;
+        let value = read_randomness(&ctx.accounts.randomness, clock.slot)?;
+
+        let tickets = if market.outcome == Some(true) {
+            market.yes_tickets
+        } else {
+            market.no_tickets
+        };
+        require!(tickets > 0, PredictDuelError::NoWinningTickets);
+
+        let draws = if market.settlement_mode == SettlementMode::Raffle {
+            market.raffle_winner_count as u32
+        } else {
+            1
+        };
+        let winner_count = draws.min(tickets) as usize;
+        let mut winners = Vec::with_capacity(winner_count);
+        let mut nonce = 0u64;
+        while winners.len() < winner_count {
+            let ticket = random_index(&value, nonce, tickets as usize) as u32;
+            if !winners.contains(&ticket) {
+                winners.push(ticket);
+            }
+            nonce += 1;
+        }
+        market.raffle_winners = winners;
+
+        msg!("Raffle drawn: tickets {:?} win", market.raffle_winners);
+
+        Ok(())
+    }
+
+    /// Divert `side_pot_bps` of the pool into a side pot (before any bets); after resolution
+    /// one correct predictor is drawn to receive it on top of their normal winnings
+    pub fn enable_side_pot(ctx: Context<EnableSidePot>, side_pot_bps: u16) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            market.settlement_mode == SettlementMode::Parimutuel && !market.sealed,
+            PredictDuelError::InvalidSettlementMode
+        );
+        require!(
+            side_pot_bps > 0 && side_pot_bps <= MAX_SIDE_POT_BPS,
+            PredictDuelError::InvalidSidePot
+        );
+
+        market.side_pot_bps = side_pot_bps;
+
+        msg!("Side pot enabled: {} bps of the pool", side_pot_bps);
+
+        Ok(())
+    }
+
+    /// Pay the side pot to the drawn ticket holder
+    pub fn claim_side_pot(ctx: Context<ClaimSidePot>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let participant = &ctx.accounts.participant;
+
+        participant.assert_settles_locally()?;
+        require!(
+            market.status == MarketStatus::Resolved,
+            PredictDuelError::MarketNotResolved
+        );
+        market.assert_claims_open(Clock::get()?.unix_timestamp)?;
+        require!(market.lst_tokens == 0, PredictDuelError::LstNotUnwound);
+        require!(!market.side_pot_claimed, PredictDuelError::AlreadyClaimed);
+        let side_pot = market.side_pot();
+        require!(side_pot > 0, PredictDuelError::InvalidSidePot);
+        require!(
+            !market.raffle_winners.is_empty(),
+            PredictDuelError::RaffleNotDrawn
+        );
+        require!(
+            market.outcome == Some(participant.prediction)
+                && market.raffle_winners[0] == participant.raffle_ticket,
+            PredictDuelError::NotAWinner
+        );
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.winner.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            side_pot,
+        )?;
+
+        market.side_pot_claimed = true;
+
+        msg!(
+            "Side pot claimed: {} SOL",
+            side_pot as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Create the booster fund (admin only). It pays booster bonuses and is the mint
+    /// authority of every booster tier.
+    pub fn init_booster_fund(ctx: Context<InitBoosterFund>) -> Result<()> {
+        let booster_fund = &mut ctx.accounts.booster_fund;
+        booster_fund.total_paid = 0;
+        booster_fund.bump = ctx.bumps.booster_fund;
+
+        msg!("Booster fund created");
+
+        Ok(())
+    }
+
+    /// Add lamports to the booster fund (treasury or any sponsor)
+    pub fn fund_boosters(ctx: Context<FundBoosters>, amount: u64) -> Result<()> {
+        require!(amount > 0, PredictDuelError::StakeTooLow);
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.booster_fund.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!(
+            "Booster fund topped up: {} SOL",
+            amount as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Create a booster NFT tier with its own mint (admin only); burning one on a position
+    /// adds `multiplier_bps` of its payout as a bonus
+    pub fn create_booster_tier(
+        ctx: Context<CreateBoosterTier>,
+        tier_id: u8,
+        multiplier_bps: u16,
+    ) -> Result<()> {
+        require!(
+            multiplier_bps > 0 && multiplier_bps <= MAX_BOOSTER_MULTIPLIER_BPS,
+            PredictDuelError::InvalidBoosterMultiplier
+        );
+
+        let booster_tier = &mut ctx.accounts.booster_tier;
+        booster_tier.mint = ctx.accounts.booster_mint.key();
+        booster_tier.tier_id = tier_id;
+        booster_tier.multiplier_bps = multiplier_bps;
+        booster_tier.bump = ctx.bumps.booster_tier;
+
+        msg!("Booster tier {} created: +{} bps", tier_id, multiplier_bps);
+
+        Ok(())
+    }
+
+    /// Mint booster NFTs of a tier to a wallet's token account (admin only)
+    pub fn mint_booster(ctx: Context<MintBooster>, amount: u64) -> Result<()> {
+        let seeds: &[&[u8]] = &[b"booster_fund", &[ctx.accounts.booster_fund.bump]];
+        anchor_spl::token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::MintTo {
+                    mint: ctx.accounts.booster_mint.to_account_info(),
+                    to: ctx.accounts.recipient_token_account.to_account_info(),
+                    authority: ctx.accounts.booster_fund.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        msg!(
+            "Minted {} tier {} boosters",
+            amount,
+            ctx.accounts.booster_tier.tier_id
+        );
+
+        Ok(())
+    }
+
+    /// Burn a booster NFT on an open position to add its tier's bonus to the payout
+    /// (one booster per position)
+    pub fn apply_booster(ctx: Context<ApplyBooster>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let participant = &mut ctx.accounts.participant;
+        let clock = Clock::get()?;
+
+        require!(
+            (market.status == MarketStatus::Pending || market.status == MarketStatus::Active)
+                && clock.unix_timestamp < market.deadline,
+            PredictDuelError::MarketNotActive
+        );
+        require!(
+            participant.stake > 0 && !participant.claimed,
+            PredictDuelError::AlreadyClaimed
+        );
+        require!(participant.boost_bps == 0, PredictDuelError::AlreadyBoosted);
+
+        anchor_spl::token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Burn {
+                    mint: ctx.accounts.booster_mint.to_account_info(),
+                    from: ctx.accounts.bettor_token_account.to_account_info(),
+                    authority: ctx.accounts.bettor.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        participant.boost_bps = ctx.accounts.booster_tier.multiplier_bps;
+
+        msg!("Booster applied: +{} bps on payout", participant.boost_bps);
+
+        Ok(())
+    }
+
+    /// Set the loyalty points emission schedule (admin only): points per SOL staked and per
+    /// correct prediction, both halving every `halving_period` seconds (0 = never), and the
+    /// points redeemed per SOL of fee-free stake (0 = redemption off)
+    pub fn set_points_schedule(
+        ctx: Context<SetPointsSchedule>,
+        bet_points_per_sol: u64,
+        win_points: u64,
+        halving_period: i64,
+        redeem_points_per_sol: u64,
+    ) -> Result<()> {
+        let schedule = &mut ctx.accounts.points_schedule;
+        let clock = Clock::get()?;
+
+        require!(halving_period >= 0, PredictDuelError::InvalidPointsSchedule);
+
+        if schedule.start_time == 0 {
+            schedule.start_time = clock.unix_timestamp;
+        }
+        schedule.bet_points_per_sol = bet_points_per_sol;
+        schedule.win_points = win_points;
+        schedule.halving_period = halving_period;
+        schedule.redeem_points_per_sol = redeem_points_per_sol;
+        schedule.bump = ctx.bumps.points_schedule;
+
+        msg!(
+            "Points schedule: {} per SOL bet, {} per win",
+            bet_points_per_sol,
+            win_points
+        );
+
+        Ok(())
+    }
+
+    /// Credit loyalty points for a position's stake not yet counted, plus the win bonus if it
+    /// won, once the market resolves and its claims open (permissionless)
+    pub fn accrue_points(ctx: Context<AccruePoints>) -> Result<()> {
+        let schedule = &ctx.accounts.points_schedule;
+        let market = &ctx.accounts.market;
+        let participant = &mut ctx.accounts.participant;
+        let clock = Clock::get()?;
+
+        let earned = schedule.accrue(market, participant, clock.unix_timestamp)?;
+
+        let points = &mut ctx.accounts.points;
+        points.wallet = participant.bettor;
+        points.balance += earned;
+        points.lifetime += earned;
+        points.bump = ctx.bumps.points;
+
+        msg!("Accrued {} points: balance {}", earned, points.balance);
+
+        Ok(())
+    }
+
+    /// Spend loyalty points on fee-free credit: the protocol fee on that much winning stake
+    /// is waived at claim. Credit lasts FEE_CREDIT_DURATION; redeeming again extends it.
+    pub fn redeem_points(ctx: Context<RedeemPoints>, points_amount: u64) -> Result<()> {
+        let schedule = &ctx.accounts.points_schedule;
+        let points = &mut ctx.accounts.points;
+        let user_profile = &mut ctx.accounts.user_profile;
+        let clock = Clock::get()?;
+
+        require!(
+            schedule.redeem_points_per_sol > 0,
+            PredictDuelError::RedemptionDisabled
+        );
+        require!(
+            points_amount > 0 && points_amount <= points.balance,
+            PredictDuelError::InsufficientPoints
+        );
+
+        let credit =
+            (points_amount as u128 * 1_000_000_000 / schedule.redeem_points_per_sol as u128) as u64;
+        require!(credit > 0, PredictDuelError::InsufficientPoints);
+
+        points.balance -= points_amount;
+        user_profile.wallet = ctx.accounts.user.key();
+        user_profile.bump = ctx.bumps.user_profile;
+        user_profile.fee_credit = user_profile.fee_credit_at(clock.unix_timestamp) + credit;
+        user_profile.fee_credit_expires_at = clock.unix_timestamp + FEE_CREDIT_DURATION;
+
+        msg!(
+            "Redeemed {} points for {} SOL of fee-free stake",
+            points_amount,
+            credit as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Publish a quest (admin only): an objective to complete with bets placed between
+    /// `starts_at` and `ends_at`, rewarded with points and optionally a booster NFT
+    pub fn create_quest(
+        ctx: Context<CreateQuest>,
+        quest_id: u64,
+        objective: QuestObjective,
+        starts_at: i64,
+        ends_at: i64,
+        reward_points: u64,
+        reward_booster: Option<Pubkey>,
+    ) -> Result<()> {
+        require!(
+            ends_at > starts_at && objective.is_valid(),
+            PredictDuelError::InvalidQuest
+        );
+
+        let quest = &mut ctx.accounts.quest;
+        quest.quest_id = quest_id;
+        quest.objective = objective;
+        quest.starts_at = starts_at;
+        quest.ends_at = ends_at;
+        quest.reward_points = reward_points;
+        quest.reward_booster = reward_booster;
+        quest.completions = 0;
+        quest.bump = ctx.bumps.quest;
+
+        msg!("Quest {} created", quest_id);
+
+        Ok(())
+    }
+
+    /// Count a position toward its bettor's quest progress (permissionless). The bet must
+    /// have been placed during the quest; win objectives also need the market resolved.
+    pub fn record_quest_progress(ctx: Context<RecordQuestProgress>) -> Result<()> {
+        let quest = &ctx.accounts.quest;
+        let market = &ctx.accounts.market;
+        let participant = &ctx.accounts.participant;
+        let progress = &mut ctx.accounts.progress;
+
+        require!(
+            participant.last_entry_at >= quest.starts_at
+                && participant.last_entry_at <= quest.ends_at,
+            PredictDuelError::OutsideQuestWindow
+        );
+        require!(
+            !progress.positions.contains(&participant.key()),
+            PredictDuelError::AlreadyCounted
+        );
+        require!(
+            progress.positions.len() < MAX_QUEST_POSITIONS,
+            PredictDuelError::QuestProgressFull
+        );
+
+        match quest.objective {
+            QuestObjective::BetCategories(_) => {
+                progress.categories |= 1 << market.category as u8;
+            }
+            QuestObjective::WinDuels(_) => {
+                require!(
+                    market.market_type == MarketType::Challenge
+                        && market.status == MarketStatus::Resolved
+                        && market.outcome == Some(participant.prediction),
+                    PredictDuelError::NoQuestProgress
+                );
+                progress.wins += 1;
+            }
+            QuestObjective::Volume(_) => {
+                progress.volume += participant.stake;
+            }
+        }
+
+        progress.quest = quest.key();
+        progress.wallet = participant.bettor;
+        progress.positions.push(participant.key());
+        progress.bump = ctx.bumps.progress;
+
+        msg!(
+            "Quest progress recorded: {}/{} positions",
+            progress.positions.len(),
+            MAX_QUEST_POSITIONS
+        );
+
+        Ok(())
+    }
+
+    /// Claim a completed quest's reward: points, plus a booster NFT when the quest offers one
+    pub fn claim_quest_reward(ctx: Context<ClaimQuestReward>) -> Result<()> {
+        let quest = &mut ctx.accounts.quest;
+        let progress = &mut ctx.accounts.progress;
+
+        require!(
+            quest.is_complete(progress),
+            PredictDuelError::QuestIncomplete
+        );
+        require!(!progress.claimed, PredictDuelError::AlreadyClaimed);
+        progress.claimed = true;
+        quest.completions += 1;
+
+        let points = &mut ctx.accounts.points;
+        points.wallet = progress.wallet;
+        points.balance += quest.reward_points;
+        points.lifetime += quest.reward_points;
+        points.bump = ctx.bumps.points;
+
+        if let Some(mint) = quest.reward_booster {
+            let (Some(booster_fund), Some(booster_mint), Some(token_account), Some(token_program)) = (
+                &ctx.accounts.booster_fund,
+                &ctx.accounts.booster_mint,
+                &ctx.accounts.booster_token_account,
+                &ctx.accounts.token_program,
+            ) else {
+                return err!(PredictDuelError::BoosterAccountsRequired);
+            };
+            require_keys_eq!(
+                booster_mint.key(),
+                mint,
+                PredictDuelError::BoosterAccountsRequired
+            );
+
+            let seeds: &[&[u8]] = &[b"booster_fund", &[booster_fund.bump]];
+            anchor_spl::token::mint_to(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    anchor_spl::token::MintTo {
+                        mint: booster_mint.to_account_info(),
+                        to: token_account.to_account_info(),
+                        authority: booster_fund.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                1,
+            )?;
+        }
+
+        msg!(
+            "Quest {} completed: {} points",
+            quest.quest_id,
+            quest.reward_points
+        );
+
+        Ok(())
+    }
+
+    /// Set the reward tokens paid at each check-in streak milestone (admin only)
+    pub fn set_check_in_reward(ctx: Context<SetCheckInReward>, check_in_reward: u64) -> Result<()> {
+        ctx.accounts.rewards.check_in_reward = check_in_reward;
+
+        msg!("Check-in milestone reward set to {}", check_in_reward);
+
+        Ok(())
+    }
+
+    /// Daily check-in: extends the streak if the last check-in was yesterday, otherwise
+    /// restarts it. Every CHECK_IN_MILESTONE_DAYS days of streak pays the rewards module's
+    /// check-in reward, for which the reward accounts must be passed.
+    pub fn check_in(ctx: Context<CheckIn>) -> Result<()> {
+        let user_profile = &mut ctx.accounts.user_profile;
+        let clock = Clock::get()?;
+
+        let day = clock.unix_timestamp / (24 * 60 * 60);
+        require!(
+            day != user_profile.last_check_in_day,
+            PredictDuelError::AlreadyCheckedIn
+        );
+        user_profile.check_in_streak = if day == user_profile.last_check_in_day + 1 {
+            user_profile.check_in_streak + 1
+        } else {
+            1
+        };
+        user_profile.last_check_in_day = day;
+        user_profile.wallet = ctx.accounts.user.key();
+        user_profile.bump = ctx.bumps.user_profile;
+
+        if user_profile
+            .check_in_streak
+            .is_multiple_of(CHECK_IN_MILESTONE_DAYS)
+        {
+            let (Some(rewards), Some(rewards_vault), Some(user_token_account), Some(token_program)) = (
+                &ctx.accounts.rewards,
+                &ctx.accounts.rewards_vault,
+                &ctx.accounts.user_token_account,
+                &ctx.accounts.token_program,
+            ) else {
+                return err!(PredictDuelError::RewardAccountsRequired);
+            };
+
+            let amount = rewards.check_in_reward.min(rewards_vault.amount);
+            if amount > 0 {
+                let seeds: &[&[u8]] = &[b"rewards", &[rewards.bump]];
+                anchor_spl::token::transfer(
+                    CpiContext::new_with_signer(
+                        token_program.to_account_info(),
+                        anchor_spl::token::Transfer {
+                            from: rewards_vault.to_account_info(),
+                            to: user_token_account.to_account_info(),
+                            authority: rewards.to_account_info(),
+                        },
+                        &[seeds],
+                    ),
+                    amount,
+                )?;
+            }
+            msg!(
+                "Check-in milestone: {} day streak, {} reward tokens",
+                user_profile.check_in_streak,
+                amount
+            );
+        } else {
+            msg!("Checked in: {} day streak", user_profile.check_in_streak);
+        }
+
+        Ok(())
+    }
+
+    /// Create an empty airdrop distributor for an SPL token (admin only). The vault is funded
+    /// by plain transfers; claims open once governance publishes the snapshot root.
+    pub fn create_distributor(ctx: Context<CreateDistributor>, distributor_id: u64) -> Result<()> {
+        let distributor = &mut ctx.accounts.distributor;
+
+        distributor.distributor_id = distributor_id;
+        distributor.mint = ctx.accounts.mint.key();
+        distributor.root = [0; 32];
+        distributor.total_claimed = 0;
+        distributor.claimed_count = 0;
+        distributor.bump = ctx.bumps.distributor;
+
+        msg!(
+            "Distributor {} created for mint {}",
+            distributor_id,
+            distributor.mint
+        );
+
+        Ok(())
+    }
+
+    /// Publish a distributor's snapshot root directly (admin only, until governance is live)
+    pub fn set_distributor_root(ctx: Context<SetDistributorRoot>, root: [u8; 32]) -> Result<()> {
+        // Once governance is live, roots are only published through proposals
+        require!(
+            ctx.accounts.config.governance_mint == Pubkey::default(),
+            PredictDuelError::GovernanceActive
+        );
+        require!(root != [0; 32], PredictDuelError::InvalidGovernanceChange);
+
+        ctx.accounts.distributor.publish_root(root)?;
+
+        Ok(())
+    }
+
+    /// Claim an airdrop allocation by proving the (index, claimant, amount) leaf against the root
+    pub fn claim_airdrop(
+        ctx: Context<ClaimAirdrop>,
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let distributor = &mut ctx.accounts.distributor;
+        let claimant = ctx.accounts.claimant.key();
+
+        require!(
+            distributor.root != [0; 32],
+            PredictDuelError::RootNotPublished
+        );
+        require!(
+            verify_merkle_proof(
+                &proof,
+                distributor.root,
+                claim_leaf(index, &claimant, amount)
+            ),
+            PredictDuelError::InvalidMerkleProof
+        );
+
+        let id_bytes = distributor.distributor_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[b"distributor", &id_bytes, &[distributor.bump]];
+        anchor_spl::token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Transfer {
+                    from: ctx.accounts.distributor_vault.to_account_info(),
+                    to: ctx.accounts.claimant_token_account.to_account_info(),
+                    authority: distributor.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        let claim_status = &mut ctx.accounts.claim_status;
+        claim_status.distributor = distributor.key();
+        claim_status.index = index;
+        claim_status.claimant = claimant;
+        claim_status.amount = amount;
+        claim_status.bump = ctx.bumps.claim_status;
+
+        distributor.total_claimed += amount;
+        distributor.claimed_count += 1;
+
+        msg!("Airdrop claimed: {} tokens (leaf {})", amount, index);
+
+        Ok(())
+    }
+
+    /// Settle a large parimutuel market from a merkle root of payouts (creator only, before
+    /// betting opens). Winners then claim with claim_settlement rather than claim_winnings.
+    pub fn enable_merkle_settlement(ctx: Context<EnableMerkleSettlement>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            market.settlement_mode == SettlementMode::Parimutuel,
+            PredictDuelError::InvalidSettlementMode
+        );
+
+        market.merkle_settlement = true;
+
+        msg!("Merkle settlement enabled");
+
+        Ok(())
+    }
+
+    /// Post the payout root of a resolved merkle-settled market (resolver only). Leaves are
+    /// claim_leaf(index, winner, payout) over 0..leaf_count; payouts can't exceed the pool.
+    pub fn post_settlement_root(
+        ctx: Context<PostSettlementRoot>,
+        root: [u8; 32],
+        total_payout: u64,
+        leaf_count: u32,
+    ) -> Result<()> {
+        let market = &ctx.accounts.market;
+
+        require!(market.merkle_settlement, PredictDuelError::NotMerkleSettled);
+        require!(
+            ctx.accounts.resolver.key() == market.resolver.unwrap_or(market.creator),
+            PredictDuelError::UnauthorizedResolver
+        );
+        require!(
+            market.status == MarketStatus::Resolved,
+            PredictDuelError::MarketNotResolved
+        );
+        // Roots are only posted once the resolution can no longer be disputed
+        let now = Clock::get()?.unix_timestamp;
+        market.assert_claims_open(now)?;
+        require!(
+            root != [0; 32] && leaf_count > 0 && leaf_count <= MAX_SETTLEMENT_LEAVES,
+            PredictDuelError::InvalidSettlementRoot
+        );
+        require!(
+            total_payout <= market.settlement_cap(now)?,
+            PredictDuelError::InvalidSettlementRoot
+        );
+
+        let settlement = &mut ctx.accounts.settlement;
+        settlement.market = market.key();
+        settlement.root = root;
+        settlement.outcome = market.outcome;
+        settlement.total_payout = total_payout;
+        settlement.claimed_amount = 0;
+        settlement.leaf_count = leaf_count;
+        settlement.claimed_bitmap = vec![0; leaf_count.div_ceil(8) as usize];
+        settlement.bump = ctx.bumps.settlement;
+
+        msg!(
+            "Settlement root posted: {} SOL across {} winners",
+            total_payout as f64 / 1_000_000_000.0,
+            leaf_count
+        );
+
+        Ok(())
+    }
+
+    /// Claim a payout from a merkle-settled market with a proof against its settlement root
+    pub fn claim_settlement(
+        ctx: Context<ClaimSettlement>,
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let settlement = &mut ctx.accounts.settlement;
+        let winner = ctx.accounts.winner.key();
+
+        market.assert_claims_open(Clock::get()?.unix_timestamp)?;
+        // A root computed for an outcome a jury has since overturned pays nothing
+        require!(
+            settlement.outcome == market.outcome,
+            PredictDuelError::StaleSettlement
+        );
+        require!(market.lst_tokens == 0, PredictDuelError::LstNotUnwound);
+        require!(
+            index < settlement.leaf_count as u64,
+            PredictDuelError::InvalidMerkleProof
+        );
+        let (byte, bit) = ((index / 8) as usize, 1u8 << (index % 8));
+        require!(
+            settlement.claimed_bitmap[byte] & bit == 0,
+            PredictDuelError::AlreadyClaimed
+        );
+        require!(
+            verify_merkle_proof(&proof, settlement.root, claim_leaf(index, &winner, amount)),
+            PredictDuelError::InvalidMerkleProof
+        );
+        // A bad root can't pay out more than it declared
+        require!(
+            settlement.claimed_amount + amount <= settlement.total_payout,
+            PredictDuelError::InvalidSettlementRoot
+        );
+        assert_claim_co_signed(
+            &ctx.accounts.profile_guard,
+            ctx.accounts.co_signer.as_ref(),
+            amount,
+        )?;
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.winner.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            amount,
+        )?;
+
+        settlement.claimed_bitmap[byte] |= bit;
+        settlement.claimed_amount += amount;
+
+        msg!(
+            "Settlement claimed: {} SOL (leaf {})",
+            amount as f64 / 1_000_000_000.0,
+            index
+        );
+
+        Ok(())
+    }
+
+    /// Store this market's positions as leaves of a concurrent merkle tree rather than one
+    /// participant PDA per bettor (creator only, before betting opens). The tree account must
+    /// already be allocated to the account compression program; this initializes it.
+    pub fn enable_compressed_positions(
+        ctx: Context<EnableCompressedPositions>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            market.position_tree.is_none(),
+            PredictDuelError::CompressedMarket
+        );
+        require!(
+            market.settlement_mode == SettlementMode::Parimutuel,
+            PredictDuelError::InvalidSettlementMode
+        );
+
+        let ix = compression_init_tree_ix(
+            ctx.accounts.merkle_tree.key(),
+            ctx.accounts.position_authority.key(),
+            max_depth,
+            max_buffer_size,
+        );
+        let market_key = market.key();
+        let authority_seeds: &[&[u8]] = &[
+            b"position_authority",
+            market_key.as_ref(),
+            &[ctx.bumps.position_authority],
+        ];
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.merkle_tree.to_account_info(),
+                ctx.accounts.position_authority.to_account_info(),
+                ctx.accounts.log_wrapper.to_account_info(),
+            ],
+            &[authority_seeds],
+        )?;
+
+        market.position_tree = Some(ctx.accounts.merkle_tree.key());
+        market.position_leaves = 0;
+
+        msg!(
+            "Compressed positions enabled: tree {} (depth {})",
+            ctx.accounts.merkle_tree.key(),
+            max_depth
+        );
+
+        Ok(())
+    }
+
+    /// Bet on a compressed market: the position is appended as a tree leaf, so the bettor
+    /// pays no account rent. Every bet is its own leaf; indexers rebuild them from events.
+    pub fn place_compressed_bet(
+        ctx: Context<PlaceCompressedBet>,
+        prediction: bool,
+        stake_amount: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let bettor = ctx.accounts.bettor.key();
+        let clock = Clock::get()?;
+
+        require!(
+            market.position_tree == Some(ctx.accounts.merkle_tree.key()),
+            PredictDuelError::NotCompressedMarket
+        );
+        // Tree leaves hold plain parimutuel positions only
+        require!(
+            market.settlement_mode == SettlementMode::Parimutuel
+                && !market.issues_tickets()
+                && !market.merkle_settlement,
+            PredictDuelError::InvalidSettlementMode
+        );
+        market.assert_accepting_stake(stake_amount, clock.unix_timestamp)?;
+        verify_attestation(
+            &ctx.accounts.config,
+            &bettor,
+            ctx.accounts.instructions.as_deref(),
+            clock.unix_timestamp,
+        )?;
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.bettor.to_account_info(),
+                    to: ctx.accounts.market_vault.to_account_info(),
+                },
+            ),
+            stake_amount,
+        )?;
+
+        let mut position = Participant::default();
+        market.credit_bet(
+            &mut position,
+            true,
+            prediction,
+            stake_amount,
+            clock.unix_timestamp,
+        )?;
+
+        let market_key = market.key();
+        let leaf = position_leaf(
+            &market_key,
+            &bettor,
+            prediction,
+            stake_amount,
+            position.weight,
+            false,
+        );
+        let ix = compression_append_ix(
+            ctx.accounts.merkle_tree.key(),
+            ctx.accounts.position_authority.key(),
+            leaf,
+        );
+        let authority_seeds: &[&[u8]] = &[
+            b"position_authority",
+            market_key.as_ref(),
+            &[ctx.bumps.position_authority],
+        ];
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.merkle_tree.to_account_info(),
+                ctx.accounts.position_authority.to_account_info(),
+                ctx.accounts.log_wrapper.to_account_info(),
+            ],
+            &[authority_seeds],
+        )?;
+
+        let leaf_index = market.position_leaves;
+        market.position_leaves += 1;
+
+        emit!(CompressedBetPlaced {
+            market: market_key,
+            bettor,
+            leaf_index,
+            prediction,
+            stake: stake_amount,
+            weight: position.weight,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Compressed bet placed: {} SOL on {} (leaf {})",
+            stake_amount as f64 / 1_000_000_000.0,
+            if prediction { "YES" } else { "NO" },
+            leaf_index
+        );
+
+        Ok(())
+    }
+
+    /// Pay out a compressed position: its winnings once resolved, or its stake if the market
+    /// was cancelled or voided. Anyone can crank it; funds go to the bettor. The leaf is
+    /// rewritten as claimed against the current root, so the tree proof (passed as remaining
+    /// accounts) both proves the position and stops it paying twice.
+    pub fn settle_compressed_position<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleCompressedPosition<'info>>,
+        root: [u8; 32],
+        leaf_index: u32,
+        prediction: bool,
+        stake: u64,
+        weight: u64,
+    ) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let bettor = ctx.accounts.bettor.key();
+
+        require!(
+            market.position_tree == Some(ctx.accounts.merkle_tree.key()),
+            PredictDuelError::NotCompressedMarket
+        );
+        require!(market.lst_tokens == 0, PredictDuelError::LstNotUnwound);
+        let payout = if market.is_refundable() {
+            stake
+        } else {
+            require!(
+                market.status == MarketStatus::Resolved,
+                PredictDuelError::MarketNotResolved
+            );
+            market.assert_claims_open(Clock::get()?.unix_timestamp)?;
+            let outcome = market.outcome.ok_or(PredictDuelError::NoOutcome)?;
+            require!(prediction == outcome, PredictDuelError::NotAWinner);
+            let winning_weight = if outcome {
+                market.yes_weight
+            } else {
+                market.no_weight
+            };
+            let payout = market.calculate_payout(weight, winning_weight)?;
+            require!(
+                !(market.vesting_mandatory && payout > market.vesting_threshold),
+                PredictDuelError::VestingRequired
+            );
+            payout
+        };
+
+        let market_key = market.key();
+        let ix = compression_replace_leaf_ix(
+            ctx.accounts.merkle_tree.key(),
+            ctx.accounts.position_authority.key(),
+            ctx.remaining_accounts,
+            root,
+            position_leaf(&market_key, &bettor, prediction, stake, weight, false),
+            position_leaf(&market_key, &bettor, prediction, stake, weight, true),
+            leaf_index,
+        );
+        let mut infos = vec![
+            ctx.accounts.merkle_tree.to_account_info(),
+            ctx.accounts.position_authority.to_account_info(),
+            ctx.accounts.log_wrapper.to_account_info(),
+        ];
+        infos.extend(ctx.remaining_accounts.iter().cloned());
+        let authority_seeds: &[&[u8]] = &[
+            b"position_authority",
+            market_key.as_ref(),
+            &[ctx.bumps.position_authority],
+        ];
+        anchor_lang::solana_program::program::invoke_signed(&ix, &infos, &[authority_seeds])?;
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.bettor.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            payout,
+        )?;
+
+        msg!(
+            "Compressed position settled: {} SOL to {} (leaf {})",
+            payout as f64 / 1_000_000_000.0,
+            bettor,
+            leaf_index
+        );
+
+        Ok(())
+    }
+
+    /// Pay up to MAX_SETTLE_BATCH winners of a resolved market in one transaction (permissionless).
+    /// Remaining accounts are (participant, bettor wallet, writable user profile PDA) triples; the
+    /// profile may not exist. Fee rebates and credits apply as in claim_winnings; positions that
+    /// need more than the wallet (payout splits, boosters, receipts, cross-chain, charity
+    /// donations, mandatory vesting) are skipped and claim themselves.
+    pub fn settle_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleBatch<'info>>,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(
+            market.status == MarketStatus::Resolved,
+            PredictDuelError::MarketNotResolved
+        );
+        require!(
+            ctx.remaining_accounts.len().is_multiple_of(3)
+                && ctx.remaining_accounts.len() <= MAX_SETTLE_BATCH * 3,
+            PredictDuelError::InvalidSettleBatch
+        );
+
+        let market_key = market.key();
+        let creator = market.creator;
+        let index_bytes = market.market_index.to_le_bytes();
+        let seeds = &[
+            b"market_vault",
+            creator.as_ref(),
+            &index_bytes,
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        let mut settled = 0u32;
+        let mut total = 0u64;
+        for entry in ctx.remaining_accounts.chunks(3) {
+            let mut participant = Account::<Participant>::try_from(&entry[0])?;
+            let (profile_key, _) =
+                Pubkey::find_program_address(&[b"user_profile", entry[1].key.as_ref()], &crate::ID);
+            require!(
+                participant.market == market_key
+                    && participant.bettor == entry[1].key()
+                    && entry[2].key() == profile_key,
+                PredictDuelError::InvalidSettleBatch
+            );
+            let mut user_profile = if entry[2].data_is_empty() {
+                None
+            } else {
+                Some(Account::<UserProfile>::try_from(&entry[2])?)
+            };
+            // Donations need the charity account, so those winners claim themselves
+            if !participant.batch_settleable()
+                || user_profile
+                    .as_ref()
+                    .is_some_and(|user_profile| user_profile.charity_bps > 0)
+            {
+                continue;
+            }
+            // Also rejects losers, claimed positions and markets that can't pay out yet
+            let Ok(payout) = market.winner_payout(&participant, clock.unix_timestamp) else {
+                continue;
+            };
+            let rebate = market.fee_rebate(
+                &ctx.accounts.config,
+                &participant,
+                user_profile.as_deref_mut(),
+                payout,
+                clock.unix_timestamp,
+            );
+            let payout = payout + rebate;
+            if market.vesting_mandatory && payout > market.vesting_threshold {
+                continue;
+            }
+
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.market_vault.to_account_info(),
+                        to: entry[1].clone(),
+                    },
+                    signer,
+                ),
+                payout,
+            )?;
+
+            market.fee_rebated += rebate;
+            participant.claimed = true;
+            participant.exit(&crate::ID)?;
+            if let Some(user_profile) = user_profile {
+                user_profile.exit(&crate::ID)?;
+            }
+            settled += 1;
+            total += payout;
+        }
+
+        emit!(BatchSettled {
+            market: market_key,
+            keeper: ctx.accounts.keeper.key(),
+            settled,
+            amount: total,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Batch settled: {} SOL to {} winners",
+            total as f64 / 1_000_000_000.0,
+            settled
+        );
+
+        Ok(())
+    }
+
+    /// Choose pull (winners claim) or push (keeper pays via settle_batch) payouts
+    /// (creator only, before betting opens)
+    pub fn set_payout_mode(ctx: Context<SetPayoutMode>, mode: PayoutMode) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+
+        market.payout_mode = mode;
+
+        msg!(
+            "Payout mode set to {}",
+            if mode == PayoutMode::Push {
+                "push"
+            } else {
+                "pull"
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Claim part of a winning payout now and the rest later, e.g. while the vault is short of
+    /// liquidity. Installments are paid at the market's fee rate and only once escrow has been
+    /// unwound from the stake pool, so the payout they count against can no longer move.
+    pub fn claim_installment(ctx: Context<ClaimInstallment>, amount: u64) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let participant = &mut ctx.accounts.participant;
+
+        require!(
+            participant.batch_settleable(),
+            PredictDuelError::InstallmentUnavailable
+        );
+        // Validates resolution, claim status, escrow and that the participant won
+        let gross = market.installment_gross(participant, amount, Clock::get()?.unix_timestamp)?;
+        // Checked against the whole win so installments can't split it under the threshold
+        assert_claim_co_signed(
+            &ctx.accounts.profile_guard,
+            ctx.accounts.co_signer.as_ref(),
+            gross,
+        )?;
+        require!(
+            ctx.accounts.market_vault.lamports() >= amount,
+            PredictDuelError::InsufficientVaultLiquidity
+        );
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.winner.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            amount,
+        )?;
+
+        participant.claimed_amount += amount;
+        participant.claimed = participant.claimed_amount == gross;
+
+        msg!(
+            "Installment claimed: {} of {} SOL",
+            participant.claimed_amount as f64 / 1_000_000_000.0,
+            gross as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Refund bettors of a cancelled or voided market (permissionless). Remaining accounts are
+    /// (participant, bettor wallet) pairs; claimed, cross-chain and receipt-backed positions are skipped.
+    pub fn refund_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefundBatch<'info>>,
+    ) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(market.is_refundable(), PredictDuelError::MarketNotCancelled);
+        require!(
+            ctx.remaining_accounts.len().is_multiple_of(2),
+            PredictDuelError::InvalidRefundBatch
+        );
+        require!(market.lst_tokens == 0, PredictDuelError::LstNotUnwound);
+
+        let market_key = market.key();
+        let index_bytes = market.market_index.to_le_bytes();
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &index_bytes,
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        let mut refunded = 0u32;
+        let mut total = 0u64;
+        for pair in ctx.remaining_accounts.chunks(2) {
+            let mut participant = Account::<Participant>::try_from(&pair[0])?;
+            require!(
+                participant.market == market_key && participant.bettor == pair[1].key(),
+                PredictDuelError::InvalidRefundBatch
+            );
+            if participant.claimed || participant.assert_settles_locally().is_err() {
+                continue;
+            }
+
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.market_vault.to_account_info(),
+                        to: pair[1].clone(),
+                    },
+                    signer,
+                ),
+                participant.stake,
+            )?;
+
+            participant.claimed = true;
+            participant.exit(&crate::ID)?;
+            refunded += 1;
+            total += participant.stake;
+        }
+
+        emit!(BatchRefunded {
+            market: market_key,
+            keeper: ctx.accounts.keeper.key(),
+            refunded,
+            amount: total,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Batch refund: {} SOL to {} bettors",
+            total as f64 / 1_000_000_000.0,
+            refunded
+        );
+
+        Ok(())
+    }
+
+    /// Create the aggregate stats account for a market category (admin only)
+    pub fn init_category_stats(
+        ctx: Context<InitCategoryStats>,
+        category: MarketCategory,
+    ) -> Result<()> {
+        let category_stats = &mut ctx.accounts.category_stats;
+        category_stats.category = category;
+        category_stats.bump = ctx.bumps.category_stats;
+
+        msg!("Category stats initialized");
+
+        Ok(())
+    }
+
+    /// Fold a market's creation, new volume and closing into its category stats
+    /// (permissionless). Covers every path that doesn't pass the stats account itself.
+    pub fn sync_category_stats(ctx: Context<SyncCategoryStats>) -> Result<()> {
+        ctx.accounts
+            .market
+            .sync_category_stats(&mut ctx.accounts.category_stats);
+
+        Ok(())
+    }
+
+    /// Replace a settled market with a compact MarketArchive record and close the market account,
+    /// returning its rent to the creator (creator only). The market must be paid out (vault down
+    /// to dust) or ARCHIVE_DELAY past settlement; whatever is left in the vault goes to the treasury.
+    pub fn archive_market(ctx: Context<ArchiveMarket>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(
+            matches!(
+                market.status,
+                MarketStatus::Resolved | MarketStatus::Voided | MarketStatus::Cancelled
+            ) && !market.disputed,
+            PredictDuelError::MarketNotResolved
+        );
+        require!(market.creation_bond == 0, PredictDuelError::BondOutstanding);
+        require!(market.lst_tokens == 0, PredictDuelError::LstNotUnwound);
+        // Unvested winnings, boosts and bounties can't all be claimed yet, and their claims
+        // need the market account, so they hold up archiving until settled
+        require!(
+            market.vesting_outstanding == 0
+                && market.boost_outstanding == 0
+                && market.bounty_outstanding == 0,
+            PredictDuelError::PayoutsOutstanding
+        );
+        let remaining = ctx.accounts.market_vault.lamports();
+        require!(
+            (remaining <= ARCHIVE_DUST && market.insurance_outstanding() <= ARCHIVE_DUST)
+                || clock.unix_timestamp >= market.settled_at + ARCHIVE_DELAY,
+            PredictDuelError::PayoutsOutstanding
+        );
+
+        if remaining > 0 {
+            let seeds = &[
+                b"market_vault",
+                market.creator.as_ref(),
+                &market.market_index.to_le_bytes(),
+                &[ctx.bumps.market_vault],
+            ];
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.market_vault.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ),
+                remaining,
+            )?;
+        }
+
+        let archive = &mut ctx.accounts.market_archive;
+        archive.market = market.key();
+        archive.creator = market.creator;
+        archive.market_index = market.market_index;
+        archive.question_hash = hash(market.question.as_bytes()).to_bytes();
+        archive.category = market.category;
+        archive.status = market.status.clone();
+        archive.outcome = market.outcome;
+        archive.yes_pool = market.yes_pool;
+        archive.no_pool = market.no_pool;
+        archive.total_participants = market.total_participants;
+        archive.created_at = market.created_at;
+        archive.settled_at = market.settled_at;
+        archive.bump = ctx.bumps.market_archive;
+
+        msg!(
+            "Market archived: {} SOL unclaimed swept to the treasury",
+            remaining as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Create a market's recent-bets feed (permissionless); bets passing it keep it current
+    pub fn init_recent_bets(ctx: Context<InitRecentBets>) -> Result<()> {
+        let recent_bets = &mut ctx.accounts.recent_bets;
+        recent_bets.market = ctx.accounts.market.key();
+        recent_bets.bump = ctx.bumps.recent_bets;
+
+        msg!("Recent bets feed initialized");
+
+        Ok(())
+    }
+
+    /// Create a creator's analytics account (creator only)
+    pub fn init_creator_stats(ctx: Context<InitCreatorStats>) -> Result<()> {
+        let creator_stats = &mut ctx.accounts.creator_stats;
+        creator_stats.creator = ctx.accounts.creator.key();
+        creator_stats.bump = ctx.bumps.creator_stats;
+
+        msg!("Creator stats initialized");
+
+        Ok(())
+    }
+
+    /// Fold a closed market's volume, resolution time and dispute history into its
+    /// creator's stats (permissionless, once per market). Resolved markets count once
+    /// their dispute window has passed.
+    pub fn record_creator_stats(ctx: Context<RecordCreatorStats>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let stats = &mut ctx.accounts.creator_stats;
+
+        require!(
+            matches!(
+                market.status,
+                MarketStatus::Resolved | MarketStatus::Voided | MarketStatus::Cancelled
+            ),
+            PredictDuelError::MarketNotResolved
+        );
+        require!(
+            !market.creator_stats_recorded,
+            PredictDuelError::AlreadyClaimed
+        );
+        require!(
+            !market.disputed
+                && (market.status != MarketStatus::Resolved
+                    || Clock::get()?.unix_timestamp
+                        > market.resolved_at + ctx.accounts.config.dispute_window),
+            PredictDuelError::DisputeOpen
+        );
+
+        stats.markets_closed += 1;
+        stats.total_volume += market.pool_size;
+        if market.status == MarketStatus::Resolved {
+            stats.markets_resolved += 1;
+            stats.total_resolution_time += market
+                .resolved_at
+                .saturating_sub(market.resolvable_at())
+                .max(0);
+        }
+        if market.was_disputed || market.halted_at != 0 {
+            stats.markets_disputed += 1;
+        }
+        market.creator_stats_recorded = true;
+
+        msg!(
+            "Creator stats: {} closed, {} bps disputed",
+            stats.markets_closed,
+            stats.dispute_rate_bps()
+        );
+
+        Ok(())
+    }
+
+    /// Hand resolution to a third-party resolver whose accuracy is at least
+    /// `min_accuracy_bps` (creator only, before any bets)
+    pub fn set_resolver(ctx: Context<SetResolver>, min_accuracy_bps: u16) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let resolver = ctx.accounts.resolver.key();
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            resolver != market.creator,
+            PredictDuelError::ResolverRequired
+        );
+        let accuracy = ctx.accounts.resolver_profile.resolver_accuracy_bps();
+        require!(
+            accuracy >= min_accuracy_bps,
+            PredictDuelError::ResolverAccuracyTooLow
+        );
+
+        market.resolver = Some(resolver);
+
+        msg!("Resolver set to {} ({} bps accurate)", resolver, accuracy);
+
+        Ok(())
+    }
+
+    /// Insure a position against its resolution being overturned by a dispute, paying
+    /// INSURANCE_PREMIUM_BPS of the stake not yet insured; open while betting is
+    /// (voided markets refund stakes anyway)
+    pub fn buy_insurance(ctx: Context<BuyInsurance>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let participant = &mut ctx.accounts.participant;
+
+        require!(
+            market.status == MarketStatus::Pending || market.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        require!(
+            market.betting_open(&Clock::get()?),
+            PredictDuelError::MarketExpired
+        );
+        let uninsured = participant.stake - participant.insured_stake;
+
+        let premium = uninsured * INSURANCE_PREMIUM_BPS / BPS_DENOMINATOR;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.bettor.to_account_info(),
+                    to: ctx.accounts.market_vault.to_account_info(),
+                },
+            ),
+            premium,
+        )?;
+
+        market.insurance_pool += premium;
+        if participant.prediction {
+            market.insured_yes += uninsured;
+        } else {
+            market.insured_no += uninsured;
+        }
+        participant.insured_stake = participant.stake;
+
+        msg!(
+            "Insured {} SOL for a {} SOL premium",
+            uninsured as f64 / 1_000_000_000.0,
+            premium as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Recover an insured stake from the insurance pool after a jury overturned the
+    /// resolution against the position
+    pub fn claim_insurance(ctx: Context<ClaimInsurance>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let participant = &mut ctx.accounts.participant;
+
+        require!(
+            market.status == MarketStatus::Resolved,
+            PredictDuelError::MarketNotResolved
+        );
+        market.assert_claims_open(Clock::get()?.unix_timestamp)?;
+        let outcome = market.outcome.ok_or(PredictDuelError::NoOutcome)?;
+        require!(
+            participant.prediction != outcome,
+            PredictDuelError::NothingToClaim
+        );
+
+        let amount = market.insurance_payout(participant.insured_stake, participant.prediction);
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.bettor.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        participant.insurance_claimed = true;
+        ctx.accounts.market.insurance_paid += amount;
+
+        msg!("Insurance claimed: {} SOL", amount as f64 / 1_000_000_000.0);
+
+        Ok(())
+    }
+
+    /// Send insurance premiums no insured bettor can claim to the treasury once the
+    /// market is final (permissionless)
+    pub fn collect_insurance_surplus(ctx: Context<CollectInsuranceSurplus>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(
+            matches!(
+                market.status,
+                MarketStatus::Resolved | MarketStatus::Voided | MarketStatus::Cancelled
+            ),
+            PredictDuelError::MarketNotResolved
+        );
+        require!(
+            !market.disputed
+                && (market.status != MarketStatus::Resolved
+                    || Clock::get()?.unix_timestamp
+                        > market.resolved_at + ctx.accounts.config.dispute_window),
+            PredictDuelError::DisputeOpen
+        );
+
+        let reserve = market.insurance_reserve();
+        let surplus = market.insurance_pool - reserve;
+        require!(surplus > 0, PredictDuelError::NothingToClaim);
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+                signer,
+            ),
+            surplus,
+        )?;
+
+        market.insurance_pool = reserve;
+
+        msg!(
+            "Insurance surplus collected: {} SOL",
+            surplus as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Link two of the creator's correlated markets so they can be hedged against each
+    /// other; their deadlines must fall within HEDGE_DEADLINE_WINDOW
+    pub fn link_markets(ctx: Context<LinkMarkets>) -> Result<()> {
+        let creator = ctx.accounts.creator.key();
+        let first_key = ctx.accounts.first_market.key();
+        let second_key = ctx.accounts.second_market.key();
+        let first = &mut ctx.accounts.first_market;
+        let second = &mut ctx.accounts.second_market;
+
+        require!(
+            first.creator == creator && second.creator == creator,
+            PredictDuelError::NotMarketCreator
+        );
+        require!(first_key != second_key, PredictDuelError::MarketsNotLinked);
+        for market in [&**first, &**second] {
+            require!(
+                market.status == MarketStatus::Pending || market.status == MarketStatus::Active,
+                PredictDuelError::MarketNotActive
+            );
+        }
+        require!(
+            (first.deadline - second.deadline).abs() <= HEDGE_DEADLINE_WINDOW,
+            PredictDuelError::HedgeDeadlineMismatch
+        );
+
+        first.linked_market = Some(second_key);
+        second.linked_market = Some(first_key);
+
+        msg!("Markets linked for hedging");
+
+        Ok(())
+    }
+
+    /// Bet `prediction` on one market and the opposite side on its linked market in one
+    /// instruction, so neither leg lands without the other
+    pub fn place_hedged_bet(
+        ctx: Context<PlaceHedgedBet>,
+        prediction: bool,
+        first_stake: u64,
+        second_stake: u64,
+    ) -> Result<()> {
+        let bettor = ctx.accounts.bettor.key();
+        let first_key = ctx.accounts.first_market.key();
+        let second_key = ctx.accounts.second_market.key();
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.first_market.linked_market == Some(second_key)
+                && ctx.accounts.second_market.linked_market == Some(first_key),
+            PredictDuelError::MarketsNotLinked
+        );
+        require!(
+            (ctx.accounts.first_market.deadline - ctx.accounts.second_market.deadline).abs()
+                <= HEDGE_DEADLINE_WINDOW,
+            PredictDuelError::HedgeDeadlineMismatch
+        );
+        // Credential-gated markets need the credential checks of place_bet
+        require!(
+            ctx.accounts.first_market.personhood_mint.is_none()
+                && ctx.accounts.second_market.personhood_mint.is_none(),
+            PredictDuelError::PersonhoodRequired
+        );
+        verify_attestation(
+            &ctx.accounts.config,
+            &bettor,
+            ctx.accounts.instructions.as_deref(),
+            clock.unix_timestamp,
+        )?;
+
+        // First leg
+        let market = &mut ctx.accounts.first_market;
+        let participant = &mut ctx.accounts.first_participant;
+        market.assert_open_bet(participant, prediction, first_stake, clock.unix_timestamp)?;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.bettor.to_account_info(),
+                    to: ctx.accounts.first_vault.to_account_info(),
+                },
+            ),
+            first_stake,
+        )?;
+        let is_new = participant.market == Pubkey::default();
+        if is_new {
+            participant.market = first_key;
+            participant.bettor = bettor;
+            participant.prediction = prediction;
+            participant.claimed = false;
+            participant.bump = ctx.bumps.first_participant;
+        }
+        market.credit_bet(
+            participant,
+            is_new,
+            prediction,
+            first_stake,
+            clock.unix_timestamp,
+        )?;
+
+        // Second leg, on the opposite side
+        let market = &mut ctx.accounts.second_market;
+        let participant = &mut ctx.accounts.second_participant;
+        market.assert_open_bet(participant, !prediction, second_stake, clock.unix_timestamp)?;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.bettor.to_account_info(),
+                    to: ctx.accounts.second_vault.to_account_info(),
+                },
+            ),
+            second_stake,
+        )?;
+        let is_new = participant.market == Pubkey::default();
+        if is_new {
+            participant.market = second_key;
+            participant.bettor = bettor;
+            participant.prediction = !prediction;
+            participant.claimed = false;
+            participant.bump = ctx.bumps.second_participant;
+        }
+        market.credit_bet(
+            participant,
+            is_new,
+            !prediction,
+            second_stake,
+            clock.unix_timestamp,
+        )?;
+
+        msg!(
+            "Hedged bet: {} SOL on {} and {} SOL on {} in the linked market",
+            first_stake as f64 / 1_000_000_000.0,
+            if prediction { "YES" } else { "NO" },
+            second_stake as f64 / 1_000_000_000.0,
+            if prediction { "NO" } else { "YES" }
+        );
+
+        Ok(())
+    }
+
+    /// Register the caller as a frontend that bets can be placed through
+    pub fn register_frontend(ctx: Context<RegisterFrontend>) -> Result<()> {
+        let frontend = &mut ctx.accounts.frontend;
+        frontend.owner = ctx.accounts.owner.key();
+        frontend.bump = ctx.bumps.frontend;
+
+        msg!("Frontend registered: {}", frontend.owner);
+
+        Ok(())
+    }
+
+    /// Credit a frontend its share of the protocol fee on the bets it brought to a resolved
+    /// market (permissionless). Participants are passed as remaining accounts; the share
+    /// comes out of the fee before it is collected, so it must run before collect_protocol_fee.
+    pub fn accrue_frontend_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AccrueFrontendFees<'info>>,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let frontend = &mut ctx.accounts.frontend;
+
+        require!(
+            market.status == MarketStatus::Resolved,
+            PredictDuelError::MarketNotResolved
+        );
+        require!(!market.fee_collected, PredictDuelError::AlreadyClaimed);
+        require!(
+            ctx.remaining_accounts.len() <= MAX_SETTLE_BATCH,
+            PredictDuelError::InvalidSettleBatch
+        );
+
+        let market_key = market.key();
+        let share_bps = ctx.accounts.config.frontend_fee_share_bps;
+        let mut stake = 0u64;
+        for info in ctx.remaining_accounts.iter() {
+            let mut participant = Account::<Participant>::try_from(info)?;
+            require!(
+                participant.market == market_key,
+                PredictDuelError::InvalidSettleBatch
+            );
+            if participant.frontend != Some(frontend.owner) || participant.frontend_stake == 0 {
+                continue;
+            }
+            stake += participant.frontend_stake;
+            participant.frontend_stake = 0;
+            participant.exit(&crate::ID)?;
+        }
+
+        let share = (stake as u128 * market.protocol_fee_bps as u128 * share_bps as u128
+            / (BPS_DENOMINATOR as u128 * BPS_DENOMINATOR as u128)) as u64;
+        let share = share.min(market.protocol_fee() - market.fee_rebated);
+        require!(share > 0, PredictDuelError::NothingToClaim);
+
+        let seeds = &[
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes(),
+            &[ctx.bumps.market_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: frontend.to_account_info(),
+                },
+                signer,
+            ),
+            share,
+        )?;
+
+        market.fee_rebated += share;
+        frontend.claimable += share;
+        frontend.total_earned += share;
+
+        msg!(
+            "Frontend fees accrued: {} SOL",
+            share as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw a frontend's accrued fee share to its owner
+    pub fn claim_frontend_fees(ctx: Context<ClaimFrontendFees>) -> Result<()> {
+        let frontend = &mut ctx.accounts.frontend;
+
+        let amount = frontend.claimable;
+        require!(amount > 0, PredictDuelError::NothingToClaim);
+
+        frontend.claimable = 0;
+        frontend.sub_lamports(amount)?;
+        ctx.accounts.owner.add_lamports(amount)?;
+
+        msg!(
+            "Frontend fees claimed: {} SOL",
+            amount as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Register, replace or remove the secondary key that must co-sign claims paying more
+    /// than `threshold`. Once a co-signer is set, changing it needs its signature too.
+    pub fn set_co_signer(
+        ctx: Context<SetCoSigner>,
+        co_signer: Option<Pubkey>,
+        threshold: u64,
+    ) -> Result<()> {
+        let user_profile = &mut ctx.accounts.user_profile;
+        user_profile.assert_co_signed(ctx.accounts.current_co_signer.as_ref(), u64::MAX)?;
+        require!(
+            co_signer != Some(user_profile.wallet),
+            PredictDuelError::InvalidCoSigner
+        );
+
+        user_profile.co_signer = co_signer;
+        user_profile.co_sign_threshold = threshold;
+
+        match co_signer {
+            Some(key) => msg!(
+                "Co-signer {} required on claims above {} SOL",
+                key,
+                threshold as f64 / 1_000_000_000.0
+            ),
+            None => msg!("Co-signer removed"),
+        }
+
+        Ok(())
+    }
+
+    /// Register or remove a recovery key that can move the profile to a new wallet after
+    /// `delay`, during which the wallet can still cancel. Needs the co-signer if one is set.
+    pub fn set_recovery_key(
+        ctx: Context<SetCoSigner>,
+        recovery_key: Option<Pubkey>,
+        delay: i64,
+    ) -> Result<()> {
+        let user_profile = &mut ctx.accounts.user_profile;
+        user_profile.assert_co_signed(ctx.accounts.current_co_signer.as_ref(), u64::MAX)?;
+        require!(
+            (0..=MAX_RECOVERY_DELAY).contains(&delay),
+            PredictDuelError::InvalidRecoveryDelay
+        );
+
+        user_profile.recovery_key = recovery_key;
+        user_profile.recovery_delay = delay;
+        user_profile.pending_wallet = None;
+        user_profile.recovery_eta = 0;
+
+        match recovery_key {
+            Some(key) => msg!("Recovery key {} set with a {}s delay", key, delay),
+            None => msg!("Recovery key removed"),
+        }
+
+        Ok(())
+    }
+
+    /// Start moving a profile to `new_wallet` with its recovery key; completes through
+    /// rotate_profile_authority once the profile's recovery delay has passed
+    pub fn request_profile_recovery(
+        ctx: Context<RequestProfileRecovery>,
+        new_wallet: Pubkey,
+    ) -> Result<()> {
+        let user_profile = &mut ctx.accounts.user_profile;
+        require!(
+            user_profile.recovery_key == Some(ctx.accounts.recovery_key.key()),
+            PredictDuelError::Unauthorized
+        );
+
+        user_profile.pending_wallet = Some(new_wallet);
+        user_profile.recovery_eta = Clock::get()?.unix_timestamp + user_profile.recovery_delay;
+
+        msg!(
+            "Profile recovery to {} requested, executable at {}",
+            new_wallet,
+            user_profile.recovery_eta
+        );
+
+        Ok(())
+    }
+
+    /// Cancel a pending recovery from the profile's own wallet
+    pub fn cancel_profile_recovery(ctx: Context<SetCoSigner>) -> Result<()> {
+        let user_profile = &mut ctx.accounts.user_profile;
+        require!(
+            user_profile.pending_wallet.is_some(),
+            PredictDuelError::NoPendingRecovery
+        );
+
+        user_profile.pending_wallet = None;
+        user_profile.recovery_eta = 0;
+
+        msg!("Profile recovery cancelled");
+
+        Ok(())
+    }
+
+    /// Move a profile, and its loyalty points if it has any, to a new wallet. Signed by the
+    /// current wallet (with its co-signer, if one is set), or by the recovery key once a
+    /// recovery to this wallet has matured; the new wallet signs too and must not have a
+    /// profile yet. The old profile stays behind as a tombstone, so the old wallet's open
+    /// positions still need its co-signer and its markets can still be resolved.
+    pub fn rotate_profile_authority(ctx: Context<RotateProfileAuthority>) -> Result<()> {
+        let user_profile = &mut ctx.accounts.user_profile;
+        let new_wallet = ctx.accounts.new_wallet.key();
+        let authority = ctx.accounts.authority.key();
+
+        require!(
+            user_profile.moved_to.is_none(),
+            PredictDuelError::ProfileMoved
+        );
+        if authority == user_profile.wallet {
+            // Otherwise a stolen hot key could move the profile, and its co-signer rule, away
+            user_profile.assert_co_signed(ctx.accounts.current_co_signer.as_ref(), u64::MAX)?;
+        } else {
+            require!(
+                user_profile.recovery_key == Some(authority)
+                    && user_profile.pending_wallet == Some(new_wallet),
+                PredictDuelError::Unauthorized
+            );
+            require!(
+                Clock::get()?.unix_timestamp >= user_profile.recovery_eta,
+                PredictDuelError::RecoveryDelayActive
+            );
+        }
+        require!(
+            ctx.accounts.points.is_some() == ctx.accounts.new_points.is_some(),
+            PredictDuelError::InvalidProfileRotation
+        );
+
+        let old_wallet = user_profile.wallet;
+        let mut moved = (***user_profile).clone();
+        moved.wallet = new_wallet;
+        moved.bump = ctx.bumps.new_profile;
+        moved.pending_wallet = None;
+        moved.recovery_eta = 0;
+        ctx.accounts.new_profile.set_inner(moved);
+        user_profile.tombstone(new_wallet);
+
+        if let (Some(points), Some(new_points)) = (
+            ctx.accounts.points.as_ref(),
+            ctx.accounts.new_points.as_mut(),
+        ) {
+            new_points.wallet = new_wallet;
+            new_points.balance = points.balance;
+            new_points.lifetime = points.lifetime;
+            new_points.bump = ctx.bumps.new_points.unwrap_or_default();
+        }
+
+        msg!("Profile moved from {} to {}", old_wallet, new_wallet);
+
+        Ok(())
+    }
+
+    /// Claim a unique lowercase username handle for the caller's wallet; a wallet holds at
+    /// most one handle at a time
+    pub fn claim_handle(ctx: Context<ClaimHandle>, handle: String) -> Result<()> {
+        require!(valid_handle(&handle), PredictDuelError::InvalidHandle);
+
+        let handle_account = &mut ctx.accounts.handle_account;
+        handle_account.handle = handle.clone();
+        handle_account.wallet = ctx.accounts.owner.key();
+        handle_account.bump = ctx.bumps.handle_account;
+
+        let wallet_handle = &mut ctx.accounts.wallet_handle;
+        wallet_handle.wallet = ctx.accounts.owner.key();
+        wallet_handle.handle = handle;
+        wallet_handle.bump = ctx.bumps.wallet_handle;
+
+        msg!(
+            "Handle @{} claimed by {}",
+            handle_account.handle,
+            handle_account.wallet
+        );
+
+        Ok(())
+    }
+
+    /// Hand a handle to another wallet, which signs to accept it and must not hold one
+    pub fn transfer_handle(ctx: Context<TransferHandle>) -> Result<()> {
+        let new_owner = ctx.accounts.new_owner.key();
+
+        let handle_account = &mut ctx.accounts.handle_account;
+        handle_account.wallet = new_owner;
+
+        let new_wallet_handle = &mut ctx.accounts.new_wallet_handle;
+        new_wallet_handle.wallet = new_owner;
+        new_wallet_handle.handle = handle_account.handle.clone();
+        new_wallet_handle.bump = ctx.bumps.new_wallet_handle;
+
+        msg!(
+            "Handle @{} transferred to {}",
+            handle_account.handle,
+            new_owner
+        );
+
+        Ok(())
+    }
+
+    /// Give up a handle so anyone can claim it again
+    pub fn release_handle(ctx: Context<ReleaseHandle>) -> Result<()> {
+        msg!("Handle @{} released", ctx.accounts.handle_account.handle);
+
+        Ok(())
+    }
+
+    /// Set the avatar/bio metadata URI on the caller's profile (empty clears it)
+    pub fn set_profile_metadata(
+        ctx: Context<SetProfileMetadata>,
+        metadata_uri: String,
+    ) -> Result<()> {
+        require!(
+            metadata_uri.len() <= MAX_PROFILE_URI_LEN,
+            PredictDuelError::ProfileUriTooLong
+        );
+
+        let user_profile = &mut ctx.accounts.user_profile;
+        user_profile.metadata_uri = metadata_uri;
+
+        msg!("Profile metadata set: {}", user_profile.metadata_uri);
+
+        Ok(())
+    }
+
+    /// Follow another predictor
+    pub fn follow(ctx: Context<FollowUser>) -> Result<()> {
+        let follower = ctx.accounts.follower.key();
+        let followed = ctx.accounts.followed_profile.wallet;
+        require!(follower != followed, PredictDuelError::CannotFollowSelf);
+
+        let follow = &mut ctx.accounts.follow;
+        follow.follower = follower;
+        follow.followed = followed;
+        follow.followed_at = Clock::get()?.unix_timestamp;
+        follow.bump = ctx.bumps.follow;
+
+        ctx.accounts.follower_profile.following += 1;
+        ctx.accounts.followed_profile.followers += 1;
+
+        msg!("{} followed {}", follower, followed);
+
+        Ok(())
+    }
+
+    /// Stop following a predictor; the Follow rent returns to the follower
+    pub fn unfollow(ctx: Context<UnfollowUser>) -> Result<()> {
+        let follower_profile = &mut ctx.accounts.follower_profile;
+        let followed_profile = &mut ctx.accounts.followed_profile;
+        follower_profile.following = follower_profile.following.saturating_sub(1);
+        followed_profile.followers = followed_profile.followers.saturating_sub(1);
+
+        msg!(
+            "{} unfollowed {}",
+            follower_profile.wallet,
+            followed_profile.wallet
+        );
+
+        Ok(())
+    }
+
+    /// Subscribe to notifications about a market
+    pub fn subscribe_market(ctx: Context<SubscribeMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.subscriber = ctx.accounts.subscriber.key();
+        subscription.target = market.key();
+        subscription.kind = SubscriptionKind::Market;
+        subscription.subscribed_at = Clock::get()?.unix_timestamp;
+        subscription.bump = ctx.bumps.subscription;
+
+        market.subscribers += 1;
+
+        msg!("Subscribed to market {}", subscription.target);
+
+        Ok(())
+    }
+
+    /// Subscribe to notifications about a creator's markets
+    pub fn subscribe_creator(ctx: Context<SubscribeCreator>) -> Result<()> {
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.subscriber = ctx.accounts.subscriber.key();
+        subscription.target = ctx.accounts.creator.key();
+        subscription.kind = SubscriptionKind::Creator;
+        subscription.subscribed_at = Clock::get()?.unix_timestamp;
+        subscription.bump = ctx.bumps.subscription;
+
+        msg!("Subscribed to creator {}", subscription.target);
+
+        Ok(())
+    }
+
+    /// Drop a subscription. Market subscriptions pass the market, unless it has since been
+    /// closed, to keep its subscriber count right.
+    pub fn unsubscribe(ctx: Context<Unsubscribe>) -> Result<()> {
+        let subscription = &ctx.accounts.subscription;
+        if let Some(market) = ctx.accounts.market.as_mut() {
+            market.subscribers = market.subscribers.saturating_sub(1);
+        }
+
+        msg!("Unsubscribed from {}", subscription.target);
+
+        Ok(())
+    }
+
+    /// Pledge a resolution boost on a market you hold a position in. Boosts raise the
+    /// market in keepers' resolution queue and are paid to its resolver if it resolves
+    /// within RESOLUTION_GRACE_PERIOD; otherwise they go back to the participant.
+    pub fn boost_resolution(ctx: Context<BoostResolution>, amount: u64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let boost = &mut ctx.accounts.resolution_boost;
+
+        require!(
+            market.status == MarketStatus::Pending || market.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        require!(
+            ctx.accounts.participant.stake > 0,
+            PredictDuelError::NotParticipant
+        );
+        require!(amount > 0, PredictDuelError::StakeTooLow);
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.contributor.to_account_info(),
+                    to: boost.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        boost.market = market.key();
+        boost.contributor = ctx.accounts.contributor.key();
+        boost.amount += amount;
+        boost.bump = ctx.bumps.resolution_boost;
+        market.resolution_boost += amount;
+
+        msg!(
+            "Resolution boost raised to {} SOL",
+            market.resolution_boost as f64 / 1_000_000_000.0
+        );
+
+        Ok(())
+    }
+
+    /// Pay out a settled market's resolution boost (permissionless): to the resolver if the
+    /// market resolved on time and stood past the dispute window, else back to its contributor
+    pub fn settle_resolution_boost(ctx: Context<SettleResolutionBoost>) -> Result<()> {
+        let market = &ctx.accounts.market;
+
+        let recipient = match market.status {
+            MarketStatus::Resolved => {
+                require!(
+                    !market.disputed
+                        && Clock::get()?.unix_timestamp
+                            > market.resolved_at + ctx.accounts.config.dispute_window,
+                    PredictDuelError::DisputeOpen
+                );
+                let on_time = market.resolved_at
+                    <= market
+                        .resolvable_at()
+                        .saturating_add(RESOLUTION_GRACE_PERIOD);
+                match market.resolved_by {
+                    Some(resolver) if on_time && !market.overturned => resolver,
+                    _ => ctx.accounts.resolution_boost.contributor,
+                }
+            }
+            MarketStatus::Voided | MarketStatus::Cancelled | MarketStatus::Halted => {
+                ctx.accounts.resolution_boost.contributor
+            }
+            _ => return err!(PredictDuelError::MarketNotResolved),
+        };
+        require!(
+            ctx.accounts.recipient.key() == recipient,
+            PredictDuelError::InvalidBountyRecipient
+        );
+
+        msg!(
+            "Resolution boost of {} SOL paid to {}",
+            ctx.accounts.resolution_boost.amount as f64 / 1_000_000_000.0,
+            recipient
+        );
+
+        Ok(())
+    }
+
+    /// Set how long after resolution new markets hold their claims, up to the dispute
+    /// window (admin only). Disputes close as claims open, so no claim is paid on a
+    /// resolution that can still be overturned. Existing markets keep their delay.
+    pub fn set_claim_delay(ctx: Context<SetClaimDelay>, delay: i64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        require!(
+            delay > 0 && delay <= config.dispute_window,
+            PredictDuelError::InvalidClaimDelay
+        );
+
+        config.claim_delay = delay;
+
+        msg!("Claim delay set to {}s", delay);
+
+        Ok(())
+    }
+
+    /// Also close the market at `slot` (before any bets), for markets about on-chain events
+    /// where slots are exact and wall-clock time drifts. Unsealed markets become resolvable
+    /// at that slot even if the unix deadline is later; 0 removes the slot deadline.
+    pub fn set_deadline_slot(ctx: Context<SetDeadlineSlot>, slot: u64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.assert_configurable(&ctx.accounts.creator.key())?;
+        require!(
+            slot == 0 || slot > Clock::get()?.slot,
+            PredictDuelError::InvalidDeadlineSlot
+        );
+
+        market.deadline_slot = slot;
+
+        msg!("Deadline slot set to {}", slot);
+
+        Ok(())
+    }
+}
+
+// Account validation structs
+#[derive(Accounts)]
+#[instruction(market_index: u64, question: String, category: MarketCategory)]
+pub struct CreateMarket<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [
+            b"market",
+            creator.key().as_ref(),
+            &market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// May be a program PDA signing through CPI; the payer covers rent, fees and the bond
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// PDA vault that holds all stakes - no data, just lamports
+    /// CHECK: PDA validated via seeds, will be created automatically on first transfer
+    /// PDA is owned by this program (required for PDAs), but only holds lamports
+    #[account(
+        seeds = [
+            b"market_vault",
+            creator.key().as_ref(),
+            &market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + OddsHistory::INIT_SPACE,
+        seeds = [b"odds_history", market.key().as_ref()],
+        bump
+    )]
+    pub odds_history: Box<Account<'info, OddsHistory>>,
+
+    /// CHECK: Blocklist entry for the creator; must not exist
+    #[account(
+        seeds = [b"blocklist", creator.key().as_ref()],
+        bump,
+        constraint = blocklist_entry.data_is_empty() @ PredictDuelError::AddressBlocked
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [b"user_profile", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_profile: Box<Account<'info, UserProfile>>,
+
+    /// CHECK: Third-party resolver, stored as a key only; required for low-score creators
+    pub resolver: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Must be the configured treasury; required unless the creation fee is waived
+    #[account(mut, address = config.treasury)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// Creator pass NFT account, waives the creation fee
+    pub creator_pass:
+        Option<Box<InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>>>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// Keeps category stats live; otherwise they catch up via sync_category_stats
+    #[account(mut, seeds = [b"category_stats", (category as u8).to_le_bytes().as_ref()], bump = category_stats.bump)]
+    pub category_stats: Option<Box<Account<'info, CategoryStats>>>,
+
+    /// Counts the market towards the creator's stats
+    #[account(mut, seeds = [b"creator_stats", creator.key().as_ref()], bump = creator_stats.bump)]
+    pub creator_stats: Option<Box<Account<'info, CreatorStats>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceBet<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = 8 + Participant::INIT_SPACE,
+        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [b"user_profile", bettor.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"odds_history", market.key().as_ref()],
+        bump = odds_history.bump
+    )]
+    pub odds_history: Box<Account<'info, OddsHistory>>,
+
+    /// Market's recent-bets feed, if one has been initialized
+    #[account(mut, seeds = [b"recent_bets", market.key().as_ref()], bump = recent_bets.bump)]
+    pub recent_bets: Option<Box<Account<'info, RecentBets>>>,
+
+    /// CHECK: Blocklist entry for the bettor; must not exist
+    #[account(
+        seeds = [b"blocklist", bettor.key().as_ref()],
+        bump,
+        constraint = blocklist_entry.data_is_empty() @ PredictDuelError::AddressBlocked
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Instructions sysvar; required while the config demands attestations
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    /// Required on personhood-gated markets
+    pub personhood_credential:
+        Option<Box<InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>>>,
+
+    /// Keeps category stats live; otherwise they catch up via sync_category_stats
+    #[account(mut, seeds = [b"category_stats", (market.category as u8).to_le_bytes().as_ref()], bump = category_stats.bump)]
+    pub category_stats: Option<Box<Account<'info, CategoryStats>>>,
+
+    /// Frontend the bet was placed through; earns a share of its protocol fee
+    #[account(seeds = [b"frontend", frontend.owner.as_ref()], bump = frontend.bump)]
+    pub frontend: Option<Box<Account<'info, Frontend>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_index: u64)]
+pub struct CreatePriceMarket<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [
+            b"market",
+            creator.key().as_ref(),
+            &market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// May be a program PDA signing through CPI; the payer covers rent and fees
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [b"oracle_feed", oracle_feed.feed.as_ref()], bump = oracle_feed.bump)]
+    pub oracle_feed: Account<'info, OracleFeed>,
+
+    /// PDA vault that holds all stakes - no data, just lamports
+    /// CHECK: PDA validated via seeds, will be created automatically on first transfer
+    #[account(
+        seeds = [
+            b"market_vault",
+            creator.key().as_ref(),
+            &market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + OddsHistory::INIT_SPACE,
+        seeds = [b"odds_history", market.key().as_ref()],
+        bump
+    )]
+    pub odds_history: Box<Account<'info, OddsHistory>>,
+
+    /// CHECK: Blocklist entry for the creator; must not exist
+    #[account(
+        seeds = [b"blocklist", creator.key().as_ref()],
+        bump,
+        constraint = blocklist_entry.data_is_empty() @ PredictDuelError::AddressBlocked
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [b"user_profile", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_profile: Box<Account<'info, UserProfile>>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Must be the configured treasury; required unless the creation fee is waived
+    #[account(mut, address = config.treasury)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// Creator pass NFT account, waives the creation fee
+    pub creator_pass:
+        Option<Box<InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>>>,
+
+    /// Keeps category stats live; otherwise they catch up via sync_category_stats
+    #[account(mut, seeds = [b"category_stats", (MarketCategory::Crypto as u8).to_le_bytes().as_ref()], bump = category_stats.bump)]
+    pub category_stats: Option<Box<Account<'info, CategoryStats>>>,
+
+    /// Counts the market towards the creator's stats
+    #[account(mut, seeds = [b"creator_stats", creator.key().as_ref()], bump = creator_stats.bump)]
+    pub creator_stats: Option<Box<Account<'info, CreatorStats>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveOracleMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"oracle_feed", price_feed.key().as_ref()],
+        bump = oracle_feed.bump,
+        constraint = market.oracle_feed == Some(oracle_feed.feed) @ PredictDuelError::InvalidOracleFeed
+    )]
+    pub oracle_feed: Account<'info, OracleFeed>,
+
+    /// CHECK: Oracle price account, tied to the market via the registry entry and owner-checked on read
+    pub price_feed: UncheckedAccount<'info>,
+
+    /// Caller supplying the resolution; earns the market's information bounty
+    pub hunter: Option<Signer<'info>>,
+
+    /// Keeps category stats live; otherwise they catch up via sync_category_stats
+    #[account(mut, seeds = [b"category_stats", (market.category as u8).to_le_bytes().as_ref()], bump = category_stats.bump)]
+    pub category_stats: Option<Account<'info, CategoryStats>>,
+}
+
+#[derive(Accounts)]
+pub struct SetWeatherCondition<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+
+    #[account(seeds = [b"oracle_feed", oracle_feed.feed.as_ref()], bump = oracle_feed.bump)]
+    pub oracle_feed: Account<'info, OracleFeed>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct ReportGameResult<'info> {
+    #[account(
+        init,
+        payer = reporter,
+        space = 8 + GameResult::INIT_SPACE,
+        seeds = [
+            b"game_result",
+            reporter.key().as_ref(),
+            &game_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub game_result: Account<'info, GameResult>,
+
+    #[account(seeds = [b"oracle_feed", reporter.key().as_ref()], bump = oracle_feed.bump)]
+    pub oracle_feed: Account<'info, OracleFeed>,
+
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BindGame<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+
+    #[account(seeds = [b"oracle_feed", oracle_feed.feed.as_ref()], bump = oracle_feed.bump)]
+    pub oracle_feed: Account<'info, OracleFeed>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveGameMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [
+            b"game_result",
+            game_result.reporter.as_ref(),
+            &game_result.game_id.to_le_bytes()
+        ],
+        bump = game_result.bump
+    )]
+    pub game_result: Account<'info, GameResult>,
+
+    #[account(seeds = [b"oracle_feed", game_result.reporter.as_ref()], bump = oracle_feed.bump)]
+    pub oracle_feed: Account<'info, OracleFeed>,
+
+    /// Caller supplying the resolution; earns the market's information bounty
+    pub hunter: Option<Signer<'info>>,
+
+    /// Keeps category stats live; otherwise they catch up via sync_category_stats
+    #[account(mut, seeds = [b"category_stats", (market.category as u8).to_le_bytes().as_ref()], bump = category_stats.bump)]
+    pub category_stats: Option<Account<'info, CategoryStats>>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", market.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Box<Account<'info, UserProfile>>,
+
+    pub resolver: Signer<'info>,
+
+    /// Keeps category stats live; otherwise they catch up via sync_category_stats
+    #[account(mut, seeds = [b"category_stats", (market.category as u8).to_le_bytes().as_ref()], bump = category_stats.bump)]
+    pub category_stats: Option<Account<'info, CategoryStats>>,
+
+    /// Third-party resolver's profile, credited with the resolution; required on markets
+    /// with one, so its accuracy record can't be dodged
+    #[account(
+        mut,
+        seeds = [b"user_profile", resolver.key().as_ref()],
+        bump = resolver_profile.bump,
+        constraint = resolver_profile.key() != creator_profile.key() @ PredictDuelError::Unauthorized
+    )]
+    pub resolver_profile: Option<Box<Account<'info, UserProfile>>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimWinnings<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), winner.key().as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// Winner's profile, for fee-tier rebates and charity donations
+    #[account(mut, seeds = [b"user_profile", winner.key().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Option<Box<Account<'info, UserProfile>>>,
+
+    /// CHECK: Must be the configured charity; required when the winner has opted in
+    #[account(mut, address = config.charity)]
+    pub charity: Option<UncheckedAccount<'info>>,
+
+    /// Pays booster bonuses; required when the position is boosted
+    #[account(mut, seeds = [b"booster_fund"], bump = booster_fund.bump)]
+    pub booster_fund: Option<Box<Account<'info, BoosterFund>>>,
+
+    /// CHECK: Any account chosen by the winner to receive the payout; defaults to the winner
+    #[account(mut)]
+    pub destination: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Winner's profile PDA, read for its co-signer rule; may not exist
+    #[account(seeds = [b"user_profile", winner.key().as_ref()], bump)]
+    pub profile_guard: UncheckedAccount<'info>,
+
+    /// Required on claims above the profile's co-sign threshold
+    pub co_signer: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RefundStake<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetImpliedTwap<'info> {
+    pub market: Account<'info, Market>,
+}
+
+#[derive(Accounts)]
+pub struct SeedLiquidity<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"odds_history", market.key().as_ref()],
+        bump = odds_history.bump
+    )]
+    pub odds_history: Box<Account<'info, OddsHistory>>,
+
+    /// CHECK: Blocklist entry for the creator; must not exist
+    #[account(
+        seeds = [b"blocklist", creator.key().as_ref()],
+        bump,
+        constraint = blocklist_entry.data_is_empty() @ PredictDuelError::AddressBlocked
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimSeed<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(event_index: u64)]
+pub struct CreateEvent<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Event::INIT_SPACE,
+        seeds = [
+            b"event",
+            authority.key().as_ref(),
+            &event_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub event: Account<'info, Event>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AttachToEvent<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveEvent<'info> {
+    #[account(mut, has_one = authority)]
+    pub event: Account<'info, Event>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveFromEvent<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", market.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Box<Account<'info, UserProfile>>,
+
+    /// Caller applying the event result, recorded as the market's resolver
+    pub resolver: Signer<'info>,
+
+    /// Keeps category stats live; otherwise they catch up via sync_category_stats
+    #[account(mut, seeds = [b"category_stats", (market.category as u8).to_le_bytes().as_ref()], bump = category_stats.bump)]
+    pub category_stats: Option<Account<'info, CategoryStats>>,
+
+    /// Designated third-party resolver's profile, credited with the resolution; required on
+    /// markets with one
+    #[account(
+        mut,
+        seeds = [b"user_profile", resolver_profile.wallet.as_ref()],
+        bump = resolver_profile.bump,
+        constraint = market.resolver == Some(resolver_profile.wallet) @ PredictDuelError::Unauthorized
+    )]
+    pub resolver_profile: Option<Box<Account<'info, UserProfile>>>,
+}
+
+#[derive(Accounts)]
+pub struct SetSpread<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSettlementMode<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OpenBook<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateOdds<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleBook<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OfferLay<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = layer,
+        space = 8 + LayOffer::INIT_SPACE,
+        seeds = [b"lay_offer", market.key().as_ref(), layer.key().as_ref()],
+        bump
+    )]
+    pub lay_offer: Account<'info, LayOffer>,
+
+    #[account(mut)]
+    pub layer: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Blocklist entry for the layer; must not exist
+    #[account(
+        seeds = [b"blocklist", layer.key().as_ref()],
+        bump,
+        constraint = blocklist_entry.data_is_empty() @ PredictDuelError::AddressBlocked
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar; required while the config demands attestations
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TakeLay<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut, has_one = market)]
+    pub lay_offer: Account<'info, LayOffer>,
+
+    #[account(
+        init_if_needed,
+        payer = backer,
+        space = 8 + BackPosition::INIT_SPACE,
+        seeds = [b"back_position", lay_offer.key().as_ref(), backer.key().as_ref()],
+        bump
+    )]
+    pub back_position: Account<'info, BackPosition>,
+
+    #[account(mut)]
+    pub backer: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Must be the configured treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Blocklist entry for the backer; must not exist
+    #[account(
+        seeds = [b"blocklist", backer.key().as_ref()],
+        bump,
+        constraint = blocklist_entry.data_is_empty() @ PredictDuelError::AddressBlocked
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar; required while the config demands attestations
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelLay<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(mut, has_one = market, has_one = layer)]
+    pub lay_offer: Account<'info, LayOffer>,
+
+    #[account(mut)]
+    pub layer: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleLayOffer<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        has_one = market,
+        has_one = layer,
+        constraint = !lay_offer.settled @ PredictDuelError::AlreadyClaimed
+    )]
+    pub lay_offer: Account<'info, LayOffer>,
+
+    /// Receives the unmatched liability, plus the backers' stakes if the lay won
+    #[account(mut)]
+    pub layer: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRebates<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        has_one = market,
+        has_one = layer,
+        constraint = lay_offer.rebates_accrued > 0 @ PredictDuelError::NothingToClaim
+    )]
+    pub lay_offer: Account<'info, LayOffer>,
+
+    /// The maker: rebates go to whoever posted the offer
+    #[account(mut)]
+    pub layer: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PurgeExpiredLay<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(mut, has_one = market, has_one = layer)]
+    pub lay_offer: Account<'info, LayOffer>,
+
+    /// CHECK: Validated against the offer's layer
+    #[account(mut)]
+    pub layer: UncheckedAccount<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimBackPosition<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(has_one = market)]
+    pub lay_offer: Account<'info, LayOffer>,
+
+    #[account(
+        mut,
+        seeds = [b"back_position", lay_offer.key().as_ref(), backer.key().as_ref()],
+        bump = back_position.bump
+    )]
+    pub back_position: Account<'info, BackPosition>,
+
+    #[account(mut)]
+    pub backer: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureEarlyBonus<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetBettingCutoff<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetClosingAuction<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClearClosingAuction<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureLateDiscount<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EnableSealedBets<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CommitBet<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = bettor,
+        space = 8 + Participant::INIT_SPACE,
+        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [b"user_profile", bettor.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Instructions sysvar; required while the config demands attestations
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Blocklist entry for the bettor; must not exist
+    #[account(
+        seeds = [b"blocklist", bettor.key().as_ref()],
+        bump,
+        constraint = blocklist_entry.data_is_empty() @ PredictDuelError::AddressBlocked
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealBet<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    pub bettor: Signer<'info>,
+
+    /// CHECK: Blocklist entry for the bettor; must not exist
+    #[account(
+        seeds = [b"blocklist", bettor.key().as_ref()],
+        bump,
+        constraint = blocklist_entry.data_is_empty() @ PredictDuelError::AddressBlocked
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CashOut<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        close = bettor,
+        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"odds_history", market.key().as_ref()],
+        bump = odds_history.bump
+    )]
+    pub odds_history: Box<Account<'info, OddsHistory>>,
+
+    /// CHECK: Bettor's profile PDA, read for its co-signer rule; may not exist
+    #[account(seeds = [b"user_profile", bettor.key().as_ref()], bump)]
+    pub profile_guard: UncheckedAccount<'info>,
+
+    /// Required on cash-outs above the profile's co-sign threshold
+    pub co_signer: Option<Signer<'info>>,
+
+    /// The position's exit order, if any; closed with it so its trigger can't fire on a
+    /// position later reopened at the same address
+    #[account(
+        mut,
+        close = bettor,
+        seeds = [b"exit_order", participant.key().as_ref()],
+        bump = exit_order.bump
+    )]
+    pub exit_order: Option<Account<'info, ExitOrder>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterExit<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    #[account(
+        init,
+        payer = bettor,
+        space = 8 + ExitOrder::INIT_SPACE,
+        seeds = [b"exit_order", participant.key().as_ref()],
+        bump
+    )]
+    pub exit_order: Account<'info, ExitOrder>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    /// CHECK: Bettor's profile PDA, read for its co-signer rule; may not exist
+    #[account(seeds = [b"user_profile", bettor.key().as_ref()], bump)]
+    pub profile_guard: UncheckedAccount<'info>,
+
+    /// Lets keepers execute the order above the profile's co-sign threshold
+    pub co_signer: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelExit<'info> {
+    #[account(
+        mut,
+        close = bettor,
+        has_one = bettor,
+    )]
+    pub exit_order: Account<'info, ExitOrder>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteExit<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        close = bettor,
+        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    /// Closed to the keeper, whose rent refund is the execution reward
+    #[account(
+        mut,
+        close = keeper,
+        has_one = market,
+        has_one = participant,
+        has_one = bettor,
+    )]
+    pub exit_order: Account<'info, ExitOrder>,
+
+    /// CHECK: Position owner receiving the cash-out, validated against the exit order
+    #[account(mut)]
+    pub bettor: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"odds_history", market.key().as_ref()],
+        bump = odds_history.bump
+    )]
+    pub odds_history: Box<Account<'info, OddsHistory>>,
+
+    /// CHECK: Bettor's profile PDA, read for its co-signer rule unless the order was
+    /// co-signed when registered; may not exist
+    #[account(seeds = [b"user_profile", bettor.key().as_ref()], bump)]
+    pub profile_guard: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureVesting<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StartVesting<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), winner.key().as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    #[account(
+        init,
+        payer = winner,
+        space = 8 + VestingSchedule::INIT_SPACE,
+        seeds = [b"vesting", participant.key().as_ref()],
+        bump
+    )]
+    pub vesting: Account<'info, VestingSchedule>,
+
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// Winner's profile, for fee-tier rebates and charity donations
+    #[account(mut, seeds = [b"user_profile", winner.key().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Option<Box<Account<'info, UserProfile>>>,
+
+    /// CHECK: Must be the configured charity; required when the winner has opted in
+    #[account(mut, address = config.charity)]
+    pub charity: Option<UncheckedAccount<'info>>,
+
+    /// Pays booster bonuses; required when the position is boosted
+    #[account(mut, seeds = [b"booster_fund"], bump = booster_fund.bump)]
+    pub booster_fund: Option<Box<Account<'info, BoosterFund>>>,
+
+    /// CHECK: Winner's profile PDA, read for its co-signer rule; may not exist
+    #[account(seeds = [b"user_profile", winner.key().as_ref()], bump)]
+    pub profile_guard: UncheckedAccount<'info>,
+
+    /// Required on claims above the profile's co-sign threshold
+    pub co_signer: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        has_one = market,
+        has_one = beneficiary,
+    )]
+    pub vesting: Account<'info, VestingSchedule>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Beneficiary's profile PDA, read for its co-signer rule; may not exist
+    #[account(seeds = [b"user_profile", beneficiary.key().as_ref()], bump)]
+    pub profile_guard: UncheckedAccount<'info>,
+
+    /// Required on claims above the profile's co-sign threshold
+    pub co_signer: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::PredictDuel>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ PredictDuelError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterOracleFeed<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + OracleFeed::INIT_SPACE,
+        seeds = [b"oracle_feed", feed.key().as_ref()],
+        bump
+    )]
+    pub oracle_feed: Account<'info, OracleFeed>,
+
+    /// CHECK: Oracle account; ownership is checked against the provider's program
+    pub feed: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetOracleFeedActive<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub oracle_feed: Account<'info, OracleFeed>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOracleFeedMaxAge<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub oracle_feed: Account<'info, OracleFeed>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMarketOracle<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(seeds = [b"oracle_feed", oracle_feed.feed.as_ref()], bump = oracle_feed.bump)]
+    pub oracle_feed: Account<'info, OracleFeed>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct RegisterForeignEmitter<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ForeignEmitter::INIT_SPACE,
+        seeds = [b"foreign_emitter", chain.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(emitter_chain: u16, sequence: u64, bettor: Pubkey)]
+pub struct ReceiveCrossChainBet<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + Participant::INIT_SPACE,
+        seeds = [b"participant", market.key().as_ref(), bettor.as_ref()],
+        bump
+    )]
+    pub participant: Box<Account<'info, Participant>>,
+
+    #[account(
+        seeds = [b"foreign_emitter", emitter_chain.to_le_bytes().as_ref()],
+        bump = foreign_emitter.bump
+    )]
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+
+    /// CHECK: Verified VAA posted by the Wormhole core bridge; owner and contents checked on read
+    pub posted_vaa: UncheckedAccount<'info>,
+
+    /// Marks the VAA as consumed so it can't be replayed
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + VaaReceipt::INIT_SPACE,
+        seeds = [
+            b"vaa_receipt",
+            emitter_chain.to_le_bytes().as_ref(),
+            &sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub vaa_receipt: Account<'info, VaaReceipt>,
+
+    /// PDA holding liquidity that backs deposits made on other chains
+    /// CHECK: PDA validated via seeds, lamports only
+    #[account(mut, seeds = [b"bridge_vault"], bump)]
+    pub bridge_vault: UncheckedAccount<'info>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"odds_history", market.key().as_ref()],
+        bump = odds_history.bump
+    )]
+    pub odds_history: Box<Account<'info, OddsHistory>>,
+
+    /// Market's recent-bets feed, if one has been initialized
+    #[account(mut, seeds = [b"recent_bets", market.key().as_ref()], bump = recent_bets.bump)]
+    pub recent_bets: Option<Box<Account<'info, RecentBets>>>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Instructions sysvar; required while the config demands attestations
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCrossChain<'info> {
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), participant.bettor.as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Box<Account<'info, Participant>>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    /// CHECK: PDA validated via seeds, lamports only
+    #[account(mut, seeds = [b"bridge_vault"], bump)]
+    pub bridge_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole core bridge config, validated via seeds
+    #[account(mut, seeds = [b"Bridge"], bump, seeds::program = WORMHOLE_CORE_BRIDGE_PROGRAM_ID)]
+    pub wormhole_bridge: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole fee collector, validated via seeds
+    #[account(mut, seeds = [b"fee_collector"], bump, seeds::program = WORMHOLE_CORE_BRIDGE_PROGRAM_ID)]
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+
+    /// CHECK: This program's emitter PDA, signs posted messages
+    #[account(seeds = [b"emitter"], bump)]
+    pub wormhole_emitter: UncheckedAccount<'info>,
+
+    /// CHECK: Emitter sequence tracker owned by the core bridge, validated via seeds
+    #[account(
+        mut,
+        seeds = [b"Sequence", wormhole_emitter.key().as_ref()],
+        bump,
+        seeds::program = WORMHOLE_CORE_BRIDGE_PROGRAM_ID
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    /// CHECK: Message account created by the core bridge, one per position
+    #[account(mut, seeds = [b"payout_message", participant.key().as_ref()], bump)]
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole core bridge program
+    #[account(address = WORMHOLE_CORE_BRIDGE_PROGRAM_ID)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetReceiptTree<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ReceiptTree::INIT_SPACE,
+        seeds = [b"receipt_tree"],
+        bump
+    )]
+    pub receipt_tree: Account<'info, ReceiptTree>,
+
+    /// CHECK: Bubblegum merkle tree, owned by the account compression program
+    #[account(owner = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintPositionReceipt<'info> {
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Box<Account<'info, Participant>>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    #[account(seeds = [b"receipt_tree"], bump = receipt_tree.bump)]
+    pub receipt_tree: Box<Account<'info, ReceiptTree>>,
+
+    /// CHECK: Tree delegate PDA that signs receipt mints
+    #[account(seeds = [b"receipt_authority"], bump)]
+    pub receipt_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum tree config, validated via seeds
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = BUBBLEGUM_PROGRAM_ID
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the registered receipt tree
+    #[account(mut, address = receipt_tree.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: SPL account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimWithReceipt<'info> {
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), participant.bettor.as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Box<Account<'info, Participant>>,
+
+    /// Current owner of the receipt; receives the payout
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// CHECK: Leaf delegate recorded in the receipt leaf (the holder if none was set)
+    pub leaf_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"receipt_tree"], bump = receipt_tree.bump)]
+    pub receipt_tree: Box<Account<'info, ReceiptTree>>,
+
+    /// CHECK: Bubblegum tree config, validated via seeds
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = BUBBLEGUM_PROGRAM_ID
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the registered receipt tree
+    #[account(mut, address = receipt_tree.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: SPL account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    /// CHECK: Holder's profile PDA, read for its co-signer rule; may not exist
+    #[account(seeds = [b"user_profile", holder.key().as_ref()], bump)]
+    pub profile_guard: UncheckedAccount<'info>,
+
+    /// Required on claims above the profile's co-sign threshold
+    pub co_signer: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeatured<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTrophy<'info> {
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), winner.key().as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Box<Account<'info, Participant>>,
+
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    #[account(seeds = [b"receipt_tree"], bump = receipt_tree.bump)]
+    pub receipt_tree: Box<Account<'info, ReceiptTree>>,
+
+    /// CHECK: Tree delegate PDA that signs mints
+    #[account(seeds = [b"receipt_authority"], bump)]
+    pub receipt_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum tree config, validated via seeds
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = BUBBLEGUM_PROGRAM_ID
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the registered tree
+    #[account(mut, address = receipt_tree.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: SPL account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetModerator<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDiscussionRoot<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Market creator or the config moderator
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BoostMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = booster,
+        space = 8 + Boost::INIT_SPACE,
+        seeds = [b"boost", market.key().as_ref()],
+        bump
+    )]
+    pub boost: Account<'info, Boost>,
+
+    #[account(
+        init_if_needed,
+        payer = booster,
+        space = 8 + BoostPosition::INIT_SPACE,
+        seeds = [b"boost_position", market.key().as_ref(), booster.key().as_ref()],
+        bump
+    )]
+    pub boost_position: Account<'info, BoostPosition>,
+
+    #[account(mut)]
+    pub booster: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CollectProtocolFee<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Must be the configured treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Present only if the market was boosted
+    #[account(mut, seeds = [b"boost", market.key().as_ref()], bump = boost.bump)]
+    pub boost: Option<Account<'info, Boost>>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawBoost<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut, seeds = [b"boost", market.key().as_ref()], bump = boost.bump)]
+    pub boost: Account<'info, Boost>,
+
+    #[account(
+        mut,
+        seeds = [b"boost_position", market.key().as_ref(), booster.key().as_ref()],
+        bump = boost_position.bump
+    )]
+    pub boost_position: Account<'info, BoostPosition>,
+
+    #[account(mut)]
+    pub booster: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateCopyConfig<'info> {
+    #[account(
+        init,
+        payer = follower,
+        space = 8 + CopyConfig::INIT_SPACE,
+        seeds = [b"copy_config", follower.key().as_ref(), leader.key().as_ref()],
+        bump
+    )]
+    pub copy_config: Account<'info, CopyConfig>,
+
+    #[account(mut)]
+    pub follower: Signer<'info>,
+
+    /// CHECK: Any wallet can be followed
+    pub leader: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CopyBet<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"participant", market.key().as_ref(), copy_config.leader.as_ref()],
+        bump = leader_position.bump
+    )]
+    pub leader_position: Box<Account<'info, Participant>>,
+
+    #[account(
+        mut,
+        seeds = [b"copy_config", copy_config.follower.as_ref(), copy_config.leader.as_ref()],
+        bump = copy_config.bump
+    )]
+    pub copy_config: Box<Account<'info, CopyConfig>>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = 8 + Participant::INIT_SPACE,
+        seeds = [b"participant", market.key().as_ref(), copy_config.follower.as_ref()],
+        bump
+    )]
+    pub participant: Box<Account<'info, Participant>>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"odds_history", market.key().as_ref()],
+        bump = odds_history.bump
+    )]
+    pub odds_history: Box<Account<'info, OddsHistory>>,
+
+    /// Market's recent-bets feed, if one has been initialized
+    #[account(mut, seeds = [b"recent_bets", market.key().as_ref()], bump = recent_bets.bump)]
+    pub recent_bets: Option<Box<Account<'info, RecentBets>>>,
+
+    /// Anyone can run the mirror; pays rent for the follower's position
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// CHECK: Blocklist entry for the follower; must not exist
+    #[account(
+        seeds = [b"blocklist", copy_config.follower.as_ref()],
+        bump,
+        constraint = blocklist_entry.data_is_empty() @ PredictDuelError::AddressBlocked
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Instructions sysvar; required while the config demands attestations
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseCopyConfig<'info> {
+    #[account(
+        mut,
+        close = follower,
+        seeds = [b"copy_config", follower.key().as_ref(), copy_config.leader.as_ref()],
+        bump = copy_config.bump
+    )]
+    pub copy_config: Account<'info, CopyConfig>,
+
+    #[account(mut)]
+    pub follower: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OpenManagedAccount<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ManagedAccount::INIT_SPACE,
+        seeds = [b"managed", owner.key().as_ref(), manager.key().as_ref()],
+        bump
+    )]
+    pub managed_account: Account<'info, ManagedAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Manager key granted betting rights
+    pub manager: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OwnManagedAccount<'info> {
+    #[account(
+        mut,
+        seeds = [b"managed", owner.key().as_ref(), managed_account.manager.as_ref()],
+        bump = managed_account.bump
+    )]
+    pub managed_account: Account<'info, ManagedAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManagedBet<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"managed", managed_account.owner.as_ref(), manager.key().as_ref()],
+        bump = managed_account.bump
+    )]
+    pub managed_account: Box<Account<'info, ManagedAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = manager,
+        space = 8 + Participant::INIT_SPACE,
+        seeds = [b"participant", market.key().as_ref(), managed_account.key().as_ref()],
+        bump
+    )]
+    pub participant: Box<Account<'info, Participant>>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"odds_history", market.key().as_ref()],
+        bump = odds_history.bump
+    )]
+    pub odds_history: Box<Account<'info, OddsHistory>>,
+
+    /// Market's recent-bets feed, if one has been initialized
+    #[account(mut, seeds = [b"recent_bets", market.key().as_ref()], bump = recent_bets.bump)]
+    pub recent_bets: Option<Box<Account<'info, RecentBets>>>,
+
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    /// CHECK: Blocklist entry for the account owner; must not exist
+    #[account(
+        seeds = [b"blocklist", managed_account.owner.as_ref()],
+        bump,
+        constraint = blocklist_entry.data_is_empty() @ PredictDuelError::AddressBlocked
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Instructions sysvar; required while the config demands attestations
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleManagedPosition<'info> {
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), managed_account.key().as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Box<Account<'info, Participant>>,
+
+    #[account(
+        mut,
+        seeds = [b"managed", managed_account.owner.as_ref(), managed_account.manager.as_ref()],
+        bump = managed_account.bump
+    )]
+    pub managed_account: Box<Account<'info, ManagedAccount>>,
+
+    /// CHECK: Receives the performance fee
+    #[account(mut, address = managed_account.manager)]
+    pub manager: UncheckedAccount<'info>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSyndicate<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = captain,
+        space = 8 + Syndicate::INIT_SPACE,
+        seeds = [b"syndicate", market.key().as_ref(), captain.key().as_ref()],
+        bump
+    )]
+    pub syndicate: Account<'info, Syndicate>,
+
+    #[account(mut)]
+    pub captain: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JoinSyndicate<'info> {
+    #[account(
+        mut,
+        seeds = [b"syndicate", syndicate.market.as_ref(), syndicate.captain.as_ref()],
+        bump = syndicate.bump
+    )]
+    pub syndicate: Account<'info, Syndicate>,
+
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = 8 + SyndicateMember::INIT_SPACE,
+        seeds = [b"syndicate_member", syndicate.key().as_ref(), member.key().as_ref()],
+        bump
+    )]
+    pub membership: Account<'info, SyndicateMember>,
+
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveSyndicate<'info> {
+    #[account(
+        mut,
+        seeds = [b"syndicate", syndicate.market.as_ref(), syndicate.captain.as_ref()],
+        bump = syndicate.bump
+    )]
+    pub syndicate: Account<'info, Syndicate>,
+
+    #[account(
+        mut,
+        seeds = [b"syndicate_member", syndicate.key().as_ref(), member.key().as_ref()],
+        bump = membership.bump
+    )]
+    pub membership: Account<'info, SyndicateMember>,
+
+    #[account(mut)]
+    pub member: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SyndicateBet<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        has_one = market,
+        seeds = [b"syndicate", market.key().as_ref(), captain.key().as_ref()],
+        bump = syndicate.bump
+    )]
+    pub syndicate: Box<Account<'info, Syndicate>>,
+
+    #[account(
+        init,
+        payer = captain,
+        space = 8 + Participant::INIT_SPACE,
+        seeds = [b"participant", market.key().as_ref(), syndicate.key().as_ref()],
+        bump
+    )]
+    pub participant: Box<Account<'info, Participant>>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"odds_history", market.key().as_ref()],
+        bump = odds_history.bump
+    )]
+    pub odds_history: Box<Account<'info, OddsHistory>>,
+
+    /// Market's recent-bets feed, if one has been initialized
+    #[account(mut, seeds = [b"recent_bets", market.key().as_ref()], bump = recent_bets.bump)]
+    pub recent_bets: Option<Box<Account<'info, RecentBets>>>,
+
+    #[account(mut)]
+    pub captain: Signer<'info>,
+
+    /// CHECK: Blocklist entry for the captain; must not exist
+    #[account(
+        seeds = [b"blocklist", captain.key().as_ref()],
+        bump,
+        constraint = blocklist_entry.data_is_empty() @ PredictDuelError::AddressBlocked
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Instructions sysvar; required while the config demands attestations
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleSyndicate<'info> {
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        has_one = market,
+        seeds = [b"syndicate", market.key().as_ref(), syndicate.captain.as_ref()],
+        bump = syndicate.bump
+    )]
+    pub syndicate: Box<Account<'info, Syndicate>>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), syndicate.key().as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Box<Account<'info, Participant>>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetGovernanceMint<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    pub governance_mint: Account<'info, anchor_spl::token::Mint>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [b"proposal", config.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(address = config.governance_mint)]
+    pub governance_mint: Account<'info, anchor_spl::token::Mint>,
+
+    /// Must hold the proposal threshold
+    #[account(token::mint = governance_mint, token::authority = proposer)]
+    pub proposer_token_account: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"proposal", proposal.index.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + VoteRecord::INIT_SPACE,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        token::mint = governance_mint,
+        token::authority = proposal,
+        seeds = [b"vote_escrow", proposal.key().as_ref()],
+        bump
+    )]
+    pub vote_escrow: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(address = config.governance_mint)]
+    pub governance_mint: Account<'info, anchor_spl::token::Mint>,
+
+    #[account(mut, token::mint = governance_mint, token::authority = voter)]
+    pub voter_token_account: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawVote<'info> {
+    #[account(seeds = [b"proposal", proposal.index.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        close = voter,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(mut, seeds = [b"vote_escrow", proposal.key().as_ref()], bump)]
+    pub vote_escrow: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(mut, token::authority = voter)]
+    pub voter_token_account: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"proposal", proposal.index.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Quorum is measured against its supply
+    #[account(address = config.governance_mint)]
+    pub governance_mint: Account<'info, anchor_spl::token::Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + QueuedAction::INIT_SPACE,
+        seeds = [b"queued_action", config.queued_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteGovernanceAction<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = executor,
+        seeds = [b"queued_action", queued_action.index.to_le_bytes().as_ref()],
+        bump = queued_action.bump
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+
+    /// Required when the change publishes an airdrop root
+    #[account(mut, seeds = [b"distributor", distributor.distributor_id.to_le_bytes().as_ref()], bump = distributor.bump)]
+    pub distributor: Option<Account<'info, MerkleDistributor>>,
+
+    #[account(mut)]
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureRewards<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Rewards::INIT_SPACE,
+        seeds = [b"rewards"],
+        bump
+    )]
+    pub rewards: Account<'info, Rewards>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = reward_mint,
+        token::authority = rewards,
+        seeds = [b"rewards_vault"],
+        bump
+    )]
+    pub rewards_vault: Account<'info, anchor_spl::token::TokenAccount>,
+
+    pub reward_mint: Account<'info, anchor_spl::token::Mint>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct RecordVolume<'info> {
+    #[account(seeds = [b"rewards"], bump = rewards.bump)]
+    pub rewards: Account<'info, Rewards>,
+
+    #[account(mut)]
+    pub participant: Account<'info, Participant>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + RewardEpoch::INIT_SPACE,
+        seeds = [b"reward_epoch", epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + UserEpochVolume::INIT_SPACE,
+        seeds = [b"user_volume", epoch.to_le_bytes().as_ref(), participant.bettor.as_ref()],
+        bump
+    )]
+    pub user_volume: Account<'info, UserEpochVolume>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct ClaimRewards<'info> {
+    #[account(seeds = [b"rewards"], bump = rewards.bump)]
+    pub rewards: Account<'info, Rewards>,
+
+    #[account(mut, seeds = [b"rewards_vault"], bump)]
+    pub rewards_vault: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(seeds = [b"reward_epoch", epoch.to_le_bytes().as_ref()], bump = reward_epoch.bump)]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+
+    #[account(
+        mut,
+        seeds = [b"user_volume", epoch.to_le_bytes().as_ref(), bettor.key().as_ref()],
+        bump = user_volume.bump
+    )]
+    pub user_volume: Account<'info, UserEpochVolume>,
+
+    #[account(mut, token::mint = rewards.reward_mint, token::authority = bettor)]
+    pub bettor_token_account: Account<'info, anchor_spl::token::TokenAccount>,
+
+    pub bettor: Signer<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitStakingPool<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StakingPool::INIT_SPACE,
+        seeds = [b"staking_pool"],
+        bump
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = stake_mint,
+        token::authority = staking_pool,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(address = config.governance_mint)]
+    pub stake_mint: Account<'info, anchor_spl::token::Mint>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakeTokens<'info> {
+    #[account(mut, seeds = [b"staking_pool"], bump = staking_pool.bump)]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = staker,
+        space = 8 + StakePosition::INIT_SPACE,
+        seeds = [b"stake_position", staker.key().as_ref()],
+        bump
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    #[account(mut, seeds = [b"stake_vault"], bump)]
+    pub stake_vault: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(mut, token::mint = staking_pool.stake_mint, token::authority = staker)]
+    pub staker_token_account: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeTokens<'info> {
+    #[account(mut, seeds = [b"staking_pool"], bump = staking_pool.bump)]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    #[account(mut, seeds = [b"stake_position", staker.key().as_ref()], bump = stake_position.bump)]
+    pub stake_position: Account<'info, StakePosition>,
+
+    #[account(mut, seeds = [b"stake_vault"], bump)]
+    pub stake_vault: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(mut, token::mint = staking_pool.stake_mint, token::authority = staker)]
+    pub staker_token_account: Account<'info, anchor_spl::token::TokenAccount>,
+
+    pub staker: Signer<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+}
+
+#[derive(Accounts)]
+pub struct CloseFeeEpoch<'info> {
+    #[account(mut, seeds = [b"staking_pool"], bump = staking_pool.bump)]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + FeeEpoch::INIT_SPACE,
+        seeds = [b"fee_epoch", staking_pool.current_epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub fee_epoch: Account<'info, FeeEpoch>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFeeShare<'info> {
+    #[account(mut, seeds = [b"staking_pool"], bump = staking_pool.bump)]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    #[account(mut, seeds = [b"stake_position", staker.key().as_ref()], bump = stake_position.bump)]
+    pub stake_position: Account<'info, StakePosition>,
+
+    #[account(mut)]
+    pub staker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitBuyback<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Buyback::INIT_SPACE,
+        seeds = [b"buyback"],
+        bump
+    )]
+    pub buyback: Account<'info, Buyback>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = native_mint,
+        token::authority = buyback,
+        seeds = [b"buyback_wsol"],
+        bump
+    )]
+    pub buyback_wsol: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = platform_mint,
+        token::authority = buyback,
+        seeds = [b"buyback_tokens"],
+        bump
+    )]
+    pub buyback_tokens: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(address = anchor_spl::token::spl_token::native_mint::ID)]
+    pub native_mint: Account<'info, anchor_spl::token::Mint>,
+
+    #[account(address = config.governance_mint)]
+    pub platform_mint: Account<'info, anchor_spl::token::Mint>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteBuyback<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"buyback"], bump = buyback.bump)]
+    pub buyback: Account<'info, Buyback>,
+
+    #[account(mut, seeds = [b"buyback_wsol"], bump)]
+    pub buyback_wsol: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(mut, seeds = [b"buyback_tokens"], bump)]
+    pub buyback_tokens: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(mut, address = buyback.platform_mint)]
+    pub platform_mint: Account<'info, anchor_spl::token::Mint>,
+
+    pub admin: Signer<'info>,
+
+    /// CHECK: Jupiter aggregator program
+    #[account(address = JUPITER_PROGRAM_ID)]
+    pub jupiter_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.pending_admin == new_admin.key() @ PredictDuelError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub new_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitTreasury<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Treasury::INIT_SPACE,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct QueueAction<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + QueuedAction::INIT_SPACE,
+        seeds = [b"queued_action", config.queued_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteQueued<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"queued_action", queued_action.index.to_le_bytes().as_ref()],
+        bump = queued_action.bump
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+
+    /// Required for treasury withdrawals
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Option<Account<'info, Treasury>>,
+
+    /// CHECK: Withdrawal recipient, must match the queued action
+    #[account(mut)]
+    pub recipient: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelQueued<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"queued_action", queued_action.index.to_le_bytes().as_ref()],
+        bump = queued_action.bump
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyHaltMarket<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// Creator's profile, charged with a dispute for the broken market
+    #[account(
+        mut,
+        seeds = [b"user_profile", market.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Option<Box<Account<'info, UserProfile>>>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyRefundAll<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct BlockAddress<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + BlocklistEntry::INIT_SPACE,
+        seeds = [b"blocklist", address.as_ref()],
+        bump
+    )]
+    pub blocklist_entry: Account<'info, BlocklistEntry>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnblockAddress<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"blocklist", blocklist_entry.address.as_ref()],
+        bump = blocklist_entry.bump
+    )]
+    pub blocklist_entry: Account<'info, BlocklistEntry>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAttestationVerifier<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRateLimits<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPersonhoodGate<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeTiers<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCreatorLimits<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCreationBond<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimBond<'info> {
+    #[account(mut, has_one = creator @ PredictDuelError::Unauthorized)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ForfeitBond<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Must be the configured treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FlagMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = reporter,
+        space = 8 + Flags::INIT_SPACE,
+        seeds = [b"flags", market.key().as_ref()],
+        bump
+    )]
+    pub flags: Account<'info, Flags>,
+
+    #[account(
+        init,
+        payer = reporter,
+        space = 8 + FlagRecord::INIT_SPACE,
+        seeds = [b"flag", market.key().as_ref(), reporter.key().as_ref()],
+        bump
+    )]
+    pub flag_record: Account<'info, FlagRecord>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReviewFlags<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = moderator @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut, seeds = [b"flags", market.key().as_ref()], bump = flags.bump)]
+    pub flags: Account<'info, Flags>,
+
+    pub moderator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFlag<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(mut, seeds = [b"flags", market.key().as_ref()], bump = flags.bump)]
+    pub flags: Account<'info, Flags>,
+
+    #[account(
+        mut,
+        close = reporter,
+        seeds = [b"flag", market.key().as_ref(), reporter.key().as_ref()],
+        bump = flag_record.bump
+    )]
+    pub flag_record: Account<'info, FlagRecord>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Must be the configured treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFlagParams<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ModerateMarket<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = moderator @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub moderator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ModeratorVoidMarket<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = moderator @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// Creator's profile, charged with a dispute for the abusive market
+    #[account(
+        mut,
+        seeds = [b"user_profile", market.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Option<Box<Account<'info, UserProfile>>>,
+
+    pub moderator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitFeatured<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Featured::INIT_SPACE,
+        seeds = [b"featured"],
+        bump
+    )]
+    pub featured: Account<'info, Featured>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeaturedMarkets<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"featured"], bump = featured.bump)]
+    pub featured: Account<'info, Featured>,
+
+    /// Admin or moderator
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMarketExtension<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + MarketExtension::INIT_SPACE,
+        seeds = [b"market_extension", market.key().as_ref()],
+        bump
+    )]
+    pub extension: Account<'info, MarketExtension>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetSeries<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundSeries<'info> {
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + SeriesFund::INIT_SPACE,
+        seeds = [b"series_fund", creator.key().as_ref()],
+        bump
+    )]
+    pub series_fund: Account<'info, SeriesFund>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSeriesFund<'info> {
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"series_fund", creator.key().as_ref()],
+        bump = series_fund.bump
+    )]
+    pub series_fund: Account<'info, SeriesFund>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Rollover<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = keeper,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            &(market.market_index + 1).to_le_bytes()
+        ],
+        bump
+    )]
+    pub next_market: Box<Account<'info, Market>>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &(market.market_index + 1).to_le_bytes()
+        ],
+        bump
+    )]
+    pub next_market_vault: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = keeper,
+        space = 8 + OddsHistory::INIT_SPACE,
+        seeds = [b"odds_history", next_market.key().as_ref()],
+        bump
+    )]
+    pub next_odds_history: Box<Account<'info, OddsHistory>>,
+
+    #[account(mut, seeds = [b"user_profile", market.creator.as_ref()], bump = creator_profile.bump)]
+    pub creator_profile: Box<Account<'info, UserProfile>>,
+
+    /// Creator's prepayment for the next market's creation fee and bond
+    #[account(mut, seeds = [b"series_fund", market.creator.as_ref()], bump = series_fund.bump)]
+    pub series_fund: Account<'info, SeriesFund>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Must be the configured treasury; required unless the creation fee is waived
+    #[account(mut, address = config.treasury)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// Creator pass NFT account, waives the creation fee
+    pub creator_pass:
+        Option<Box<InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>>>,
+
+    /// Keeps category stats live; otherwise they catch up via sync_category_stats
+    #[account(mut, seeds = [b"category_stats", (market.category as u8).to_le_bytes().as_ref()], bump = category_stats.bump)]
+    pub category_stats: Option<Box<Account<'info, CategoryStats>>>,
+
+    /// Counts the market towards the creator's stats
+    #[account(mut, seeds = [b"creator_stats", market.creator.as_ref()], bump = creator_stats.bump)]
+    pub creator_stats: Option<Box<Account<'info, CreatorStats>>>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// CHECK: Blocklist entry for the creator; must not exist
+    #[account(
+        seeds = [b"blocklist", market.creator.as_ref()],
+        bump,
+        constraint = blocklist_entry.data_is_empty() @ PredictDuelError::AddressBlocked
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(template_id: u64)]
+pub struct CreateTemplate<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + MarketTemplate::INIT_SPACE,
+        seeds = [b"template", creator.key().as_ref(), &template_id.to_le_bytes()],
+        bump
+    )]
+    pub template: Account<'info, MarketTemplate>,
+
+    /// CHECK: Third-party resolver for the template's markets, stored as a key only
+    pub resolver: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSchedule<'info> {
+    #[account(has_one = creator @ PredictDuelError::NotMarketCreator)]
+    pub template: Account<'info, MarketTemplate>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Schedule::INIT_SPACE,
+        seeds = [b"schedule", template.key().as_ref()],
+        bump
+    )]
+    pub schedule: Account<'info, Schedule>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteSchedule<'info> {
+    #[account(mut, seeds = [b"schedule", template.key().as_ref()], bump = schedule.bump, has_one = template)]
+    pub schedule: Account<'info, Schedule>,
+
+    pub template: Box<Account<'info, MarketTemplate>>,
+
+    #[account(
+        init,
+        payer = keeper,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [
+            b"market",
+            schedule.creator.as_ref(),
+            &schedule.next_market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// CHECK: PDA validated via seeds, funded by the first bet
+    #[account(
+        seeds = [
+            b"market_vault",
+            schedule.creator.as_ref(),
+            &schedule.next_market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = keeper,
+        space = 8 + OddsHistory::INIT_SPACE,
+        seeds = [b"odds_history", market.key().as_ref()],
+        bump
+    )]
+    pub odds_history: Box<Account<'info, OddsHistory>>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [b"user_profile", schedule.creator.as_ref()],
+        bump
+    )]
+    pub creator_profile: Box<Account<'info, UserProfile>>,
+
+    /// CHECK: Blocklist entry for the creator; must not exist
+    #[account(
+        seeds = [b"blocklist", schedule.creator.as_ref()],
+        bump,
+        constraint = blocklist_entry.data_is_empty() @ PredictDuelError::AddressBlocked
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Must be the configured treasury; required unless the creation fee is waived
+    #[account(mut, address = config.treasury)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// Creator pass NFT account, waives the creation fee
+    pub creator_pass:
+        Option<Box<InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>>>,
+
+    /// Keeps category stats live; otherwise they catch up via sync_category_stats
+    #[account(mut, seeds = [b"category_stats", (template.category as u8).to_le_bytes().as_ref()], bump = category_stats.bump)]
+    pub category_stats: Option<Box<Account<'info, CategoryStats>>>,
+
+    /// Counts the market towards the creator's stats
+    #[account(mut, seeds = [b"creator_stats", schedule.creator.as_ref()], bump = creator_stats.bump)]
+    pub creator_stats: Option<Box<Account<'info, CreatorStats>>>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundSchedule<'info> {
+    #[account(mut, has_one = creator @ PredictDuelError::NotMarketCreator)]
+    pub schedule: Account<'info, Schedule>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSchedule<'info> {
+    #[account(mut, close = creator, has_one = creator @ PredictDuelError::NotMarketCreator)]
+    pub schedule: Account<'info, Schedule>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetLstStakePool<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EnableLstYield<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DepositToLst<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
+    /// CHECK: PDA validated via seeds; signs the deposit as the lamport source
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [b"market_lst", market.key().as_ref()],
+        bump,
+        token::mint = pool_mint,
+        token::authority = market_vault
+    )]
+    pub market_lst: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Must be the configured stake pool; validated by the stake pool program
+    #[account(mut, address = config.lst_stake_pool)]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub pool_mint: Box<Account<'info, anchor_spl::token::Mint>>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// CHECK: SPL stake pool program
+    #[account(address = STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnwindLst<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
+    /// CHECK: PDA validated via seeds; owns the pool tokens and receives the SOL
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"market_lst", market.key().as_ref()], bump)]
+    pub market_lst: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Must be the configured treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the configured stake pool; validated by the stake pool program
+    #[account(mut, address = config.lst_stake_pool)]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    #[account(mut, address = market_lst.mint)]
+    pub pool_mint: Box<Account<'info, anchor_spl::token::Mint>>,
+
+    /// CHECK: Clock sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::clock::ID)]
+    pub clock: UncheckedAccount<'info>,
+
+    /// CHECK: Stake history sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: Native stake program
+    #[account(address = STAKE_PROGRAM_ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL stake pool program
+    #[account(address = STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPayoutSplit<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    pub bettor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCharity<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Box<Account<'info, Config>>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCharityBps<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundBounty<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        space = 8 + Bounty::INIT_SPACE,
+        seeds = [b"bounty", market.key().as_ref()],
+        bump
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimBounty<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        close = recipient,
+        seeds = [b"bounty", market.key().as_ref()],
+        bump = bounty.bump
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    /// CHECK: Must be the bounty hunter, or the creator if there is none
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct JoinJurorPool<'info> {
+    #[account(
+        init_if_needed,
+        payer = juror,
+        space = 8 + JurorPool::INIT_SPACE,
+        seeds = [b"juror_pool"],
+        bump
+    )]
+    pub juror_pool: Account<'info, JurorPool>,
+
+    #[account(seeds = [b"stake_position", juror.key().as_ref()], bump = stake_position.bump)]
+    pub stake_position: Account<'info, StakePosition>,
+
+    #[account(mut)]
+    pub juror: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveJurorPool<'info> {
+    #[account(mut, seeds = [b"juror_pool"], bump = juror_pool.bump)]
+    pub juror_pool: Account<'info, JurorPool>,
+
+    pub juror: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = disputer,
+        space = 8 + Dispute::INIT_SPACE,
+        seeds = [b"dispute", market.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(seeds = [b"juror_pool"], bump = juror_pool.bump)]
+    pub juror_pool: Account<'info, JurorPool>,
+
+    /// CHECK: Switchboard randomness account, checked as committed in the previous slot
+    pub randomness: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DrawJury<'info> {
+    #[account(mut, seeds = [b"dispute", dispute.market.as_ref()], bump = dispute.bump)]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(seeds = [b"juror_pool"], bump = juror_pool.bump)]
+    pub juror_pool: Account<'info, JurorPool>,
+
+    /// CHECK: The randomness account bound when the dispute opened
+    #[account(address = dispute.randomness)]
+    pub randomness: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CastJuryVote<'info> {
+    #[account(mut, seeds = [b"dispute", dispute.market.as_ref()], bump = dispute.bump)]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(mut, seeds = [b"stake_position", juror.key().as_ref()], bump = stake_position.bump)]
+    pub stake_position: Account<'info, StakePosition>,
+
+    pub juror: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeDispute<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", market.key().as_ref()],
+        bump = dispute.bump,
+        has_one = disputer @ PredictDuelError::Unauthorized
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(seeds = [b"staking_pool"], bump = staking_pool.bump)]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Must be the configured treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet that opened the dispute, checked against it
+    #[account(mut)]
+    pub disputer: UncheckedAccount<'info>,
+
+    /// Profile of whoever resolved the market, to record the verdict against
+    #[account(
+        mut,
+        seeds = [b"user_profile", market.accountable_resolver().as_ref()],
+        bump = resolver_profile.bump
+    )]
+    pub resolver_profile: Box<Account<'info, UserProfile>>,
+}
+
+#[derive(Accounts)]
+pub struct EnableRaffle<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CommitRaffleDraw<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Switchboard randomness account, checked as committed in the previous slot
+    pub randomness: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DrawRaffle<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Must be the randomness account committed for the draw
+    pub randomness: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EnableSidePot<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimSidePot<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"participant", market.key().as_ref(), winner.key().as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitBoosterFund<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + BoosterFund::INIT_SPACE,
+        seeds = [b"booster_fund"],
+        bump
+    )]
+    pub booster_fund: Account<'info, BoosterFund>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundBoosters<'info> {
+    #[account(mut, seeds = [b"booster_fund"], bump = booster_fund.bump)]
+    pub booster_fund: Account<'info, BoosterFund>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(tier_id: u8)]
+pub struct CreateBoosterTier<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"booster_fund"], bump = booster_fund.bump)]
+    pub booster_fund: Account<'info, BoosterFund>,
+
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = booster_fund,
+        seeds = [b"booster_mint", tier_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub booster_mint: Account<'info, anchor_spl::token::Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + BoosterTier::INIT_SPACE,
+        seeds = [b"booster_tier", booster_mint.key().as_ref()],
+        bump
+    )]
+    pub booster_tier: Account<'info, BoosterTier>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintBooster<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"booster_fund"], bump = booster_fund.bump)]
+    pub booster_fund: Account<'info, BoosterFund>,
+
+    #[account(seeds = [b"booster_tier", booster_mint.key().as_ref()], bump = booster_tier.bump)]
+    pub booster_tier: Account<'info, BoosterTier>,
+
+    #[account(mut)]
+    pub booster_mint: Account<'info, anchor_spl::token::Mint>,
+
+    #[account(mut, token::mint = booster_mint)]
+    pub recipient_token_account: Account<'info, anchor_spl::token::TokenAccount>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyBooster<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    #[account(seeds = [b"booster_tier", booster_mint.key().as_ref()], bump = booster_tier.bump)]
+    pub booster_tier: Account<'info, BoosterTier>,
+
+    #[account(mut)]
+    pub booster_mint: Account<'info, anchor_spl::token::Mint>,
+
+    #[account(mut, token::mint = booster_mint, token::authority = bettor)]
+    pub bettor_token_account: Account<'info, anchor_spl::token::TokenAccount>,
+
+    pub bettor: Signer<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetPointsSchedule<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PointsSchedule::INIT_SPACE,
+        seeds = [b"points_schedule"],
+        bump
+    )]
+    pub points_schedule: Account<'info, PointsSchedule>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AccruePoints<'info> {
+    #[account(seeds = [b"points_schedule"], bump = points_schedule.bump)]
+    pub points_schedule: Account<'info, PointsSchedule>,
+
+    pub market: Account<'info, Market>,
+
+    #[account(mut, has_one = market)]
+    pub participant: Account<'info, Participant>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Points::INIT_SPACE,
+        seeds = [b"points", participant.bettor.as_ref()],
+        bump
+    )]
+    pub points: Account<'info, Points>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemPoints<'info> {
+    #[account(seeds = [b"points_schedule"], bump = points_schedule.bump)]
+    pub points_schedule: Account<'info, PointsSchedule>,
+
+    #[account(mut, seeds = [b"points", user.key().as_ref()], bump = points.bump)]
+    pub points: Account<'info, Points>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(quest_id: u64)]
+pub struct CreateQuest<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Quest::INIT_SPACE,
+        seeds = [b"quest", quest_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub quest: Account<'info, Quest>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordQuestProgress<'info> {
+    #[account(seeds = [b"quest", quest.quest_id.to_le_bytes().as_ref()], bump = quest.bump)]
+    pub quest: Account<'info, Quest>,
+
+    pub market: Account<'info, Market>,
+
+    #[account(has_one = market)]
+    pub participant: Account<'info, Participant>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + QuestProgress::INIT_SPACE,
+        seeds = [b"quest_progress", quest.key().as_ref(), participant.bettor.as_ref()],
+        bump
+    )]
+    pub progress: Account<'info, QuestProgress>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimQuestReward<'info> {
+    #[account(mut, seeds = [b"quest", quest.quest_id.to_le_bytes().as_ref()], bump = quest.bump)]
+    pub quest: Account<'info, Quest>,
+
+    #[account(
+        mut,
+        seeds = [b"quest_progress", quest.key().as_ref(), wallet.key().as_ref()],
+        bump = progress.bump
+    )]
+    pub progress: Account<'info, QuestProgress>,
+
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = 8 + Points::INIT_SPACE,
+        seeds = [b"points", wallet.key().as_ref()],
+        bump
+    )]
+    pub points: Account<'info, Points>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    /// Booster mint authority; required when the quest rewards a booster
+    #[account(seeds = [b"booster_fund"], bump = booster_fund.bump)]
+    pub booster_fund: Option<Account<'info, BoosterFund>>,
+
+    #[account(mut)]
+    pub booster_mint: Option<Account<'info, anchor_spl::token::Mint>>,
+
+    #[account(mut, token::mint = booster_mint, token::authority = wallet)]
+    pub booster_token_account: Option<Account<'info, anchor_spl::token::TokenAccount>>,
+
+    pub token_program: Option<Program<'info, anchor_spl::token::Token>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCheckInReward<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"rewards"], bump = rewards.bump)]
+    pub rewards: Account<'info, Rewards>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CheckIn<'info> {
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Milestone reward accounts; required on milestone days
+    #[account(seeds = [b"rewards"], bump = rewards.bump)]
+    pub rewards: Option<Account<'info, Rewards>>,
+
+    #[account(mut, seeds = [b"rewards_vault"], bump)]
+    pub rewards_vault: Option<Account<'info, anchor_spl::token::TokenAccount>>,
+
+    #[account(mut, token::authority = user)]
+    pub user_token_account: Option<Account<'info, anchor_spl::token::TokenAccount>>,
+
+    pub token_program: Option<Program<'info, anchor_spl::token::Token>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(distributor_id: u64)]
+pub struct CreateDistributor<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + MerkleDistributor::INIT_SPACE,
+        seeds = [b"distributor", distributor_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub distributor: Account<'info, MerkleDistributor>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = mint,
+        token::authority = distributor,
+        seeds = [b"distributor_vault", distributor.key().as_ref()],
+        bump
+    )]
+    pub distributor_vault: Account<'info, anchor_spl::token::TokenAccount>,
+
+    pub mint: Account<'info, anchor_spl::token::Mint>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetDistributorRoot<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"distributor", distributor.distributor_id.to_le_bytes().as_ref()], bump = distributor.bump)]
+    pub distributor: Account<'info, MerkleDistributor>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct ClaimAirdrop<'info> {
+    #[account(mut, seeds = [b"distributor", distributor.distributor_id.to_le_bytes().as_ref()], bump = distributor.bump)]
+    pub distributor: Account<'info, MerkleDistributor>,
+
+    #[account(mut, seeds = [b"distributor_vault", distributor.key().as_ref()], bump)]
+    pub distributor_vault: Account<'info, anchor_spl::token::TokenAccount>,
+
+    /// One per leaf; its existence marks the leaf claimed
+    #[account(
+        init,
+        payer = claimant,
+        space = 8 + AirdropClaim::INIT_SPACE,
+        seeds = [b"airdrop_claim", distributor.key().as_ref(), index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub claim_status: Account<'info, AirdropClaim>,
+
+    #[account(
+        mut,
+        token::mint = distributor.mint,
+        token::authority = claimant
+    )]
+    pub claimant_token_account: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnableMerkleSettlement<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(root: [u8; 32], total_payout: u64, leaf_count: u32)]
+pub struct PostSettlementRoot<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = resolver,
+        space = 8 + Settlement::INIT_SPACE + leaf_count.div_ceil(8) as usize,
+        seeds = [b"settlement", market.key().as_ref()],
+        bump
+    )]
+    pub settlement: Account<'info, Settlement>,
+
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimSettlement<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"settlement", market.key().as_ref()],
+        bump = settlement.bump
+    )]
+    pub settlement: Account<'info, Settlement>,
+
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Winner's profile PDA, read for its co-signer rule; may not exist
+    #[account(seeds = [b"user_profile", winner.key().as_ref()], bump)]
+    pub profile_guard: UncheckedAccount<'info>,
+
+    /// Required on claims above the profile's co-sign threshold
+    pub co_signer: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnableCompressedPositions<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+
+    /// CHECK: Pre-allocated tree account, initialized by the compression program
+    #[account(mut, owner = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: PDA that owns the market's position tree
+    #[account(seeds = [b"position_authority", market.key().as_ref()], bump)]
+    pub position_authority: UncheckedAccount<'info>,
+
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: SPL account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceCompressedBet<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    /// CHECK: PDA vault that holds all stakes
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the market's position tree (checked in the handler)
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: PDA that owns the market's position tree
+    #[account(seeds = [b"position_authority", market.key().as_ref()], bump)]
+    pub position_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Blocklist entry for the bettor; must not exist
+    #[account(
+        seeds = [b"blocklist", bettor.key().as_ref()],
+        bump,
+        constraint = blocklist_entry.data_is_empty() @ PredictDuelError::AddressBlocked
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Instructions sysvar; required while the config demands attestations
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: SPL account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleCompressedPosition<'info> {
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Bettor recorded in the leaf; receives the payout
+    #[account(mut)]
+    pub bettor: UncheckedAccount<'info>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the market's position tree (checked in the handler)
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: PDA that owns the market's position tree
+    #[account(seeds = [b"position_authority", market.key().as_ref()], bump)]
+    pub position_authority: UncheckedAccount<'info>,
+
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: SPL account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleBatch<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    pub keeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPayoutMode<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimInstallment<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), winner.key().as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Winner's profile PDA, read for its co-signer rule; may not exist
+    #[account(seeds = [b"user_profile", winner.key().as_ref()], bump)]
+    pub profile_guard: UncheckedAccount<'info>,
+
+    /// Required on claims above the profile's co-sign threshold
+    pub co_signer: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefundBatch<'info> {
+    pub market: Account<'info, Market>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub keeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MutualCancel<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    pub bettor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VoteVoid<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    pub bettor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(category: MarketCategory)]
+pub struct InitCategoryStats<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CategoryStats::INIT_SPACE,
+        seeds = [b"category_stats", (category as u8).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub category_stats: Account<'info, CategoryStats>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SyncCategoryStats<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut, seeds = [b"category_stats", (market.category as u8).to_le_bytes().as_ref()], bump = category_stats.bump)]
+    pub category_stats: Account<'info, CategoryStats>,
+}
+
+#[derive(Accounts)]
+pub struct ArchiveMarket<'info> {
+    #[account(mut, close = creator, has_one = creator @ PredictDuelError::Unauthorized)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + MarketArchive::INIT_SPACE,
+        seeds = [b"market_archive", market.key().as_ref()],
+        bump
+    )]
+    pub market_archive: Account<'info, MarketArchive>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Must be the configured treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitRecentBets<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RecentBets::INIT_SPACE,
+        seeds = [b"recent_bets", market.key().as_ref()],
+        bump
+    )]
+    pub recent_bets: Account<'info, RecentBets>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitCreatorStats<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CreatorStats::INIT_SPACE,
+        seeds = [b"creator_stats", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_stats: Account<'info, CreatorStats>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordCreatorStats<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut, seeds = [b"creator_stats", market.creator.as_ref()], bump = creator_stats.bump)]
+    pub creator_stats: Account<'info, CreatorStats>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+}
+
+#[derive(Accounts)]
+pub struct SetResolver<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+
+    /// CHECK: Any wallet; vetted through its profile
+    pub resolver: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"user_profile", resolver.key().as_ref()], bump = resolver_profile.bump)]
+    pub resolver_profile: Box<Account<'info, UserProfile>>,
+}
+
+#[derive(Accounts)]
+pub struct BuyInsurance<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// Only the part of the stake not yet covered is insured
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
+        bump,
+        constraint = participant.stake > participant.insured_stake @ PredictDuelError::NothingToClaim
+    )]
+    pub participant: Account<'info, Participant>,
+
+    /// Pays the premium
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimInsurance<'info> {
+    /// Insurance only pays out on a resolution a jury overturned
+    #[account(mut, constraint = market.overturned @ PredictDuelError::MarketNotResolved)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", market.key().as_ref(), bettor.key().as_ref()],
+        bump,
+        constraint = participant.insured_stake > 0 @ PredictDuelError::NothingToClaim,
+        constraint = !participant.insurance_claimed @ PredictDuelError::AlreadyClaimed
+    )]
+    pub participant: Account<'info, Participant>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CollectInsuranceSurplus<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// PDA vault that holds all stakes
+    /// CHECK: PDA validated via seeds, owned by this program (PDA requirement)
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Must be the configured treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LinkMarkets<'info> {
+    #[account(mut)]
+    pub first_market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub second_market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceHedgedBet<'info> {
+    #[account(mut)]
+    pub first_market: Box<Account<'info, Market>>,
+
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = 8 + Participant::INIT_SPACE,
+        seeds = [b"participant", first_market.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub first_participant: Box<Account<'info, Participant>>,
+
+    /// CHECK: PDA vault that holds the first market's stakes
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            first_market.creator.as_ref(),
+            &first_market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub first_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub second_market: Box<Account<'info, Market>>,
+
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = 8 + Participant::INIT_SPACE,
+        seeds = [b"participant", second_market.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub second_participant: Box<Account<'info, Participant>>,
+
+    /// CHECK: PDA vault that holds the second market's stakes
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            second_market.creator.as_ref(),
+            &second_market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub second_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    /// CHECK: Blocklist entry for the bettor; must not exist
+    #[account(
+        seeds = [b"blocklist", bettor.key().as_ref()],
+        bump,
+        constraint = blocklist_entry.data_is_empty() @ PredictDuelError::AddressBlocked
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Instructions sysvar; required while the config demands attestations
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterFrontend<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Frontend::INIT_SPACE,
+        seeds = [b"frontend", owner.key().as_ref()],
+        bump
+    )]
+    pub frontend: Account<'info, Frontend>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AccrueFrontendFees<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut, seeds = [b"frontend", frontend.owner.as_ref()], bump = frontend.bump)]
+    pub frontend: Account<'info, Frontend>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: PDA validated via seeds
+    #[account(
+        mut,
+        seeds = [
+            b"market_vault",
+            market.creator.as_ref(),
+            &market.market_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFrontendFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"frontend", owner.key().as_ref()],
+        bump = frontend.bump,
+        has_one = owner @ PredictDuelError::Unauthorized
+    )]
+    pub frontend: Account<'info, Frontend>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCoSigner<'info> {
+    #[account(mut, seeds = [b"user_profile", user.key().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    pub user: Signer<'info>,
+
+    /// Required while a co-signer is registered
+    pub current_co_signer: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct RequestProfileRecovery<'info> {
+    #[account(mut, seeds = [b"user_profile", user_profile.wallet.as_ref()], bump = user_profile.bump)]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    pub recovery_key: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RotateProfileAuthority<'info> {
+    /// Stays open as a tombstone for what the old wallet still holds
+    #[account(
+        mut,
+        seeds = [b"user_profile", user_profile.wallet.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    #[account(
+        init,
+        payer = new_wallet,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [b"user_profile", new_wallet.key().as_ref()],
+        bump
+    )]
+    pub new_profile: Box<Account<'info, UserProfile>>,
+
+    /// Old wallet's loyalty points, moved along with the profile
+    #[account(
+        mut,
+        close = new_wallet,
+        seeds = [b"points", user_profile.wallet.as_ref()],
+        bump = points.bump
+    )]
+    pub points: Option<Account<'info, Points>>,
+
+    /// Required alongside `points`
+    #[account(
+        init,
+        payer = new_wallet,
+        space = 8 + Points::INIT_SPACE,
+        seeds = [b"points", new_wallet.key().as_ref()],
+        bump
+    )]
+    pub new_points: Option<Account<'info, Points>>,
+
+    /// The profile's wallet, or its recovery key once a recovery has matured
+    pub authority: Signer<'info>,
+
+    /// Required on rotations by the wallet while a co-signer is registered
+    pub current_co_signer: Option<Signer<'info>>,
+
+    #[account(mut)]
+    pub new_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(handle: String)]
+pub struct ClaimHandle<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Handle::INIT_SPACE,
+        seeds = [b"handle", handle.as_bytes()],
+        bump
+    )]
+    pub handle_account: Account<'info, Handle>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + WalletHandle::INIT_SPACE,
+        seeds = [b"wallet_handle", owner.key().as_ref()],
+        bump
+    )]
+    pub wallet_handle: Account<'info, WalletHandle>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferHandle<'info> {
+    #[account(
+        mut,
+        seeds = [b"handle", handle_account.handle.as_bytes()],
+        bump = handle_account.bump,
+        constraint = handle_account.wallet == owner.key() @ PredictDuelError::Unauthorized
+    )]
+    pub handle_account: Account<'info, Handle>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"wallet_handle", owner.key().as_ref()],
+        bump = wallet_handle.bump
+    )]
+    pub wallet_handle: Account<'info, WalletHandle>,
+
+    #[account(
+        init,
+        payer = new_owner,
+        space = 8 + WalletHandle::INIT_SPACE,
+        seeds = [b"wallet_handle", new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_wallet_handle: Account<'info, WalletHandle>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseHandle<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"handle", handle_account.handle.as_bytes()],
+        bump = handle_account.bump,
+        constraint = handle_account.wallet == owner.key() @ PredictDuelError::Unauthorized
+    )]
+    pub handle_account: Account<'info, Handle>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"wallet_handle", owner.key().as_ref()],
+        bump = wallet_handle.bump
+    )]
+    pub wallet_handle: Account<'info, WalletHandle>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetProfileMetadata<'info> {
+    #[account(mut, seeds = [b"user_profile", user.key().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FollowUser<'info> {
+    #[account(
+        init,
+        payer = follower,
+        space = 8 + Follow::INIT_SPACE,
+        seeds = [b"follow", follower.key().as_ref(), followed_profile.wallet.as_ref()],
+        bump
+    )]
+    pub follow: Account<'info, Follow>,
+
+    #[account(mut, seeds = [b"user_profile", follower.key().as_ref()], bump = follower_profile.bump)]
+    pub follower_profile: Box<Account<'info, UserProfile>>,
+
+    #[account(mut, seeds = [b"user_profile", followed_profile.wallet.as_ref()], bump = followed_profile.bump)]
+    pub followed_profile: Box<Account<'info, UserProfile>>,
+
+    #[account(mut)]
+    pub follower: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnfollowUser<'info> {
+    #[account(
+        mut,
+        close = follower,
+        seeds = [b"follow", follower.key().as_ref(), followed_profile.wallet.as_ref()],
+        bump = follow.bump
+    )]
+    pub follow: Account<'info, Follow>,
+
+    #[account(mut, seeds = [b"user_profile", follower.key().as_ref()], bump = follower_profile.bump)]
+    pub follower_profile: Box<Account<'info, UserProfile>>,
+
+    #[account(mut, seeds = [b"user_profile", followed_profile.wallet.as_ref()], bump = followed_profile.bump)]
+    pub followed_profile: Box<Account<'info, UserProfile>>,
+
+    #[account(mut)]
+    pub follower: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SubscribeMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = subscriber,
+        space = 8 + Subscription::INIT_SPACE,
+        seeds = [b"subscription", subscriber.key().as_ref(), market.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubscribeCreator<'info> {
+    /// CHECK: Any wallet; subscribers hear about the markets it creates
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = subscriber,
+        space = 8 + Subscription::INIT_SPACE,
+        seeds = [b"subscription", subscriber.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Unsubscribe<'info> {
+    #[account(
+        mut,
+        close = subscriber,
+        seeds = [b"subscription", subscriber.key().as_ref(), subscription.target.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    /// Subscribed market, while it still exists
+    #[account(mut, address = subscription.target)]
+    pub market: Option<Account<'info, Market>>,
+
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BoostResolution<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"participant", market.key().as_ref(), contributor.key().as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = 8 + ResolutionBoost::INIT_SPACE,
+        seeds = [b"resolution_boost", market.key().as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub resolution_boost: Account<'info, ResolutionBoost>,
+
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleResolutionBoost<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        close = recipient,
+        seeds = [b"resolution_boost", market.key().as_ref(), resolution_boost.contributor.as_ref()],
+        bump = resolution_boost.bump
+    )]
+    pub resolution_boost: Account<'info, ResolutionBoost>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Must be the market's resolver if it earned the boost, else the contributor
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetClaimDelay<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin @ PredictDuelError::Unauthorized)]
+    pub config: Box<Account<'info, Config>>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetDeadlineSlot<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+// State structs
+#[account]
+#[derive(InitSpace)]
+pub struct Market {
+    pub creator: Pubkey,
+    pub market_index: u64,
+    #[max_len(200)]
+    pub question: String,
+    pub category: MarketCategory,
+    pub stake_amount: u64,
+    pub deadline: i64,
+    pub market_type: MarketType,
+    pub status: MarketStatus,
+    pub pool_size: u64,
+    pub yes_count: u32,
+    pub no_count: u32,
+    pub yes_pool: u64,
+    pub no_pool: u64,
+    pub total_participants: u32,
+    pub outcome: Option<bool>,
+    pub created_at: i64,
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub seed_yes: u64,
+    pub seed_no: u64,
+    pub seed_claimed: bool,
+    pub vesting_threshold: u64,
+    pub vesting_duration: i64,
+    pub vesting_mandatory: bool,
+    pub sealed: bool,
+    pub reveal_window: i64,
+    /// Sum of implied YES probability (bps) x seconds since creation
+    pub prob_cumulative: u128,
+    pub twap_last_update: i64,
+    pub early_bonus_bps: u16,
+    /// Payout weight backing each side (equals the side's pool unless early bonus or late discount is on)
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub settlement_mode: SettlementMode,
+    /// Fixed-odds book: decimal odds (bps) quoted for new bets on each side
+    pub yes_odds_bps: u32,
+    pub no_odds_bps: u32,
+    pub book_collateral: u64,
+    /// Total locked payouts owed if the side wins
+    pub yes_liability: u64,
+    pub no_liability: u64,
+    pub book_settled: bool,
+    /// Handicap for Sports spread markets, in tenths of a point
+    pub spread_x10: Option<i32>,
+    /// Event this market resolves from, with the rule mapping its result to YES/NO
+    pub event: Option<Pubkey>,
+    pub event_condition: Option<EventCondition>,
+    /// Registered oracle feed used for automated resolution
+    pub oracle_feed: Option<Pubkey>,
+    /// Structured price-threshold definition; expiry is the market deadline
+    pub price_condition: Option<PriceCondition>,
+    /// Weather reading spec; the reading is taken at the market deadline
+    pub weather_condition: Option<WeatherCondition>,
+    /// Reported game this Sports market resolves from
+    pub game_condition: Option<GameCondition>,
+    /// Admin-curated highlight; winners can mint a trophy NFT
+    pub featured: bool,
+    /// Merkle root of the market's off-chain comments
+    pub discussion_root: [u8; 32],
+    /// Number of comments committed under discussion_root
+    pub discussion_count: u32,
+    pub discussion_updated_at: i64,
+    /// Protocol fee rate in effect when the market was created
+    pub protocol_fee_bps: u16,
+    pub fee_collected: bool,
+    /// Minimum stake per bet, snapshotted from the config at creation
+    pub min_stake: u64,
+    /// When the admin froze the market for an emergency refund (0 = never)
+    pub halted_at: i64,
+    /// Credential mint bettors must hold; gated markets take one bet per person
+    pub personhood_mint: Option<Pubkey>,
+    /// Protocol fee returned to winners at claim time (volume tiers, category fee cuts)
+    pub fee_rebated: u64,
+    /// Third-party resolver; when set, only it can resolve the market
+    pub resolver: Option<Pubkey>,
+    /// Largest pool the market accepts (0 = uncapped)
+    pub max_pool: u64,
+    /// Creator's bond held in the market account until reclaimed or forfeited
+    pub creation_bond: u64,
+    /// Manually resolved after RESOLUTION_GRACE_PERIOD; forfeits the bond
+    pub resolved_late: bool,
+    /// Betting paused by reports pending moderator review
+    pub frozen: bool,
+    /// Hidden from listings by the moderator (also frozen)
+    pub delisted: bool,
+    /// Voided by the moderator as abusive; forfeits the creation bond
+    pub force_voided: bool,
+    /// Category requires oracle settlement; manual resolution is disabled
+    pub oracle_required: bool,
+    /// Period between markets in a rolling series (0 = not a series)
+    pub series_interval: i64,
+    /// On rollover, move the creator's unclaimed seed into the next market
+    pub series_migrate_seed: bool,
+    pub rolled_over: bool,
+    /// Creator opted in to staking idle escrow through config.lst_stake_pool
+    pub lst_enabled: bool,
+    /// Yield goes to the winners' pool rather than the treasury
+    pub lst_yield_to_winners: bool,
+    /// Lamports currently deposited in the stake pool
+    pub lst_deposited: u64,
+    /// Pool tokens held for the market
+    pub lst_tokens: u64,
+    /// Caller whose oracle resolution earned the information bounty
+    pub bounty_hunter: Option<Pubkey>,
+    /// Resolution is being challenged before a jury; claims wait for the verdict
+    pub disputed: bool,
+    /// Raffle mode: correct predictors drawn to split the whole pot
+    pub raffle_winner_count: u8,
+    /// Raffle tickets issued per side, one per bettor
+    pub yes_tickets: u32,
+    pub no_tickets: u32,
+    /// Switchboard randomness account committed for the raffle or side-pot draw, and when
+    pub raffle_randomness: Option<Pubkey>,
+    pub raffle_committed_slot: u64,
+    /// Winning-side tickets drawn (empty until the draw); a side pot draws one
+    #[max_len(MAX_RAFFLE_WINNERS)]
+    pub raffle_winners: Vec<u32>,
+    /// Share of the pool diverted into a side pot for one drawn winner (0 = off)
+    pub side_pot_bps: u16,
+    pub side_pot_claimed: bool,
+    /// Winners claim against a posted payout root instead of their positions
+    pub merkle_settlement: bool,
+    /// Concurrent merkle tree holding the market's positions instead of participant PDAs
+    pub position_tree: Option<Pubkey>,
+    /// Position leaves appended so far (the next leaf's index)
+    pub position_leaves: u32,
+    /// Whether winners claim or a keeper pays them with settle_batch
+    pub payout_mode: PayoutMode,
+    /// Each side of a duel has agreed to call it off
+    pub yes_cancel_approved: bool,
+    pub no_cancel_approved: bool,
+    /// Bettor stake that has voted to void the market as abandoned
+    pub void_votes: u64,
+    /// When the outcome was recorded (0 = unresolved)
+    pub resolved_at: i64,
+    /// When the last stake entered the market (0 = no bets)
+    pub last_bet_at: i64,
+    /// When payouts became final: resolution, void or cancellation, or a dispute verdict
+    pub settled_at: i64,
+    /// Pool volume already added to the category stats, and whether the market has been
+    /// counted there as listed and as closed
+    pub stats_volume: u64,
+    pub stats_listed: bool,
+    pub stats_closed: bool,
+    /// Length of the closing auction before the deadline (0 = none)
+    pub auction_window: i64,
+    /// Closing-auction stakes, held out of the pools until the auction clears
+    pub auction_yes: u64,
+    pub auction_no: u64,
+    /// Seconds before the deadline that betting closes (0 = at the deadline)
+    pub betting_cutoff_secs: i64,
+    /// Bets in this final share of the lifetime have their weight cut by late_discount_bps
+    pub late_window_bps: u16,
+    pub late_discount_bps: u16,
+    /// Implied YES probability of the current pools, in basis points; kept in step with
+    /// every pool change so readers don't have to recompute it
+    pub implied_yes_prob_bps: u16,
+    /// Resolution was ever challenged through open_dispute
+    pub was_disputed: bool,
+    /// Outcome has been folded into the creator's CreatorStats
+    pub creator_stats_recorded: bool,
+    /// Insurance premiums held in the vault, and the insured stake on each side
+    pub insurance_pool: u64,
+    pub insured_yes: u64,
+    pub insured_no: u64,
+    /// A jury reversed the original resolution
+    pub overturned: bool,
+    /// Correlated market of the same creator that hedged bets pair this one with
+    pub linked_market: Option<Pubkey>,
+    /// Wallets subscribed to notifications about this market
+    pub subscribers: u32,
+    /// Resolution boosts pledged by participants; keepers resolve the highest first
+    pub resolution_boost: u64,
+    /// Wallet that resolved the market by hand or triggered its oracle resolution
+    pub resolved_by: Option<Pubkey>,
+    /// Config's claim delay when the market was created
+    pub claim_delay: i64,
+    /// Slot at which betting closes and, on unsealed markets, resolution opens, whichever
+    /// of it and the unix deadline comes first (0 = unix deadline only)
+    pub deadline_slot: u64,
+    /// Slot the market was created in, alongside created_at
+    pub created_slot: u64,
+    /// Winnings settled into vesting schedules and not yet released
+    pub vesting_outstanding: u64,
+    /// Lamports held by the market's Boost and Bounty accounts, which are paid out against
+    /// this account, so it can't be archived until they are
+    pub boost_outstanding: u64,
+    pub bounty_outstanding: u64,
+    /// Insurance paid out after an overturned resolution
+    pub insurance_paid: u64,
+    /// Cancelled by its creator after bets came in; forfeits the creation bond
+    pub creator_cancelled: bool,
+}
+
+impl Market {
+    /// Reset every lifecycle and feature field to its default for a freshly created market
+    pub fn init_state(&mut self, now: i64) {
+        self.status = MarketStatus::Pending;
+        self.pool_size = 0;
+        self.yes_count = 0;
+        self.no_count = 0;
+        self.yes_pool = 0;
+        self.no_pool = 0;
+        self.total_participants = 0;
+        self.outcome = None;
+        self.seed_yes = 0;
+        self.seed_no = 0;
+        self.seed_claimed = false;
+        self.vesting_threshold = DEFAULT_VESTING_THRESHOLD;
+        self.vesting_duration = DEFAULT_VESTING_DURATION;
+        self.vesting_mandatory = false;
+        self.sealed = false;
+        self.reveal_window = 0;
+        self.prob_cumulative = 0;
+        self.twap_last_update = now;
+        self.early_bonus_bps = 0;
+        self.yes_weight = 0;
+        self.no_weight = 0;
+        self.settlement_mode = SettlementMode::Parimutuel;
+        self.yes_odds_bps = 0;
+        self.no_odds_bps = 0;
+        self.book_collateral = 0;
+        self.yes_liability = 0;
+        self.no_liability = 0;
+        self.book_settled = false;
+        self.spread_x10 = None;
+        self.event = None;
+        self.event_condition = None;
+        self.oracle_feed = None;
+        self.price_condition = None;
+        self.weather_condition = None;
+        self.game_condition = None;
+        self.featured = false;
+        self.discussion_root = [0; 32];
+        self.discussion_count = 0;
+        self.discussion_updated_at = 0;
+        self.protocol_fee_bps = 0;
+        self.fee_collected = false;
+        self.min_stake = DEFAULT_MIN_STAKE;
+        self.halted_at = 0;
+        self.personhood_mint = None;
+        self.fee_rebated = 0;
+        self.resolver = None;
+        self.max_pool = 0;
+        self.creation_bond = 0;
+        self.resolved_late = false;
+        self.frozen = false;
+        self.delisted = false;
+        self.force_voided = false;
+        self.oracle_required = false;
+        self.series_interval = 0;
+        self.series_migrate_seed = false;
+        self.rolled_over = false;
+        self.lst_enabled = false;
+        self.lst_yield_to_winners = false;
+        self.lst_deposited = 0;
+        self.lst_tokens = 0;
+        self.bounty_hunter = None;
+        self.disputed = false;
+        self.raffle_winner_count = 0;
+        self.yes_tickets = 0;
+        self.no_tickets = 0;
+        self.raffle_randomness = None;
+        self.raffle_committed_slot = 0;
+        self.raffle_winners = Vec::new();
+        self.side_pot_bps = 0;
+        self.side_pot_claimed = false;
+        self.merkle_settlement = false;
+        self.position_tree = None;
+        self.position_leaves = 0;
+        self.payout_mode = PayoutMode::Pull;
+        self.yes_cancel_approved = false;
+        self.no_cancel_approved = false;
+        self.void_votes = 0;
+        self.resolved_at = 0;
+        self.last_bet_at = 0;
+        self.settled_at = 0;
+        self.stats_volume = 0;
+        self.stats_listed = false;
+        self.stats_closed = false;
+        self.auction_window = 0;
+        self.auction_yes = 0;
+        self.auction_no = 0;
+        self.betting_cutoff_secs = 0;
+        self.late_window_bps = 0;
+        self.late_discount_bps = 0;
+        self.implied_yes_prob_bps = (BPS_DENOMINATOR / 2) as u16;
+        self.was_disputed = false;
+        self.creator_stats_recorded = false;
+        self.insurance_pool = 0;
+        self.insured_yes = 0;
+        self.insured_no = 0;
+        self.overturned = false;
+        self.linked_market = None;
+        self.subscribers = 0;
+        self.resolution_boost = 0;
+        self.resolved_by = None;
+        self.claim_delay = 0;
+        self.deadline_slot = 0;
+        self.vesting_outstanding = 0;
+        self.boost_outstanding = 0;
+        self.bounty_outstanding = 0;
+        self.insurance_paid = 0;
+        self.creator_cancelled = false;
+    }
+
+    /// Snapshot the config's parameters for this market's category and check the
+    /// creator's stake and deadline against them
+    pub fn apply_category_params(&mut self, config: &Config, now: i64) -> Result<()> {
+        let params = config.category_params(self.category);
+
+        self.min_stake = if params.min_stake > 0 {
+            params.min_stake
+        } else {
+            config.min_stake
+        };
+        // Upper bound on the fee; winners may pay less at claim time
+        self.protocol_fee_bps = config.category_fee_bps(self.category);
+        self.oracle_required = params.oracle_required;
+        self.claim_delay = config.claim_delay;
+
+        require!(
+            self.stake_amount >= self.min_stake,
+            PredictDuelError::StakeTooLow
+        );
+        require!(
+            params.max_duration == 0 || self.deadline - now <= params.max_duration,
+            PredictDuelError::InvalidDeadline
+        );
+        Ok(())
+    }
+
+    /// Low-reputation creators get a capped pool and can't resolve their own market;
+    /// oracle markets settle from their feed, so only the cap applies there
+    pub fn apply_creator_limits(
+        &mut self,
+        config: &Config,
+        creator_profile: &UserProfile,
+    ) -> Result<()> {
+        if creator_profile.creator_score_bps() < config.min_creator_score_bps {
+            require!(
+                self.oracle_feed.is_some()
+                    || self
+                        .resolver
+                        .is_some_and(|resolver| resolver != self.creator),
+                PredictDuelError::ResolverRequired
+            );
+            self.max_pool = config.low_score_max_pool;
+        }
+        Ok(())
+    }
+
+    /// Seed counts towards the pools like any other stake, but not towards bettor counts
+    pub fn add_seed(&mut self, yes_amount: u64, no_amount: u64, now: i64) {
+        self.accrue_twap(now);
+        self.seed_yes = yes_amount;
+        self.seed_no = no_amount;
+        self.yes_pool += yes_amount;
+        self.no_pool += no_amount;
+        self.pool_size += yes_amount + no_amount;
+        // House seed never earns the early-entry bonus
+        self.yes_weight += yes_amount;
+        self.no_weight += no_amount;
+        self.sync_implied_prob();
+    }
+
+    /// What the creator's seed returns once the market has settled. A winning seed waits
+    /// for the claim lock like any other winnings.
+    pub fn seed_payout(&self, now: i64) -> Result<u64> {
+        require!(self.lst_tokens == 0, PredictDuelError::LstNotUnwound);
+        match self.status {
+            MarketStatus::Resolved => {
+                self.assert_claims_open(now)?;
+                let outcome = self.outcome.ok_or(PredictDuelError::NoOutcome)?;
+                let (seed_stake, winning_weight) = if outcome {
+                    (self.seed_yes, self.yes_weight)
+                } else {
+                    (self.seed_no, self.no_weight)
+                };
+                // The losing-side seed stays in the pool for winners, like any losing stake
+                self.calculate_payout(seed_stake, winning_weight)
+            }
+            MarketStatus::Cancelled | MarketStatus::Voided => Ok(self
+                .seed_yes
+                .checked_add(self.seed_no)
+                .ok_or(PredictDuelError::MathOverflow)?),
+            _ => err!(PredictDuelError::MarketNotResolved),
+        }
+    }
+
+    /// What a fixed-odds book returns to its creator: the collateral plus stakes left after
+    /// the winning side's liability, held behind the claim lock since a verdict can flip it
+    pub fn book_surplus(&self, now: i64) -> Result<u64> {
+        require!(self.lst_tokens == 0, PredictDuelError::LstNotUnwound);
+        match self.status {
+            MarketStatus::Resolved => {
+                self.assert_claims_open(now)?;
+                let outcome = self.outcome.ok_or(PredictDuelError::NoOutcome)?;
+                let liability = if outcome {
+                    self.yes_liability
+                } else {
+                    self.no_liability
+                };
+                Ok(self
+                    .book_collateral
+                    .checked_add(self.pool_size)
+                    .and_then(|total| total.checked_sub(liability))
+                    .ok_or(PredictDuelError::MathOverflow)?)
+            }
+            // Stakes go back to bettors through refund_stake
+            MarketStatus::Cancelled | MarketStatus::Voided => Ok(self.book_collateral),
+            _ => err!(PredictDuelError::MarketNotResolved),
+        }
+    }
+
+    /// Credit a hand resolution to the third-party resolver, or to the creator resolving
+    /// their own market
+    pub fn credit_resolution(
+        &self,
+        creator_profile: &mut UserProfile,
+        resolver_profile: Option<&mut UserProfile>,
+    ) -> Result<()> {
+        if self
+            .resolver
+            .is_some_and(|resolver| resolver != self.creator)
+        {
+            resolver_profile
+                .ok_or(PredictDuelError::ResolverProfileRequired)?
+                .resolutions += 1;
+        } else {
+            creator_profile.resolutions += 1;
+        }
+        Ok(())
+    }
+
+    /// Wallet whose resolver record a dispute verdict counts against: whoever resolved the
+    /// market by hand, or the creator who bound its oracle (hunters only relay the feed)
+    pub fn accountable_resolver(&self) -> Pubkey {
+        match self.resolved_by {
+            Some(resolver) if !self.is_oracle_resolved() => resolver,
+            _ => self.creator,
+        }
+    }
+
+    /// Market outcome comes from a bound oracle condition rather than the creator
+    pub fn is_oracle_resolved(&self) -> bool {
+        self.price_condition.is_some()
+            || self.weather_condition.is_some()
+            || self.game_condition.is_some()
+    }
+
+    /// Oracle reading that settles this market, or None once the resolution window has lapsed
+    /// without one
+    pub fn settlement_reading(
+        &self,
+        provider: OracleProvider,
+        max_age: i64,
+        feed: &AccountInfo,
+        now: i64,
+    ) -> Result<Option<OraclePrice>> {
+        require!(
+            self.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        require!(now >= self.deadline, PredictDuelError::MarketNotExpired);
+
+        let settles_by = self.deadline.saturating_add(max_age);
+        if now > settles_by.saturating_add(PRICE_RESOLUTION_WINDOW) {
+            return Ok(None);
+        }
+
+        let price = read_oracle_price(provider, feed)?;
+        // An update at or after expiry, no later than the feed's heartbeat allows
+        require!(
+            price.publish_time >= self.deadline,
+            PredictDuelError::StalePrice
+        );
+        require!(
+            price.publish_time <= settles_by,
+            PredictDuelError::PriceTooLate
+        );
+        Ok(Some(price))
+    }
+
+    /// Checks shared by every open (non-sealed) bet entry path
+    pub fn assert_open_bet(
+        &self,
+        participant: &Participant,
+        prediction: bool,
+        stake_amount: u64,
+        now: i64,
+    ) -> Result<()> {
+        // Compressed markets only take bets through place_compressed_bet
+        require!(
+            self.position_tree.is_none(),
+            PredictDuelError::CompressedMarket
+        );
+        self.assert_accepting_stake(stake_amount, now)?;
+        // A position tracks a single side; top-ups must back the same outcome
+        require!(
+            participant.market == Pubkey::default() || participant.prediction == prediction,
+            PredictDuelError::SideMismatch
+        );
+        // A receipt encodes the stake it was minted for
+        require!(
+            participant.receipt_nonce.is_none(),
+            PredictDuelError::ReceiptHeld
+        );
+        // Winner-takes-all duels allow exactly one bettor per side
+        if self.settlement_mode == SettlementMode::WinnerTakesAll
+            && participant.market == Pubkey::default()
+        {
+            let side_pool = if prediction {
+                self.yes_pool
+            } else {
+                self.no_pool
+            };
+            require!(side_pool == 0, PredictDuelError::DuelSideTaken);
+        }
+        Ok(())
+    }
+
+    /// Boosts buy discovery, so they close with betting and stay shut while it is frozen
+    pub fn assert_boostable(&self, clock: &Clock) -> Result<()> {
+        require!(
+            self.status == MarketStatus::Pending || self.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        require!(self.betting_open(clock), PredictDuelError::MarketExpired);
+        require!(!self.frozen, PredictDuelError::MarketFrozen);
+        Ok(())
+    }
+
+    /// Market-level checks for a new stake, whatever holds the position
+    pub fn assert_accepting_stake(&self, stake_amount: u64, now: i64) -> Result<()> {
+        self.assert_open_for(stake_amount, now)?;
+        // Sealed markets only accept commitments via commit_bet
+        require!(!self.sealed, PredictDuelError::SealedMarket);
+        Ok(())
+    }
+
+    /// Market-level checks for a sealed commitment; the stake is public, so it is held to
+    /// the same limits as an open one
+    pub fn assert_accepting_commitment(&self, stake_amount: u64, now: i64) -> Result<()> {
+        require!(self.sealed, PredictDuelError::NotSealedMarket);
+        self.assert_open_for(stake_amount, now)
+    }
+
+    /// Checks shared by open and sealed stakes
+    fn assert_open_for(&self, stake_amount: u64, now: i64) -> Result<()> {
+        // Validate market is open for betting
+        require!(
+            self.status == MarketStatus::Pending || self.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        require!(
+            now < self.betting_closes_at()
+                && (self.deadline_slot == 0 || Clock::get()?.slot < self.deadline_slot),
+            PredictDuelError::MarketExpired
+        );
+        require!(
+            stake_amount >= self.min_stake,
+            PredictDuelError::StakeTooLow
+        );
+        require!(!self.frozen, PredictDuelError::MarketFrozen);
+        // Oracle-only categories open once an oracle condition is bound
+        require!(
+            !self.oracle_required || self.is_oracle_resolved(),
+            PredictDuelError::OracleRequired
+        );
+        require!(
+            self.max_pool == 0 || self.pool_size.saturating_add(stake_amount) <= self.max_pool,
+            PredictDuelError::PoolCapExceeded
+        );
+        Ok(())
+    }
+
+    /// Credit a stake that has already reached the vault to a participant's position
+    /// yes_count and no_count count positions, so top-ups leave them alone and a cash-out
+    /// takes one off
+    fn count_position(&mut self, is_new: bool, prediction: bool) {
+        if !is_new {
+            return;
+        }
+        if prediction {
+            self.yes_count += 1;
+        } else {
+            self.no_count += 1;
+        }
+    }
+
+    pub fn credit_bet(
+        &mut self,
+        participant: &mut Participant,
+        is_new: bool,
+        prediction: bool,
+        stake_amount: u64,
+        now: i64,
+    ) -> Result<()> {
+        participant.stake += stake_amount;
+        participant.record_entry(now, stake_amount, self.implied_yes_prob_bps);
+        if is_new {
+            self.total_participants += 1;
+            if self.issues_tickets() {
+                let tickets = if prediction {
+                    &mut self.yes_tickets
+                } else {
+                    &mut self.no_tickets
+                };
+                participant.raffle_ticket = *tickets;
+                *tickets += 1;
+            }
+        }
+
+        // Closing-auction entries all clear at the deadline's weight and stay out of
+        // the pools, so late flow can't move the odds, until the auction clears. They lock
+        // no quote, so only a parimutuel pool can pay them.
+        if self.in_closing_auction(now) {
+            require!(
+                self.settlement_mode == SettlementMode::Parimutuel,
+                PredictDuelError::InvalidSettlementMode
+            );
+            participant.weight += self.entry_weight(stake_amount, self.deadline)?;
+            participant.last_entry_at = now;
+            self.pool_size += stake_amount;
+            self.last_bet_at = now;
+            self.count_position(is_new, prediction);
+            if prediction {
+                self.auction_yes += stake_amount;
+            } else {
+                self.auction_no += stake_amount;
+            }
+            if self.status == MarketStatus::Pending {
+                self.status = MarketStatus::Active;
+            }
+            return Ok(());
+        }
+
+        // Each entry's payout weight is fixed at the time it is placed
+        let weight = self.entry_weight(stake_amount, now)?;
+        participant.weight += weight;
+        participant.last_entry_at = now;
+
+        // Fixed-odds bets lock their payout now, bounded by what the book can cover
+        if self.settlement_mode == SettlementMode::FixedOdds {
+            let locked = self.fixed_odds_payout(stake_amount, prediction)?;
+            participant.locked_payout += locked;
+            let liability = if prediction {
+                self.yes_liability += locked;
+                self.yes_liability
+            } else {
+                self.no_liability += locked;
+                self.no_liability
+            };
+            require!(
+                liability <= self.book_collateral + self.pool_size + stake_amount,
+                PredictDuelError::BookExposureExceeded
+            );
+        }
+
+        // Update market stats
+        self.accrue_twap(now);
+        self.pool_size += stake_amount;
+        self.last_bet_at = now;
+        self.count_position(is_new, prediction);
+        if prediction {
+            self.yes_pool += stake_amount;
+            self.yes_weight += weight;
+        } else {
+            self.no_pool += stake_amount;
+            self.no_weight += weight;
+        }
+        self.sync_implied_prob();
+
+        // Activate market if it was pending
+        if self.status == MarketStatus::Pending {
+            self.status = MarketStatus::Active;
+        }
+        Ok(())
+    }
+
+    /// Bets close `betting_cutoff_secs` before the deadline; resolution still waits for it
+    pub fn betting_closes_at(&self) -> i64 {
+        self.deadline - self.betting_cutoff_secs
+    }
+
+    /// Before both the betting cutoff and the slot deadline, if any
+    pub fn betting_open(&self, clock: &Clock) -> bool {
+        clock.unix_timestamp < self.betting_closes_at()
+            && (self.deadline_slot == 0 || clock.slot < self.deadline_slot)
+    }
+
+    /// Past the unix deadline (and reveal window), or the slot deadline on unsealed markets.
+    /// The unix deadline also needs most of its expected slots to have passed.
+    pub fn deadline_reached(&self, clock: &Clock) -> bool {
+        (clock.unix_timestamp >= self.resolvable_at() && clock.slot >= self.min_deadline_slot())
+            || (self.deadline_slot > 0 && !self.sealed && clock.slot >= self.deadline_slot)
+    }
+
+    /// Earliest slot the unix deadline can be trusted at: MIN_SLOT_PROGRESS_BPS of the
+    /// slots expected between creation and resolvable_at (0 for markets created unslotted)
+    pub fn min_deadline_slot(&self) -> u64 {
+        if self.created_slot == 0 {
+            return 0;
+        }
+        let lifetime_ms =
+            self.resolvable_at().saturating_sub(self.created_at).max(0) as u64 * 1_000;
+        let expected = lifetime_ms / SLOT_DURATION_MS;
+        self.created_slot + expected * MIN_SLOT_PROGRESS_BPS / BPS_DENOMINATOR
+    }
+
+    pub fn in_closing_auction(&self, now: i64) -> bool {
+        let closes_at = self.betting_closes_at();
+        self.auction_window > 0 && now >= closes_at - self.auction_window && now < closes_at
+    }
+
+    /// Fold the closing auction's stakes into the pools at one uniform weight.
+    /// Must run before anything reads the final pools.
+    pub fn clear_closing_auction(&mut self, now: i64) -> Result<()> {
+        if self.auction_yes == 0 && self.auction_no == 0 {
+            return Ok(());
+        }
+        self.accrue_twap(now);
+        self.yes_pool += self.auction_yes;
+        self.yes_weight += self.entry_weight(self.auction_yes, self.deadline)?;
+        self.no_pool += self.auction_no;
+        self.no_weight += self.entry_weight(self.auction_no, self.deadline)?;
+        self.auction_yes = 0;
+        self.auction_no = 0;
+        self.sync_implied_prob();
+        Ok(())
+    }
+
+    /// Creator-only settings can change until the first bet lands
+    pub fn assert_configurable(&self, signer: &Pubkey) -> Result<()> {
+        require!(*signer == self.creator, PredictDuelError::NotMarketCreator);
+        require!(
+            self.status == MarketStatus::Pending && self.total_participants == 0,
+            PredictDuelError::MarketNotActive
+        );
+        Ok(())
+    }
+
+    /// On personhood-gated markets the bettor must hold the credential, and may only bet once
+    pub fn assert_personhood(
+        &self,
+        bettor: &Pubkey,
+        participant: &Participant,
+        credential: Option<&anchor_spl::token_interface::TokenAccount>,
+    ) -> Result<()> {
+        let Some(mint) = self.personhood_mint else {
+            return Ok(());
+        };
+        let credential = credential.ok_or(PredictDuelError::PersonhoodRequired)?;
+        require!(
+            credential.mint == mint && credential.owner == *bettor && credential.amount > 0,
+            PredictDuelError::PersonhoodRequired
+        );
+        require!(participant.stake == 0, PredictDuelError::AlreadyBet);
+        Ok(())
+    }
+
+    /// Resolver may settle: must be the designated resolver (or the creator if none),
+    /// on an active market past its resolution time
+    pub fn assert_resolvable(&self, resolver: &Pubkey) -> Result<()> {
+        let clock = Clock::get()?;
+
+        // Only creator or designated resolver can resolve
+        require!(
+            *resolver == self.resolver.unwrap_or(self.creator),
+            PredictDuelError::UnauthorizedResolver
+        );
+        require!(!self.oracle_required, PredictDuelError::OracleRequired);
+
+        // Market must be active and past deadline
+        require!(
+            self.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        require!(
+            self.deadline_reached(&clock),
+            PredictDuelError::MarketNotExpired
+        );
+        Ok(())
+    }
+
+    /// Outcome may be set by hand: spread, event and oracle markets derive theirs from
+    /// reported results
+    pub fn assert_manual_outcome(&self) -> Result<()> {
+        require!(self.spread_x10.is_none(), PredictDuelError::SpreadMarket);
+        require!(self.event.is_none(), PredictDuelError::EventMarket);
+        require!(!self.is_oracle_resolved(), PredictDuelError::OracleMarket);
+        Ok(())
+    }
+
+    /// Settle a spread market from the final score: YES if home covers the spread, voided
+    /// for refunds if it lands exactly on the line. Either way `resolver` resolved it.
+    pub fn settle_spread(
+        &mut self,
+        home_score: u32,
+        away_score: u32,
+        resolver: Pubkey,
+        now: i64,
+    ) -> Result<Option<bool>> {
+        let spread_x10 = self.spread_x10.ok_or(PredictDuelError::NotSpreadMarket)?;
+        let margin_x10 = (home_score as i64 - away_score as i64) * 10 + spread_x10 as i64;
+        let outcome = (margin_x10 != 0).then_some(margin_x10 > 0);
+
+        match outcome {
+            Some(_) => {
+                self.status = MarketStatus::Resolved;
+                self.resolved_at = now;
+            }
+            None => self.status = MarketStatus::Voided,
+        }
+        self.settled_at = now;
+        self.outcome = outcome;
+        self.resolved_by = Some(resolver);
+        Ok(outcome)
+    }
+
+    /// Settle a winner-takes-all duel as a draw, voided for refunds. `resolver` still
+    /// resolved it, the same as a spread that lands on the line.
+    pub fn push_duel(&mut self, resolver: Pubkey, now: i64) -> Result<()> {
+        require!(
+            self.settlement_mode == SettlementMode::WinnerTakesAll,
+            PredictDuelError::InvalidSettlementMode
+        );
+        // A push is still an outcome, so reported-result markets can't be pushed by hand
+        self.assert_manual_outcome()?;
+        self.status = MarketStatus::Voided;
+        self.settled_at = now;
+        self.resolved_by = Some(resolver);
+        Ok(())
+    }
+
+    /// Creation bond is lost if the market was force-halted, force-voided, cancelled by its
+    /// creator with bets in, resolved late or voided by its bettors as abandoned
+    pub fn bond_forfeited(&self) -> bool {
+        self.halted_at != 0
+            || self.force_voided
+            || self.creator_cancelled
+            || self.resolved_late
+            || self.void_vote_passed()
+    }
+
+    /// Call off a market that already has bets before its deadline. Every stake becomes
+    /// refundable and the creator gives up the bond, so cancelling a market that is going
+    /// the wrong way costs more than letting it resolve.
+    pub fn cancel_with_refunds(&mut self, now: i64) -> Result<()> {
+        require!(
+            self.status == MarketStatus::Pending || self.status == MarketStatus::Active,
+            PredictDuelError::CannotCancel
+        );
+        require!(now < self.deadline, PredictDuelError::MarketExpired);
+
+        self.status = MarketStatus::Cancelled;
+        self.settled_at = now;
+        self.creator_cancelled = true;
+        Ok(())
+    }
+
+    /// Bring the category stats up to date with this market; each step counts only once
+    pub fn sync_category_stats(&mut self, stats: &mut CategoryStats) {
+        if !self.stats_listed {
+            stats.total_markets += 1;
+            stats.open_markets += 1;
+            self.stats_listed = true;
+        }
+        // Cash-outs shrink the pool; volume only counts stake that entered
+        stats.total_volume += self.pool_size.saturating_sub(self.stats_volume);
+        self.stats_volume = self.stats_volume.max(self.pool_size);
+        let closed = matches!(
+            self.status,
+            MarketStatus::Resolved | MarketStatus::Voided | MarketStatus::Cancelled
+        );
+        if closed && !self.stats_closed {
+            stats.open_markets -= 1;
+            stats.closed_markets += 1;
+            stats.closed_pool_total += self.pool_size;
+            self.stats_closed = true;
+        }
+    }
+
+    /// Premiums owed to insured bettors on the losing side of an overturned resolution;
+    /// if they don't cover every insured stake, each is paid pro rata
+    pub fn insurance_reserve(&self) -> u64 {
+        match (self.overturned, self.outcome) {
+            (true, Some(outcome)) => {
+                let insured_losers = if outcome {
+                    self.insured_no
+                } else {
+                    self.insured_yes
+                };
+                self.insurance_pool.min(insured_losers)
+            }
+            _ => 0,
+        }
+    }
+
+    /// Winnings can be paid: no dispute is open and the claim delay, which outlasts the
+    /// window for opening one, has passed since resolution
+    pub fn assert_claims_open(&self, now: i64) -> Result<()> {
+        require!(!self.disputed, PredictDuelError::DisputeOpen);
+        require!(now >= self.claims_open_at(), PredictDuelError::ClaimLocked);
+        Ok(())
+    }
+
+    pub fn claims_open_at(&self) -> i64 {
+        self.resolved_at.saturating_add(self.claim_delay)
+    }
+
+    /// Insurance still owed to insured bettors after an overturned resolution
+    pub fn insurance_outstanding(&self) -> u64 {
+        self.insurance_reserve().saturating_sub(self.insurance_paid)
+    }
+
+    /// Insurance paid on an insured stake that lost to an overturned resolution
+    pub fn insurance_payout(&self, insured_stake: u64, prediction: bool) -> u64 {
+        let insured_losers = if prediction {
+            self.insured_yes
+        } else {
+            self.insured_no
+        };
+        (insured_stake as u128 * self.insurance_reserve() as u128)
+            .checked_div(insured_losers as u128)
+            .unwrap_or(0) as u64
+    }
+
+    /// Stake placed by bettors, excluding the creator's seed
+    pub fn bettor_stake(&self) -> u64 {
+        (self.yes_pool + self.no_pool + self.auction_yes + self.auction_no)
+            .saturating_sub(self.seed_yes + self.seed_no)
+    }
+
+    /// A supermajority of bettor stake has voted the market void
+    pub fn void_vote_passed(&self) -> bool {
+        self.void_votes > 0
+            && self.void_votes as u128 * BPS_DENOMINATOR as u128
+                >= self.bettor_stake() as u128 * VOID_SUPERMAJORITY_BPS as u128
+    }
+
+    /// Stakes can be refunded once the market is cancelled or voided
+    pub fn is_refundable(&self) -> bool {
+        self.status == MarketStatus::Cancelled || self.status == MarketStatus::Voided
+    }
+
+    /// Earliest time the market can be resolved (after the reveal window on sealed markets)
+    pub fn resolvable_at(&self) -> i64 {
+        if self.sealed {
+            self.deadline.saturating_add(self.reveal_window)
+        } else {
+            self.deadline
+        }
+    }
+
+    /// Payout = (weight / winning_weight) * total_pool
+    pub fn calculate_payout(&self, weight: u64, winning_weight: u64) -> Result<u64> {
+        // Use u128 to prevent overflow
+        let payout = (weight as u128)
+            .checked_mul(self.distributable_pool() as u128)
+            .ok_or(PredictDuelError::MathOverflow)?
+            .checked_div(winning_weight as u128)
+            .ok_or(PredictDuelError::MathOverflow)?;
+        Ok(payout as u64)
+    }
+
+    /// Protocol's cut of the pool; fixed-odds books pay locked payouts and take no cut
+    pub fn protocol_fee(&self) -> u64 {
+        if self.settlement_mode == SettlementMode::FixedOdds {
+            return 0;
+        }
+        (self.pool_size as u128 * self.protocol_fee_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Pool left for winners once the protocol fee and side pot are taken
+    pub fn distributable_pool(&self) -> u64 {
+        self.pool_size - self.protocol_fee() - self.side_pot()
+    }
+
+    /// Whole winning payout an installment of `amount` counts against. Escrowed stake must be
+    /// unwound first: until then the pools, and so the payout, can still move.
+    pub fn installment_gross(
+        &self,
+        participant: &Participant,
+        amount: u64,
+        now: i64,
+    ) -> Result<u64> {
+        require!(self.lst_tokens == 0, PredictDuelError::LstNotUnwound);
+        let gross = self.gross_payout(participant, now)?;
+        require!(
+            !(self.vesting_mandatory && gross > self.vesting_threshold),
+            PredictDuelError::VestingRequired
+        );
+        require!(
+            amount > 0 && participant.claimed_amount + amount <= gross,
+            PredictDuelError::InvalidInstallment
+        );
+        Ok(gross)
+    }
+
+    /// Most a merkle settlement root may pay out: the pool left for winners less the
+    /// creator's seed, which is still claimed through claim_seed
+    pub fn settlement_cap(&self, now: i64) -> Result<u64> {
+        Ok(self
+            .distributable_pool()
+            .saturating_sub(self.seed_payout(now)?))
+    }
+
+    /// Lottery side pot carved from a parimutuel pool for one drawn winner
+    pub fn side_pot(&self) -> u64 {
+        if self.settlement_mode != SettlementMode::Parimutuel {
+            return 0;
+        }
+        (self.pool_size as u128 * self.side_pot_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Bettors get a ticket for a random draw (raffles and side-pot markets)
+    pub fn issues_tickets(&self) -> bool {
+        self.settlement_mode == SettlementMode::Raffle
+            || (self.settlement_mode == SettlementMode::Parimutuel && self.side_pot_bps > 0)
+    }
+
+    /// Tickets are drawn from the winning side, so the draw waits until no dispute can
+    /// change which side that is
+    pub fn assert_raffle_drawable(&self, now: i64) -> Result<()> {
+        require!(
+            self.issues_tickets(),
+            PredictDuelError::InvalidSettlementMode
+        );
+        require!(
+            self.status == MarketStatus::Resolved,
+            PredictDuelError::MarketNotResolved
+        );
+        require!(
+            self.raffle_winners.is_empty(),
+            PredictDuelError::RaffleDrawn
+        );
+        self.assert_claims_open(now)
+    }
+
+    /// Payout locked for a fixed-odds bet at the currently quoted price
+    pub fn fixed_odds_payout(&self, stake: u64, prediction: bool) -> Result<u64> {
+        let odds_bps = if prediction {
+            self.yes_odds_bps
+        } else {
+            self.no_odds_bps
+        };
+        let payout = (stake as u128)
+            .checked_mul(odds_bps as u128)
+            .ok_or(PredictDuelError::MathOverflow)?
+            / BPS_DENOMINATOR as u128;
+        u64::try_from(payout).map_err(|_| error!(PredictDuelError::MathOverflow))
+    }
+
+    /// Payout weight of a stake entered at `now`: the stake plus the early bonus
+    /// still remaining at that point of the market's lifetime, less the late discount
+    /// if it lands in the final late_window_bps of it
+    pub fn entry_weight(&self, stake: u64, now: i64) -> Result<u64> {
+        if self.early_bonus_bps == 0 && self.late_discount_bps == 0 {
+            return Ok(stake);
+        }
+        let lifetime = self.deadline.saturating_sub(self.created_at).max(1) as u128;
+        let remaining = self.deadline.saturating_sub(now).clamp(0, lifetime as i64) as u128;
+        let bonus_bps = self.early_bonus_bps as u128 * remaining / lifetime;
+        let mut weight = (stake as u128)
+            .checked_mul(BPS_DENOMINATOR as u128 + bonus_bps)
+            .ok_or(PredictDuelError::MathOverflow)?
+            / BPS_DENOMINATOR as u128;
+        if remaining * (BPS_DENOMINATOR as u128) < lifetime * self.late_window_bps as u128 {
+            weight = weight * (BPS_DENOMINATOR - self.late_discount_bps as u64) as u128
+                / BPS_DENOMINATOR as u128;
+        }
+        u64::try_from(weight).map_err(|_| error!(PredictDuelError::MathOverflow))
+    }
+
+    /// Implied YES probability from the current pools, in basis points
+    pub fn implied_yes_prob_bps(&self) -> u16 {
+        let total = self.yes_pool as u128 + self.no_pool as u128;
+        if total == 0 {
+            return (BPS_DENOMINATOR / 2) as u16;
+        }
+        (self.yes_pool as u128 * BPS_DENOMINATOR as u128 / total) as u16
+    }
+
+    /// Refresh the stored implied probability; call after the pools change
+    pub fn sync_implied_prob(&mut self) {
+        self.implied_yes_prob_bps = self.implied_yes_prob_bps();
+    }
+
+    /// Fold the probability held since the last pool change into the TWAP accumulator.
+    /// Must be called before the pools are modified.
+    pub fn accrue_twap(&mut self, now: i64) {
+        let elapsed = now.saturating_sub(self.twap_last_update).max(0) as u128;
+        self.prob_cumulative = self
+            .prob_cumulative
+            .saturating_add(elapsed * self.implied_yes_prob_bps() as u128);
+        self.twap_last_update = now;
+    }
+
+    /// Time-weighted implied YES probability since creation, in basis points
+    pub fn twap_bps(&self, now: i64) -> u16 {
+        let window = now.saturating_sub(self.created_at).max(0) as u128;
+        if window == 0 {
+            return self.implied_yes_prob_bps();
+        }
+        let pending = now.saturating_sub(self.twap_last_update).max(0) as u128
+            * self.implied_yes_prob_bps() as u128;
+        (self.prob_cumulative.saturating_add(pending) / window) as u16
+    }
+
+    /// Take a position out of the pools before the deadline, returning the refund owed.
+    /// The cash-out fee stays in `pool_size` and is shared by the eventual winners.
+    pub fn remove_position(&mut self, participant: &Participant, clock: &Clock) -> Result<u64> {
+        // Cross-chain and receipt-backed positions are paid out through their own paths
+        participant.assert_settles_locally()?;
+        require!(
+            self.status == MarketStatus::Active,
+            PredictDuelError::MarketNotActive
+        );
+        // A sealed position's side isn't known until after the deadline
+        require!(!self.sealed, PredictDuelError::SealedMarket);
+        // Fixed-odds positions are priced against the book, not the pool, and
+        // raffle tickets can't be withdrawn once issued
+        require!(
+            self.settlement_mode != SettlementMode::FixedOdds && !self.issues_tickets(),
+            PredictDuelError::InvalidSettlementMode
+        );
+        // A duelist can only back out before an opponent takes the other side, or the
+        // vacated seat could be retaken mid-duel
+        require!(
+            self.settlement_mode != SettlementMode::WinnerTakesAll
+                || self.yes_pool == 0
+                || self.no_pool == 0,
+            PredictDuelError::DuelMatched
+        );
+        require!(self.betting_open(clock), PredictDuelError::MarketExpired);
+        require!(
+            !self.in_closing_auction(clock.unix_timestamp),
+            PredictDuelError::ClosingAuctionOpen
+        );
+        require!(
+            !participant.claimed && participant.stake > 0,
+            PredictDuelError::AlreadyClaimed
+        );
+
+        let fee = participant.stake * CASH_OUT_FEE_BPS / BPS_DENOMINATOR;
+        let refund_amount = participant.stake - fee;
+
+        self.accrue_twap(clock.unix_timestamp);
+
+        if participant.prediction {
+            self.yes_pool -= participant.stake;
+            self.yes_weight -= participant.weight;
+            self.yes_count -= 1;
+            self.insured_yes -= participant.insured_stake;
+        } else {
+            self.no_pool -= participant.stake;
+            self.no_weight -= participant.weight;
+            self.no_count -= 1;
+            self.insured_no -= participant.insured_stake;
+        }
+        self.sync_implied_prob();
+        self.pool_size -= refund_amount;
+        self.total_participants -= 1;
+
+        Ok(refund_amount)
+    }
+
+    /// Rebate owed on top of a winner's net payout. Winners pay the lowest of the market's
+    /// rate, its category's current rate and their volume tier, and fee-free credits waive
+    /// the fee on the part of the stake they cover; spends the credit it uses.
+    pub fn fee_rebate(
+        &self,
+        config: &Config,
+        participant: &Participant,
+        user_profile: Option<&mut UserProfile>,
+        payout: u64,
+        now: i64,
+    ) -> u64 {
+        if self.fee_collected {
+            return 0;
+        }
+        let category_bps = config
+            .category_fee_bps(self.category)
+            .min(self.protocol_fee_bps);
+        // A rotated profile's volume and credit belong to its new wallet
+        let user_profile = user_profile.filter(|user_profile| user_profile.moved_to.is_none());
+        let volume = user_profile
+            .as_ref()
+            .map_or(0, |user_profile| user_profile.volume_at(now));
+        let mut claim_bps = config.tiered_fee_bps(volume, category_bps);
+        if let Some(user_profile) = user_profile {
+            let credit = user_profile.fee_credit_at(now);
+            let covered = credit.min(participant.stake);
+            if covered > 0 {
+                claim_bps -=
+                    (claim_bps as u128 * covered as u128 / participant.stake as u128) as u16;
+                user_profile.fee_credit = credit - covered;
+            }
+        }
+        if claim_bps >= self.protocol_fee_bps {
+            return 0;
+        }
+        // payout is the net share (1 - fee) of the gross; rebate the fee difference on that gross
+        let rebate = (payout as u128 * (self.protocol_fee_bps - claim_bps) as u128
+            / (BPS_DENOMINATOR - self.protocol_fee_bps as u64) as u128) as u64;
+        rebate.min(self.protocol_fee() - self.fee_rebated)
+    }
+
+    /// What a winning claim pays out before splits and donations: the amount still owed plus
+    /// any fee rebate, which is booked against the protocol fee
+    pub fn claim_payout(
+        &mut self,
+        config: &Config,
+        participant: &Participant,
+        user_profile: Option<&mut UserProfile>,
+        now: i64,
+    ) -> Result<u64> {
+        let payout = self.winner_payout(participant, now)?;
+        let rebate = self.fee_rebate(config, participant, user_profile, payout, now);
+        self.fee_rebated += rebate;
+        Ok(payout + rebate)
+    }
+
+    /// Payout may go into a vesting schedule: the market offers vesting and the payout is
+    /// above its threshold
+    pub fn assert_vestable(&self, payout: u64) -> Result<()> {
+        require!(self.vesting_duration > 0, PredictDuelError::VestingDisabled);
+        require!(
+            payout > self.vesting_threshold,
+            PredictDuelError::PayoutBelowVestingThreshold
+        );
+        Ok(())
+    }
+
+    /// Amount still owed to a participant (net of installments already claimed),
+    /// validating the market resolved in their favour
+    pub fn winner_payout(&self, participant: &Participant, now: i64) -> Result<u64> {
+        require!(self.lst_tokens == 0, PredictDuelError::LstNotUnwound);
+        Ok(self
+            .gross_payout(participant, now)?
+            .saturating_sub(participant.claimed_amount))
+    }
+
+    /// What a pooled position (a syndicate's) settles for: its stake on a refund, its
+    /// winnings or nothing once resolved. A loss is only final once claims open, since a
+    /// dispute can still flip the outcome.
+    pub fn pooled_settlement(&self, participant: &Participant, now: i64) -> Result<u64> {
+        require!(self.lst_tokens == 0, PredictDuelError::LstNotUnwound);
+        if self.is_refundable() {
+            return Ok(participant.stake);
+        }
+        require!(
+            self.status == MarketStatus::Resolved,
+            PredictDuelError::MarketNotResolved
+        );
+        self.assert_claims_open(now)?;
+        let outcome = self.outcome.ok_or(PredictDuelError::NoOutcome)?;
+        if participant.prediction == outcome {
+            self.winner_payout(participant, now)
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// A winning position's whole payout at the market's fee rate. Pools can still move
+    /// while escrow is staked, so this is only final once the stake is unwound.
+    pub fn gross_payout(&self, participant: &Participant, now: i64) -> Result<u64> {
+        // Validate market is resolved
+        require!(
+            self.status == MarketStatus::Resolved,
+            PredictDuelError::MarketNotResolved
+        );
+        self.assert_claims_open(now)?;
+        require!(!self.merkle_settlement, PredictDuelError::MerkleSettled);
+        require!(!participant.claimed, PredictDuelError::AlreadyClaimed);
+
+        let outcome = self.outcome.ok_or(PredictDuelError::NoOutcome)?;
+
+        // Unrevealed sealed bets never win
+        require!(
+            !self.sealed || participant.revealed,
+            PredictDuelError::NotAWinner
+        );
+
+        // Check if participant won
+        let won = participant.prediction == outcome;
+        require!(won, PredictDuelError::NotAWinner);
+
+        // A winner-takes-all duel has a single winner: no proportional math needed
+        if self.settlement_mode == SettlementMode::WinnerTakesAll {
+            return Ok(self.distributable_pool());
+        }
+        // Fixed-odds payouts were locked in when the bets were placed
+        if self.settlement_mode == SettlementMode::FixedOdds {
+            return Ok(participant.locked_payout);
+        }
+        // Raffle winners split the whole pot evenly
+        if self.settlement_mode == SettlementMode::Raffle {
+            require!(
+                !self.raffle_winners.is_empty(),
+                PredictDuelError::RaffleNotDrawn
+            );
+            require!(
+                self.raffle_winners.contains(&participant.raffle_ticket),
+                PredictDuelError::NotAWinner
+            );
+            return Ok(self.distributable_pool() / self.raffle_winners.len() as u64);
+        }
+
+        // Calculate payout based on proportional share of the winning side's weight
+        // (equal to its stake unless the market rewards early entries)
+        let winning_weight = if outcome {
+            self.yes_weight
+        } else {
+            self.no_weight
+        };
+
+        require!(winning_weight > 0, PredictDuelError::MarketNotActive);
+
+        let payout = self.calculate_payout(participant.weight, winning_weight)?;
+
+        // Validate payout is positive
+        require!(payout > 0, PredictDuelError::MarketNotActive);
+
+        Ok(payout)
+    }
+}
+
+#[account]
+#[derive(Default, InitSpace)]
+pub struct Participant {
+    pub market: Pubkey,
+    pub bettor: Pubkey,
+    pub prediction: bool,
+    pub stake: u64,
+    pub claimed: bool,
+    pub bump: u8,
+    pub commitment: [u8; 32],
+    pub revealed: bool,
+    /// Stake weighted by entry time, used for payouts
+    pub weight: u64,
+    pub last_entry_at: i64,
+    /// Payout owed if this position wins on a fixed-odds market
+    pub locked_payout: u64,
+    /// Wormhole chain the position was opened from (0 = Solana)
+    pub origin_chain: u16,
+    /// Address on the origin chain that receives payouts
+    pub origin_address: [u8; 32],
+    /// Leaf nonce of the compressed NFT receipt; while set, only its holder can claim
+    pub receipt_nonce: Option<u64>,
+    pub trophy_minted: bool,
+    /// Stake already credited to reward epochs
+    pub volume_recorded: u64,
+    /// Shares of the payout sent to other wallets at claim; the rest goes to the winner
+    #[max_len(MAX_PAYOUT_SPLITS)]
+    pub payout_splits: Vec<PayoutSplit>,
+    /// Raffle ticket, numbered within the side the bettor picked
+    pub raffle_ticket: u32,
+    /// Bonus on the payout from a burned booster NFT, paid by the booster fund
+    pub boost_bps: u16,
+    /// Stake already credited with loyalty points
+    pub points_credited: u64,
+    pub win_points_credited: bool,
+    /// Payout already received through installments; `claimed` is set once it is all paid
+    pub claimed_amount: u64,
+    pub voted_void: bool,
+    /// Bets making up the position, with the price each one entered at
+    #[max_len(MAX_BET_ENTRIES)]
+    pub entries: Vec<BetEntry>,
+    /// Stake covered by dispute insurance
+    pub insured_stake: u64,
+    pub insurance_claimed: bool,
+    /// Frontend the position was first placed through, and the stake placed through it
+    /// that hasn't yet been credited a fee share
+    pub frontend: Option<Pubkey>,
+    pub frontend_stake: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct PayoutSplit {
+    pub recipient: Pubkey,
+    pub bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub struct BetEntry {
+    pub timestamp: i64,
+    pub amount: u64,
+    /// Market's implied YES probability when the bet was placed
+    pub implied_yes_prob_bps: u16,
+}
+
+impl Participant {
+    /// Attribute stake to the frontend it came through; a position keeps the first frontend
+    /// it was placed through
+    pub fn record_frontend(&mut self, frontend: Pubkey, stake_amount: u64) {
+        if self.frontend.is_none() {
+            self.frontend = Some(frontend);
+        }
+        if self.frontend == Some(frontend) {
+            self.frontend_stake += stake_amount;
+        }
+    }
+
+    /// Log a bet; once the log is full it folds into the last entry at the stake-weighted price
+    pub fn record_entry(&mut self, timestamp: i64, amount: u64, implied_yes_prob_bps: u16) {
+        if self.entries.len() < MAX_BET_ENTRIES {
+            self.entries.push(BetEntry {
+                timestamp,
+                amount,
+                implied_yes_prob_bps,
+            });
+        } else if let Some(last) = self.entries.last_mut() {
+            let total = last.amount + amount;
+            last.implied_yes_prob_bps = ((last.amount as u128 * last.implied_yes_prob_bps as u128
+                + amount as u128 * implied_yes_prob_bps as u128)
+                / total.max(1) as u128) as u16;
+            last.amount = total;
+            last.timestamp = timestamp;
+        }
+    }
+
+    /// Stake-weighted implied YES probability across the position's entries
+    pub fn average_entry_prob_bps(&self) -> u16 {
+        let total: u128 = self.entries.iter().map(|entry| entry.amount as u128).sum();
+        if total == 0 {
+            return 0;
+        }
+        (self
+            .entries
+            .iter()
+            .map(|entry| entry.amount as u128 * entry.implied_yes_prob_bps as u128)
+            .sum::<u128>()
+            / total) as u16
+    }
+
+    /// Position is paid to the bettor's Solana wallet (not cross-chain, no receipt outstanding)
+    pub fn assert_settles_locally(&self) -> Result<()> {
+        require!(self.origin_chain == 0, PredictDuelError::CrossChainPosition);
+        require!(self.receipt_nonce.is_none(), PredictDuelError::ReceiptHeld);
+        Ok(())
+    }
+
+    /// Paid in full to the bettor's wallet, so settle_batch can pay it (no splits or booster)
+    pub fn batch_settleable(&self) -> bool {
+        self.assert_settles_locally().is_ok()
+            && self.payout_splits.is_empty()
+            && self.boost_bps == 0
+    }
+}
+
+/// Reusable market definition; a schedule's markets get deadline = run time + duration
+#[account]
+#[derive(InitSpace)]
+pub struct MarketTemplate {
+    pub creator: Pubkey,
+    pub template_id: u64,
+    #[max_len(200)]
+    pub question: String,
+    pub category: MarketCategory,
+    pub stake_amount: u64,
+    pub market_type: MarketType,
+    pub duration: i64,
+    pub resolver: Option<Pubkey>,
+    pub bump: u8,
+}
+
+/// A creator's prepayment for series rollovers, held as the lamports above rent
+#[account]
+#[derive(InitSpace)]
+pub struct SeriesFund {
+    pub creator: Pubkey,
+    pub bump: u8,
+}
+
+/// Recurring market creation from a template, executed by keepers. Lamports above rent
+/// are the creator's prepayment for each run's creation fee and bond.
+#[account]
+#[derive(InitSpace)]
+pub struct Schedule {
+    pub template: Pubkey,
+    pub creator: Pubkey,
+    pub interval: i64,
+    pub next_run: i64,
+    /// Market index the next run creates under the creator's seeds
+    pub next_market_index: u64,
+    pub runs_remaining: u32,
+    pub bump: u8,
+}
+
+/// Minimal record of a closed market, kept after the market account is reclaimed
+#[account]
+#[derive(InitSpace)]
+pub struct MarketArchive {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub market_index: u64,
+    /// sha256 of the question text
+    pub question_hash: [u8; 32],
+    pub category: MarketCategory,
+    pub status: MarketStatus,
+    pub outcome: Option<bool>,
+    pub yes_pool: u64,
+    pub no_pool: u64,
+    pub total_participants: u32,
+    pub created_at: i64,
+    pub settled_at: i64,
+    pub bump: u8,
+}
+
+/// Third-party UI that earns a share of the protocol fee on bets placed through it
+#[account]
+#[derive(InitSpace)]
+pub struct Frontend {
+    pub owner: Pubkey,
+    /// Fee share held here until the owner claims it
+    pub claimable: u64,
+    pub total_earned: u64,
+    pub bump: u8,
+}
+
+/// Wallet's interest in a market or creator; notification keepers read these
+#[account]
+#[derive(InitSpace)]
+pub struct Subscription {
+    pub subscriber: Pubkey,
+    pub target: Pubkey,
+    pub kind: SubscriptionKind,
+    pub subscribed_at: i64,
+    pub bump: u8,
+}
+
+/// One wallet following another; indexers read these for feeds and follower rankings
+#[account]
+#[derive(InitSpace)]
+pub struct Follow {
+    pub follower: Pubkey,
+    pub followed: Pubkey,
+    pub followed_at: i64,
+    pub bump: u8,
+}
+
+/// Unique username handle, looked up by name to find its wallet
+#[account]
+#[derive(InitSpace)]
+pub struct Handle {
+    #[max_len(MAX_HANDLE_LEN)]
+    pub handle: String,
+    pub wallet: Pubkey,
+    pub bump: u8,
+}
+
+/// Reverse lookup from a wallet to the handle it holds
+#[account]
+#[derive(InitSpace)]
+pub struct WalletHandle {
+    pub wallet: Pubkey,
+    #[max_len(MAX_HANDLE_LEN)]
+    pub handle: String,
+    pub bump: u8,
+}
+
+/// Per-creator track record for dashboards and reputation
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorStats {
+    pub creator: Pubkey,
+    pub markets_created: u32,
+    /// Markets recorded once closed, and the stake they attracted
+    pub markets_closed: u32,
+    pub total_volume: u64,
+    /// Resolved markets, and the summed seconds from resolvable to resolved
+    pub markets_resolved: u32,
+    pub total_resolution_time: i64,
+    /// Closed markets whose resolution was disputed or that the admin halted
+    pub markets_disputed: u32,
+    pub bump: u8,
+}
+
+impl CreatorStats {
+    pub fn average_resolution_secs(&self) -> i64 {
+        self.total_resolution_time
+            .checked_div(self.markets_resolved as i64)
+            .unwrap_or(0)
+    }
+
+    pub fn dispute_rate_bps(&self) -> u16 {
+        (self.markets_disputed as u64 * BPS_DENOMINATOR)
+            .checked_div(self.markets_closed as u64)
+            .unwrap_or(0) as u16
+    }
+}
+
+/// Running totals for one market category, so listings don't need chain scans
+#[account]
+#[derive(InitSpace)]
+pub struct CategoryStats {
+    pub category: MarketCategory,
+    pub total_markets: u64,
+    pub open_markets: u32,
+    /// Stake that has entered the category's markets
+    pub total_volume: u64,
+    /// Markets resolved, voided or cancelled, and the sum of their final pools
+    pub closed_markets: u64,
+    pub closed_pool_total: u64,
+    pub bump: u8,
+}
+
+impl CategoryStats {
+    /// Mean final pool of the category's closed markets
+    pub fn average_pool_size(&self) -> u64 {
+        self.closed_pool_total
+            .checked_div(self.closed_markets)
+            .unwrap_or(0)
+    }
+}
+
+/// Payout root of a merkle-settled market, with a bitmap of claimed leaves
+#[account]
+#[derive(InitSpace)]
+pub struct Settlement {
+    pub market: Pubkey,
+    pub root: [u8; 32],
+    pub total_payout: u64,
+    pub claimed_amount: u64,
+    pub leaf_count: u32,
+    /// One bit per leaf; sized from leaf_count when the account is created
+    #[max_len(0)]
+    pub claimed_bitmap: Vec<u8>,
+    pub bump: u8,
+    /// Outcome the root was computed for
+    pub outcome: Option<bool>,
+}
+
+/// Merkle airdrop of an SPL token to a snapshot of wallets, root published by governance
+#[account]
+#[derive(InitSpace)]
+pub struct MerkleDistributor {
+    pub distributor_id: u64,
+    pub mint: Pubkey,
+    /// Zero until published; claims open once set
+    pub root: [u8; 32],
+    pub total_claimed: u64,
+    pub claimed_count: u64,
+    pub bump: u8,
+}
+
+impl MerkleDistributor {
+    /// Roots are write-once so published allocations can't be changed under claimants
+    pub fn publish_root(&mut self, root: [u8; 32]) -> Result<()> {
+        require!(self.root == [0; 32], PredictDuelError::RootAlreadyPublished);
+        self.root = root;
+
+        msg!("Distributor {} root published", self.distributor_id);
+
+        Ok(())
+    }
+}
+
+/// Receipt for one claimed airdrop leaf
+#[account]
+#[derive(InitSpace)]
+pub struct AirdropClaim {
+    pub distributor: Pubkey,
+    pub index: u64,
+    pub claimant: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+/// Time-boxed engagement objective rewarded with points and optionally a booster NFT
+#[account]
+#[derive(InitSpace)]
+pub struct Quest {
+    pub quest_id: u64,
+    pub objective: QuestObjective,
+    pub starts_at: i64,
+    pub ends_at: i64,
+    pub reward_points: u64,
+    /// Booster tier mint awarded on completion
+    pub reward_booster: Option<Pubkey>,
+    pub completions: u32,
+    pub bump: u8,
+}
+
+impl Quest {
+    pub fn is_complete(&self, progress: &QuestProgress) -> bool {
+        match self.objective {
+            QuestObjective::BetCategories(count) => {
+                progress.categories.count_ones() >= count as u32
+            }
+            QuestObjective::WinDuels(count) => progress.wins >= count,
+            QuestObjective::Volume(lamports) => progress.volume >= lamports,
+        }
+    }
+}
+
+/// A wallet's progress on one quest
+#[account]
+#[derive(InitSpace)]
+pub struct QuestProgress {
+    pub quest: Pubkey,
+    pub wallet: Pubkey,
+    /// Bitmask of MarketCategory values bet in
+    pub categories: u8,
+    pub wins: u8,
+    pub volume: u64,
+    /// Participant accounts already counted
+    #[max_len(MAX_QUEST_POSITIONS)]
+    pub positions: Vec<Pubkey>,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+/// Admin-defined loyalty points emission; rates halve every halving_period
+#[account]
+#[derive(InitSpace)]
+pub struct PointsSchedule {
+    pub bet_points_per_sol: u64,
+    pub win_points: u64,
+    pub start_time: i64,
+    /// Seconds between halvings (0 = rates never decay)
+    pub halving_period: i64,
+    pub bump: u8,
+    /// Points per SOL of fee-free credit (0 = redemption off)
+    pub redeem_points_per_sol: u64,
+}
+
+impl PointsSchedule {
+    /// Number of halvings applied to the base rates at `now`
+    pub fn halvings_at(&self, now: i64) -> u32 {
+        if self.halving_period == 0 {
+            return 0;
+        }
+        (((now - self.start_time).max(0) / self.halving_period) as u32).min(63)
+    }
+
+    /// Points owed to a position and marks them credited. Nothing accrues until the market
+    /// resolves and its claims open, so refunded, voided or cashed-out stakes never earn
+    pub fn accrue(&self, market: &Market, participant: &mut Participant, now: i64) -> Result<u64> {
+        require!(
+            market.status == MarketStatus::Resolved,
+            PredictDuelError::MarketNotResolved
+        );
+        market.assert_claims_open(now)?;
+
+        let halvings = self.halvings_at(now);
+        let new_stake = participant
+            .stake
+            .saturating_sub(participant.points_credited);
+        let mut earned = ((new_stake as u128 * self.bet_points_per_sol as u128 / 1_000_000_000)
+            as u64)
+            >> halvings;
+        participant.points_credited = participant.points_credited.max(participant.stake);
+
+        let won = market.outcome == Some(participant.prediction)
+            && (!market.sealed || participant.revealed);
+        if won && !participant.win_points_credited {
+            earned += self.win_points >> halvings;
+            participant.win_points_credited = true;
+        }
+        require!(earned > 0, PredictDuelError::NoPointsToAccrue);
+        Ok(earned)
+    }
+}
+
+/// Non-transferable loyalty points; the balance is spent on perks, lifetime never drops
+#[account]
+#[derive(InitSpace)]
+pub struct Points {
+    pub wallet: Pubkey,
+    pub balance: u64,
+    pub lifetime: u64,
+    pub bump: u8,
+}
+
+/// Lamports paying booster bonuses; also the mint authority of booster tiers
+#[account]
+#[derive(InitSpace)]
+pub struct BoosterFund {
+    pub total_paid: u64,
+    pub bump: u8,
+}
+
+/// Booster NFT tier: burning one of `mint` adds `multiplier_bps` of the payout as a bonus
+#[account]
+#[derive(InitSpace)]
+pub struct BoosterTier {
+    pub mint: Pubkey,
+    pub tier_id: u8,
+    pub multiplier_bps: u16,
+    pub bump: u8,
+}
+
+/// Stakers eligible to be drawn onto dispute juries
+#[account]
+#[derive(InitSpace)]
+pub struct JurorPool {
+    #[max_len(MAX_JUROR_POOL)]
+    pub jurors: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+/// Challenge to a market's resolution, settled by a randomly drawn jury of stakers
+#[account]
+#[derive(InitSpace)]
+pub struct Dispute {
+    pub market: Pubkey,
+    pub disputer: Pubkey,
+    /// Lamports held in this account until the verdict
+    pub bond: u64,
+    /// Switchboard randomness account committed when the dispute opened
+    pub randomness: Pubkey,
+    pub opened_at: i64,
+    pub voting_ends_at: i64,
+    #[max_len(JURY_SIZE)]
+    pub panel: Vec<JurorSeat>,
+    pub status: DisputeStatus,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct JurorSeat {
+    pub juror: Pubkey,
+    pub vote: Option<bool>,
+    /// Juror's stake when they voted
+    pub weight: u64,
+}
+
+impl Dispute {
+    /// Outcome backed by more voting stake, or None on a tie (including no votes)
+    pub fn majority(&self) -> Option<bool> {
+        let weight_for = |side: bool| {
+            self.panel
+                .iter()
+                .filter(|seat| seat.vote == Some(side))
+                .map(|seat| seat.weight)
+                .sum::<u64>()
+        };
+        match weight_for(true).cmp(&weight_for(false)) {
+            std::cmp::Ordering::Greater => Some(true),
+            std::cmp::Ordering::Less => Some(false),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+/// Release the panel's vote locks and, given a majority, move JUROR_SLASH_BPS of every other
+/// juror's stake to the majority jurors pro rata to their voting stake.
+/// `positions` are the panel's stake positions in seat order.
+fn settle_jurors<'info>(
+    panel: &[JurorSeat],
+    majority: Option<bool>,
+    pool: &StakingPool,
+    positions: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    require!(
+        positions.len() == panel.len(),
+        PredictDuelError::JurorPositionMismatch
+    );
+
+    let mut accounts = Vec::with_capacity(panel.len());
+    let mut slashed = 0u64;
+    for (seat, info) in panel.iter().zip(positions) {
+        let mut position = Account::<StakePosition>::try_from(info)?;
+        require!(
+            position.staker == seat.juror,
+            PredictDuelError::JurorPositionMismatch
+        );
+        position.accrue(pool.acc_fee_per_share)?;
+        if seat.vote.is_some() {
+            position.jury_votes -= 1;
+        }
+        if majority.is_some() && seat.vote != majority {
+            let slash = (position.amount as u128 * JUROR_SLASH_BPS as u128
+                / BPS_DENOMINATOR as u128) as u64;
+            position.amount -= slash;
+            slashed += slash;
+        }
+        accounts.push(position);
+    }
+
+    let in_majority = |seat: &JurorSeat| majority.is_some() && seat.vote == majority;
+    let majority_weight: u64 = panel
+        .iter()
+        .filter(|seat| in_majority(seat))
+        .map(|seat| seat.weight)
+        .sum();
+    let mut distributed = 0u64;
+    let mut first_winner = None;
+    for (index, (seat, position)) in panel.iter().zip(accounts.iter_mut()).enumerate() {
+        if in_majority(seat) {
+            let reward = (slashed as u128 * seat.weight as u128 / majority_weight as u128) as u64;
+            position.amount += reward;
+            distributed += reward;
+            first_winner.get_or_insert(index);
+        }
+    }
+    // Rounding dust stays staked with the first majority juror
+    if let Some(index) = first_winner {
+        accounts[index].amount += slashed - distributed;
+    }
+
+    for position in accounts.iter_mut() {
+        position.reward_debt = position.amount as u128 * pool.acc_fee_per_share;
+        position.exit(&crate::ID)?;
+    }
+    Ok(())
+}
+
+/// Information bounty for resolving a market from its oracle; holds the funded lamports
+#[account]
+#[derive(InitSpace)]
+pub struct Bounty {
+    pub market: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+/// Participant's resolution boost on a market; holds the pledged lamports
+#[account]
+#[derive(InitSpace)]
+pub struct ResolutionBoost {
+    pub market: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+/// Display metadata kept off the Market account, which every bet touches
+#[account]
+#[derive(InitSpace)]
+pub struct MarketExtension {
+    pub market: Pubkey,
+    /// BCP 47 language tag, e.g. "en" or "pt-BR"
+    #[max_len(MAX_LANGUAGE_LEN)]
+    pub language: String,
+    #[max_len(MAX_IMAGE_URI_LEN)]
+    pub image_uri: String,
+    /// URLs or descriptions of where the outcome will be checked
+    #[max_len(MAX_RESOLUTION_SOURCES, MAX_RESOLUTION_SOURCE_LEN)]
+    pub resolution_sources: Vec<String>,
+    /// Identifier in an external system (e.g. a sports feed's fixture id)
+    #[max_len(MAX_EXTERNAL_ID_LEN)]
+    pub external_id: String,
+    pub bump: u8,
+}
+
+/// Ordered homepage carousel; trophy eligibility still comes from Market::featured
+#[account]
+#[derive(InitSpace)]
+pub struct Featured {
+    #[max_len(MAX_FEATURED_MARKETS)]
+    pub markets: Vec<Pubkey>,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+/// Report tally for a market
+#[account]
+#[derive(InitSpace)]
+pub struct Flags {
+    pub market: Pubkey,
+    pub flag_count: u32,
+    /// Flag stakes not yet withdrawn
+    pub total_staked: u64,
+    pub review: FlagReview,
+    pub bump: u8,
+}
+
+/// One reporter's flag; holds the flag stake until withdrawn
+#[account]
+#[derive(InitSpace)]
+pub struct FlagRecord {
+    pub market: Pubkey,
+    pub reporter: Pubkey,
+    pub reason: FlagReason,
+    pub stake: u64,
+    pub bump: u8,
+}
+
+/// Per-wallet activity record, created on a wallet's first bet
+#[account]
+#[derive(InitSpace)]
+pub struct UserProfile {
+    pub wallet: Pubkey,
+    pub last_bet_slot: u64,
+    pub bets_in_slot: u16,
+    /// Start of the current one-minute rate window
+    pub window_start: i64,
+    pub bets_in_window: u16,
+    pub bump: u8,
+    /// Staked volume over roughly the last 30 days, as of volume_updated_at
+    pub volume_30d: u64,
+    pub volume_updated_at: i64,
+    pub markets_created: u32,
+    /// Markets resolved within RESOLUTION_GRACE_PERIOD of becoming resolvable
+    pub resolved_on_time: u32,
+    pub resolved_late: u32,
+    /// Markets the admin halted as broken
+    pub markets_disputed: u32,
+    /// Share of each win donated to config.charity (opt-in)
+    pub charity_bps: u16,
+    /// Lamports donated to charity over the wallet's lifetime
+    pub charity_donated: u64,
+    /// Stake whose protocol fee is waived at claim, redeemed from loyalty points
+    pub fee_credit: u64,
+    pub fee_credit_expires_at: i64,
+    /// Consecutive days checked in, and the day (unix time / 1 day) of the last check-in
+    pub check_in_streak: u32,
+    pub last_check_in_day: i64,
+    /// Markets resolved as a third-party resolver (or as creator-resolver), and how many of
+    /// those resolutions went to a jury and how many the jury reversed
+    pub resolutions: u32,
+    pub resolutions_disputed: u32,
+    pub resolutions_overturned: u32,
+    /// Secondary key that must co-sign claims paying more than co_sign_threshold
+    pub co_signer: Option<Pubkey>,
+    pub co_sign_threshold: u64,
+    /// Key that can move the profile to a new wallet, recovery_delay after requesting it
+    pub recovery_key: Option<Pubkey>,
+    pub recovery_delay: i64,
+    /// Wallet a pending recovery moves the profile to, and when it can complete
+    pub pending_wallet: Option<Pubkey>,
+    pub recovery_eta: i64,
+    /// Off-chain JSON with the avatar and bio frontends show for this wallet
+    #[max_len(MAX_PROFILE_URI_LEN)]
+    pub metadata_uri: String,
+    /// Follow PDAs pointing at and from this wallet
+    pub followers: u32,
+    pub following: u32,
+    /// Wallet the profile was rotated to. The old profile stays open so positions and
+    /// markets still held by this wallet keep its co-signer and can still be resolved.
+    pub moved_to: Option<Pubkey>,
+}
+
+impl UserProfile {
+    /// Leave this profile behind after rotating it to `new_wallet`. It keeps its co-signer
+    /// and charity split for the positions still held by the old wallet, but its balances
+    /// and reputation went with the move, and it can't be rotated or recovered again.
+    pub fn tombstone(&mut self, new_wallet: Pubkey) {
+        self.moved_to = Some(new_wallet);
+        self.recovery_key = None;
+        self.pending_wallet = None;
+        self.recovery_eta = 0;
+        self.volume_30d = 0;
+        self.fee_credit = 0;
+        self.fee_credit_expires_at = 0;
+        self.charity_donated = 0;
+        self.check_in_streak = 0;
+        self.markets_created = 0;
+        self.resolved_on_time = 0;
+        self.resolved_late = 0;
+        self.markets_disputed = 0;
+        self.resolutions = 0;
+        self.resolutions_disputed = 0;
+        self.resolutions_overturned = 0;
+    }
+
+    /// Require the co-signer on a claim paying `amount`, if one is registered and the
+    /// amount is above its threshold
+    pub fn assert_co_signed(&self, co_signer: Option<&Signer>, amount: u64) -> Result<()> {
+        if let Some(key) = self.co_signer {
+            require!(
+                amount <= self.co_sign_threshold
+                    || co_signer.is_some_and(|signer| signer.key() == key),
+                PredictDuelError::CoSignerRequired
+            );
+        }
+        Ok(())
+    }
+
+    /// Fee-free credit still usable at `now`
+    pub fn fee_credit_at(&self, now: i64) -> u64 {
+        if now < self.fee_credit_expires_at {
+            self.fee_credit
+        } else {
+            0
+        }
+    }
+
+    /// 30-day volume at `now`, decayed linearly since the last update
+    pub fn volume_at(&self, now: i64) -> u64 {
+        let remaining = FEE_TIER_VOLUME_WINDOW
+            - (now - self.volume_updated_at).clamp(0, FEE_TIER_VOLUME_WINDOW);
+        (self.volume_30d as u128 * remaining as u128 / FEE_TIER_VOLUME_WINDOW as u128) as u64
+    }
+
+    pub fn record_volume(&mut self, stake: u64, now: i64) {
+        self.volume_30d = self.volume_at(now).saturating_add(stake);
+        self.volume_updated_at = now;
+    }
+
+    /// Record a manual resolution; returns whether it was on time
+    pub fn record_resolution(&mut self, resolvable_at: i64, now: i64) -> bool {
+        let on_time = now <= resolvable_at.saturating_add(RESOLUTION_GRACE_PERIOD);
+        if on_time {
+            self.resolved_on_time += 1;
+        } else {
+            self.resolved_late += 1;
+        }
+        on_time
+    }
+
+    /// Share of this wallet's resolutions that were not overturned, in bps (0 with no record)
+    pub fn resolver_accuracy_bps(&self) -> u16 {
+        if self.resolutions == 0 {
+            return 0;
+        }
+        (self.resolutions.saturating_sub(self.resolutions_overturned) as u64 * BPS_DENOMINATOR
+            / self.resolutions as u64) as u16
+    }
+
+    /// Creator reputation in bps: share of on-time resolutions, with disputes counted
+    /// twice; creators with no history score 0
+    pub fn creator_score_bps(&self) -> u16 {
+        let total = self.resolved_on_time as u64
+            + self.resolved_late as u64
+            + 2 * self.markets_disputed as u64;
+        if total == 0 {
+            return 0;
+        }
+        (self.resolved_on_time as u64 * BPS_DENOMINATOR / total) as u16
+    }
+
+    /// Count a bet against the config's per-slot and per-minute caps
+    pub fn record_bet(&mut self, config: &Config, slot: u64, now: i64) -> Result<()> {
+        if slot != self.last_bet_slot {
+            self.last_bet_slot = slot;
+            self.bets_in_slot = 0;
+        }
+        if now >= self.window_start + 60 {
+            self.window_start = now;
+            self.bets_in_window = 0;
+        }
+        self.bets_in_slot += 1;
+        self.bets_in_window += 1;
+
+        require!(
+            config.max_bets_per_slot == 0 || self.bets_in_slot <= config.max_bets_per_slot,
+            PredictDuelError::RateLimited
+        );
+        require!(
+            config.max_bets_per_minute == 0 || self.bets_in_window <= config.max_bets_per_minute,
+            PredictDuelError::RateLimited
+        );
+        Ok(())
+    }
+}
+
+/// Pay a winning claim's split recipients, then the winner's charity donation, out of the
+/// vault; returns the winner's own share, which is still in the vault
+#[allow(clippy::too_many_arguments)]
+pub fn pay_claim_shares<'info>(
+    payout: u64,
+    participant: &Participant,
+    recipients: &[AccountInfo<'info>],
+    user_profile: Option<&mut UserProfile>,
+    charity: Option<AccountInfo<'info>>,
+    market_vault: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<u64> {
+    require!(
+        recipients.len() == participant.payout_splits.len(),
+        PredictDuelError::InvalidPayoutSplit
+    );
+    let mut remainder = payout;
+    for (split, recipient) in participant.payout_splits.iter().zip(recipients) {
+        require!(
+            recipient.key() == split.recipient,
+            PredictDuelError::InvalidPayoutSplit
+        );
+        let share = (payout as u128 * split.bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: market_vault.clone(),
+                    to: recipient.clone(),
+                },
+                signer,
+            ),
+            share,
+        )?;
+        remainder -= share;
+    }
+
+    // Opted-in winners donate a share of what they keep
+    if let Some(user_profile) = user_profile {
+        if user_profile.charity_bps > 0 {
+            let charity = charity.ok_or(PredictDuelError::CharityRequired)?;
+            let donation = (remainder as u128 * user_profile.charity_bps as u128
+                / BPS_DENOMINATOR as u128) as u64;
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    system_program,
+                    anchor_lang::system_program::Transfer {
+                        from: market_vault,
+                        to: charity,
+                    },
+                    signer,
+                ),
+                donation,
+            )?;
+            user_profile.charity_donated += donation;
+            remainder -= donation;
+        }
+    }
+    Ok(remainder)
+}
+
+/// Boosted positions earn a bonus on top of the payout, paid from the booster fund while
+/// it lasts
+pub fn pay_boost_bonus<'info>(
+    participant: &Participant,
+    payout: u64,
+    booster_fund: Option<&mut Account<'info, BoosterFund>>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    if participant.boost_bps == 0 {
+        return Ok(());
+    }
+    let booster_fund = booster_fund.ok_or(PredictDuelError::BoosterFundRequired)?;
+    let rent_floor = Rent::get()?.minimum_balance(booster_fund.to_account_info().data_len());
+    let available = booster_fund.get_lamports().saturating_sub(rent_floor);
+    let bonus = ((payout as u128 * participant.boost_bps as u128 / BPS_DENOMINATOR as u128) as u64)
+        .min(available);
+    booster_fund.sub_lamports(bonus)?;
+    destination.add_lamports(bonus)?;
+    booster_fund.total_paid += bonus;
+    Ok(())
+}
+
+/// Apply the co-signer rule of a wallet's profile, which claimants can't dodge by
+/// leaving it out; wallets without a profile have no co-signer
+pub fn assert_claim_co_signed(
+    profile: &AccountInfo,
+    co_signer: Option<&Signer>,
+    amount: u64,
+) -> Result<()> {
+    if profile.data_is_empty() {
+        return Ok(());
+    }
+    require!(profile.owner == &crate::ID, PredictDuelError::Unauthorized);
+    UserProfile::try_deserialize(&mut &profile.try_borrow_data()?[..])?
+        .assert_co_signed(co_signer, amount)
+}
+
+/// Counts a new market towards its creator's and category's stats and applies the creator's limits
+pub fn register_market(
+    market: &mut Market,
+    config: &Config,
+    creator_profile: &mut UserProfile,
+    creator_stats: Option<&mut Account<CreatorStats>>,
+    category_stats: Option<&mut Account<CategoryStats>>,
+) -> Result<()> {
+    require!(
+        creator_profile.moved_to.is_none(),
+        PredictDuelError::ProfileMoved
+    );
+    creator_profile.markets_created += 1;
+    if let Some(creator_stats) = creator_stats {
+        creator_stats.markets_created += 1;
+    }
+    market.apply_creator_limits(config, creator_profile)?;
+    if let Some(category_stats) = category_stats {
+        market.sync_category_stats(category_stats);
+    }
+    Ok(())
+}
+
+/// Spam fee a creator owes for a new market, waived for reputable creators and creator
+/// pass holders
+pub fn creation_fee_due(
+    config: &Config,
+    creator: &Pubkey,
+    creator_profile: &UserProfile,
+    creator_pass: Option<&anchor_spl::token_interface::TokenAccount>,
+) -> u64 {
+    let fee_waived = (config.fee_waiver_score_bps > 0
+        && creator_profile.creator_score_bps() >= config.fee_waiver_score_bps)
+        || config.creator_pass_mint.is_some_and(|mint| {
+            creator_pass
+                .is_some_and(|pass| pass.mint == mint && pass.owner == *creator && pass.amount > 0)
+        });
+    if fee_waived {
+        0
+    } else {
+        config.creation_fee
+    }
+}
+
+/// Takes the creation fee and bond from the payer, whichever instruction creates the market
+pub fn charge_creation<'info>(
+    market: &mut Account<'info, Market>,
+    config: &Config,
+    creator_profile: &UserProfile,
+    creator_pass: Option<&anchor_spl::token_interface::TokenAccount>,
+    payer: AccountInfo<'info>,
+    treasury: Option<AccountInfo<'info>>,
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    let fee = creation_fee_due(config, &market.creator, creator_profile, creator_pass);
+    if fee > 0 {
+        let treasury = treasury.ok_or(PredictDuelError::TreasuryRequired)?;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: treasury,
+                },
+            ),
+            fee,
+        )?;
+    }
+
+    // Bond sits on the market account itself until the market settles
+    market.creation_bond = config.creation_bond;
+    if market.creation_bond > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program,
+                anchor_lang::system_program::Transfer {
+                    from: payer,
+                    to: market.to_account_info(),
+                },
+            ),
+            market.creation_bond,
+        )?;
+    }
+    Ok(())
+}
+
+/// Takes the creation fee and bond for a market a keeper creates out of lamports the
+/// creator prefunded onto a program account, so the keeper only fronts rent
+pub fn charge_prepaid_creation<'info>(
+    market: &mut Account<'info, Market>,
+    config: &Config,
+    creator_profile: &UserProfile,
+    creator_pass: Option<&anchor_spl::token_interface::TokenAccount>,
+    prepaid: &AccountInfo<'info>,
+    treasury: Option<AccountInfo<'info>>,
+) -> Result<()> {
+    let fee = creation_fee_due(config, &market.creator, creator_profile, creator_pass);
+    let balance = prepaid
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(prepaid.data_len()));
+    require!(
+        balance >= fee + config.creation_bond,
+        PredictDuelError::PrepaidBalanceTooLow
+    );
+
+    if fee > 0 {
+        let treasury = treasury.ok_or(PredictDuelError::TreasuryRequired)?;
+        prepaid.sub_lamports(fee)?;
+        treasury.add_lamports(fee)?;
+    }
+
+    // Bond sits on the market account itself until the market settles
+    market.creation_bond = config.creation_bond;
+    if market.creation_bond > 0 {
+        prepaid.sub_lamports(market.creation_bond)?;
+        market.add_lamports(market.creation_bond)?;
+    }
+    Ok(())
+}
+
+/// Handles are 3 to MAX_HANDLE_LEN characters of lowercase letters, digits and '_', so
+/// each name has exactly one spelling
+pub fn valid_handle(handle: &str) -> bool {
+    (3..=MAX_HANDLE_LEN).contains(&handle.len())
+        && handle
+            .bytes()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'_')
+}
+
+/// Commitment for a sealed bet: hash(market, bettor, side, salt)
+pub fn bet_commitment(
+    market: &Pubkey,
+    bettor: &Pubkey,
+    prediction: bool,
+    salt: &[u8; 32],
+) -> [u8; 32] {
+    hashv(&[market.as_ref(), bettor.as_ref(), &[prediction as u8], salt]).to_bytes()
+}
+
+/// Merkle claim leaf (airdrops, settlements): hash(0x00, index, claimant, amount);
+/// the prefix keeps leaves distinct from nodes
+pub fn claim_leaf(index: u64, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[
+        &[0],
+        &index.to_le_bytes(),
+        claimant.as_ref(),
+        &amount.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Walk a proof up to the root; each node is hash(0x01, lower child, higher child)
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node <= *sibling {
+            (node, *sibling)
+        } else {
+            (*sibling, node)
+        };
+        hashv(&[&[1], &left, &right]).to_bytes()
+    });
+    computed == root
+}
+
+/// Global program settings, owned by the admin
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub bump: u8,
+    /// Key trusted to curate market content alongside creators
+    pub moderator: Pubkey,
+    /// Protocol cut of each resolved pool, snapshotted into markets at creation
+    pub protocol_fee_bps: u16,
+    /// Receives protocol fees
+    pub treasury: Pubkey,
+    /// Minimum stake per bet, snapshotted into markets at creation
+    pub min_stake: u64,
+    /// Time a resolution can be challenged before it is final
+    pub dispute_window: i64,
+    /// Token whose holders govern protocol parameters; unset while the admin does
+    pub governance_mint: Pubkey,
+    pub proposal_count: u64,
+    /// Admin nominated by a timelocked rotation, pending their acceptance
+    pub pending_admin: Pubkey,
+    /// Delay between queueing and executing sensitive admin actions
+    pub timelock_delay: i64,
+    pub queued_count: u64,
+    /// Key whose ed25519 attestations bets must carry; unset disables the gate
+    pub attestation_verifier: Pubkey,
+    /// How old an attestation can be when the bet lands
+    pub attestation_max_age: i64,
+    /// Per-wallet bet caps (0 = unlimited)
+    pub max_bets_per_slot: u16,
+    pub max_bets_per_minute: u16,
+    /// Discounted fee rates by 30-day volume; unused tiers have min_volume 0
+    pub fee_tiers: [FeeTier; FEE_TIER_COUNT],
+    /// Creators scoring below this must use a third-party resolver (0 = no limits)
+    pub min_creator_score_bps: u16,
+    /// Pool cap on markets from creators below min_creator_score_bps (0 = uncapped)
+    pub low_score_max_pool: u64,
+    /// Lamports a creator posts with each create_market, returned on timely resolution
+    pub creation_bond: u64,
+    /// Non-refundable fee paid to the treasury by create_market
+    pub creation_fee: u64,
+    /// Holders of this NFT mint create markets without the creation fee
+    pub creator_pass_mint: Option<Pubkey>,
+    /// Creators scoring at least this skip the creation fee (0 = no reputation waiver)
+    pub fee_waiver_score_bps: u16,
+    /// Lamports staked with each flag; slashed if the moderator dismisses the flags
+    pub flag_stake: u64,
+    /// Flags that freeze betting on a market until the moderator reviews it
+    pub flag_threshold: u32,
+    /// Overrides indexed by MarketCategory
+    pub category_params: [CategoryParams; CATEGORY_COUNT],
+    /// SPL stake pool LST-yield markets deposit into (default = disabled)
+    pub lst_stake_pool: Pubkey,
+    /// Recipient of opted-in winnings donations (default = none configured)
+    pub charity: Pubkey,
+    /// Fee on stakes matched against lay offers, paid by the backer on top of the stake
+    pub taker_fee_bps: u16,
+    /// Part of the taker fee credited to the lay offer's maker; the rest goes to the treasury
+    pub maker_rebate_bps: u16,
+    /// Share of a bet's protocol fee owed to the frontend it was placed through
+    pub frontend_fee_share_bps: u16,
+    /// Wait after resolution before winnings can be claimed; disputes close when claims
+    /// open, so a shorter delay also shortens the dispute window
+    pub claim_delay: i64,
+}
+
+impl Config {
+    /// Fee rate for a bettor with `volume` over the last 30 days, capped at the market's rate
+    pub fn tiered_fee_bps(&self, volume: u64, market_fee_bps: u16) -> u16 {
+        self.fee_tiers
+            .iter()
+            .filter(|tier| tier.min_volume > 0 && volume >= tier.min_volume)
+            .map(|tier| tier.fee_bps)
+            .min()
+            .unwrap_or(market_fee_bps)
+            .min(market_fee_bps)
+    }
+}
+
+impl Config {
+    pub fn category_params(&self, category: MarketCategory) -> &CategoryParams {
+        &self.category_params[category as usize]
+    }
+
+    /// Current protocol fee rate for a category
+    pub fn category_fee_bps(&self, category: MarketCategory) -> u16 {
+        let params = self.category_params(category);
+        if params.fee_override {
+            params.fee_bps
+        } else {
+            self.protocol_fee_bps
+        }
+    }
+}
+
+pub const FEE_TIER_COUNT: usize = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub struct FeeTier {
+    pub min_volume: u64,
+    pub fee_bps: u16,
+}
+
+pub const CATEGORY_COUNT: usize = 6;
+
+/// Per-category overrides of the global market parameters; zero values defer to the config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub struct CategoryParams {
+    /// Minimum stake (0 = config.min_stake)
+    pub min_stake: u64,
+    /// Longest time from creation to deadline (0 = unlimited)
+    pub max_duration: i64,
+    /// Protocol fee used instead of config.protocol_fee_bps when fee_override is set
+    pub fee_bps: u16,
+    pub fee_override: bool,
+    /// Markets must settle from a bound oracle rather than the creator
+    pub oracle_required: bool,
+}
+
+impl CategoryParams {
+    pub fn validate(&self, config: &Config) -> Result<()> {
+        if self.fee_override {
+            // Once governance is live, fees only change through proposals
+            require!(
+                config.governance_mint == Pubkey::default(),
+                PredictDuelError::GovernanceActive
+            );
+            require!(
+                self.fee_bps <= MAX_PROTOCOL_FEE_BPS,
+                PredictDuelError::InvalidProtocolFee
+            );
+        }
+        require!(self.max_duration >= 0, PredictDuelError::InvalidDeadline);
+        Ok(())
+    }
+}
+
+/// Admin-curated registry entry for a legitimate oracle feed
+#[account]
+#[derive(InitSpace)]
+pub struct OracleFeed {
+    pub feed: Pubkey,
+    pub provider: OracleProvider,
+    #[max_len(32)]
+    pub asset_name: String,
+    pub active: bool,
+    pub added_at: i64,
+    pub bump: u8,
+    /// Seconds past expiry the feed's settling update may be published, about one heartbeat
+    pub max_age: i64,
+}
+
+/// Final state of a game as published by a registered sports reporter
+#[account]
+#[derive(InitSpace)]
+pub struct GameResult {
+    pub reporter: Pubkey,
+    pub game_id: u64,
+    pub status: GameStatus,
+    pub home_score: u32,
+    pub away_score: u32,
+    pub reported_at: i64,
+    pub bump: u8,
+}
+
+/// Address barred from creating markets and betting; exists only while blocked
+#[account]
+#[derive(InitSpace)]
+pub struct BlocklistEntry {
+    pub address: Pubkey,
+    pub blocked_at: i64,
+    pub bump: u8,
+}
+
+/// Program-owned holder of protocol fees; withdrawals go through the timelock
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    pub bump: u8,
+}
+
+/// Admin action waiting out the timelock delay
+#[account]
+#[derive(InitSpace)]
+pub struct QueuedAction {
+    pub index: u64,
+    pub action: TimelockAction,
+    /// Earliest time the action can execute
+    pub eta: i64,
+    pub bump: u8,
+}
+
+/// Vault that turns protocol fees into platform token burns, capped per epoch
+#[account]
+#[derive(InitSpace)]
+pub struct Buyback {
+    pub platform_mint: Pubkey,
+    pub max_per_epoch: u64,
+    pub epoch_length: i64,
+    pub epoch_started_at: i64,
+    pub spent_this_epoch: u64,
+    pub total_spent: u64,
+    pub total_burned: u64,
+    pub bump: u8,
+}
+
+/// Platform token staking pool; holds protocol fee lamports owed to stakers
+#[account]
+#[derive(InitSpace)]
+pub struct StakingPool {
+    pub stake_mint: Pubkey,
+    pub total_staked: u64,
+    /// Fees per staked token, scaled by FEE_SHARE_PRECISION
+    pub acc_fee_per_share: u128,
+    /// Fees distributed but not yet claimed
+    pub owed: u64,
+    pub epoch_length: i64,
+    pub epoch_started_at: i64,
+    pub current_epoch: u64,
+    pub bump: u8,
+}
+
+/// Fees split across stakers when an epoch closed
+#[account]
+#[derive(InitSpace)]
+pub struct FeeEpoch {
+    pub epoch: u64,
+    pub fees: u64,
+    pub total_staked: u64,
+    pub closed_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakePosition {
+    pub staker: Pubkey,
+    pub amount: u64,
+    pub reward_debt: u128,
+    pub unclaimed: u64,
+    pub staked_epoch: u64,
+    pub bump: u8,
+    /// Jury votes awaiting a verdict; the stake cannot be withdrawn meanwhile
+    pub jury_votes: u16,
+}
+
+impl StakePosition {
+    /// Move fees accrued since the last update into `unclaimed`
+    pub fn accrue(&mut self, acc_fee_per_share: u128) -> Result<()> {
+        let accrued = (self.amount as u128)
+            .checked_mul(acc_fee_per_share)
+            .ok_or(PredictDuelError::MathOverflow)?
+            .saturating_sub(self.reward_debt)
+            / FEE_SHARE_PRECISION;
+        self.unclaimed += accrued as u64;
+        Ok(())
+    }
+}
+
+/// SPL reward emissions split across bettors by staked volume each epoch
+#[account]
+#[derive(InitSpace)]
+pub struct Rewards {
+    pub reward_mint: Pubkey,
+    pub emission_per_epoch: u64,
+    pub epoch_length: i64,
+    pub start_time: i64,
+    pub bump: u8,
+    /// Reward tokens paid at each check-in streak milestone
+    pub check_in_reward: u64,
+}
+
+impl Rewards {
+    pub fn epoch_at(&self, now: i64) -> u64 {
+        ((now - self.start_time) / self.epoch_length) as u64
+    }
+}
+
+/// Total volume recorded in one reward epoch
+#[account]
+#[derive(InitSpace)]
+pub struct RewardEpoch {
+    pub epoch: u64,
+    pub total_volume: u64,
+    pub bump: u8,
+}
+
+/// One bettor's recorded volume in a reward epoch
+#[account]
+#[derive(InitSpace)]
+pub struct UserEpochVolume {
+    pub bettor: Pubkey,
+    pub epoch: u64,
+    pub volume: u64,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+/// Token-weighted vote on a single config parameter change
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub change: GovernanceChange,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub voting_ends_at: i64,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+impl Proposal {
+    /// Governance tokens a wallet must hold to propose
+    pub fn threshold(supply: u64) -> u64 {
+        (supply as u128 * PROPOSAL_THRESHOLD_BPS as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Passed: a majority in favour, with at least GOVERNANCE_QUORUM_BPS of the supply voting
+    pub fn assert_passed(&self, supply: u64) -> Result<()> {
+        let turnout = self.yes_votes as u128 + self.no_votes as u128;
+        require!(
+            turnout * BPS_DENOMINATOR as u128 >= supply as u128 * GOVERNANCE_QUORUM_BPS as u128,
+            PredictDuelError::QuorumNotReached
+        );
+        require!(
+            self.yes_votes > self.no_votes,
+            PredictDuelError::ProposalRejected
+        );
+        Ok(())
+    }
+}
+
+/// Governance tokens a voter has locked on a proposal
+#[account]
+#[derive(InitSpace)]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+/// Shared pot for one market; the captain places a single bet on behalf of all members
+#[account]
+#[derive(InitSpace)]
+pub struct Syndicate {
+    pub market: Pubkey,
+    pub captain: Pubkey,
+    pub total_deposits: u64,
+    pub bet_placed: bool,
+    pub settled: bool,
+    /// Payout (or refund) returned to the pot, shared pro-rata by deposit
+    pub settled_amount: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SyndicateMember {
+    pub syndicate: Pubkey,
+    pub member: Pubkey,
+    pub deposit: u64,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+/// Owner-funded sub-account a manager may bet from (but never withdraw)
+#[account]
+#[derive(InitSpace)]
+pub struct ManagedAccount {
+    pub owner: Pubkey,
+    pub manager: Pubkey,
+    /// Manager's cut of each settled position's profit
+    pub performance_fee_bps: u16,
+    /// Idle lamports available for bets or owner withdrawal
+    pub balance: u64,
+    pub fees_paid: u64,
+    pub bump: u8,
+}
+
+/// Follower's standing authorization to mirror a leader's bets from an escrowed budget
+#[account]
+#[derive(InitSpace)]
+pub struct CopyConfig {
+    pub follower: Pubkey,
+    pub leader: Pubkey,
+    pub max_per_bet: u64,
+    pub max_total: u64,
+    pub total_copied: u64,
+    /// Escrowed lamports still available for mirrored bets
+    pub balance: u64,
+    pub bump: u8,
+}
+
+/// Visibility stake pool for a market; holds boost stakes and the boosters' fee share
+#[account]
+#[derive(InitSpace)]
+pub struct Boost {
+    pub market: Pubkey,
+    pub total_staked: u64,
+    /// Protocol fee lamports earned by boosters, split pro-rata by stake
+    pub fee_share: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BoostPosition {
+    pub market: Pubkey,
+    pub booster: Pubkey,
+    pub amount: u64,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+/// Bubblegum tree that position receipts and winner trophies are minted into
+#[account]
+#[derive(InitSpace)]
+pub struct ReceiptTree {
+    pub merkle_tree: Pubkey,
+    /// Receipt metadata URI prefix; the participant address is appended
+    #[max_len(MAX_RECEIPT_BASE_URI_LEN)]
+    pub base_uri: String,
+    pub bump: u8,
+}
+
+/// Deposit contract on another chain whose Wormhole messages are trusted
+#[account]
+#[derive(InitSpace)]
+pub struct ForeignEmitter {
+    /// Wormhole chain id
+    pub chain: u16,
+    /// Emitter address, left-padded to 32 bytes
+    pub address: [u8; 32],
+    pub bump: u8,
+}
+
+/// Consumed cross-chain message, keyed by (emitter chain, sequence)
+#[account]
+#[derive(InitSpace)]
+pub struct VaaReceipt {
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub received_at: i64,
+    pub bump: u8,
+}
+
+/// Groups related markets (e.g. "Super Bowl LX") under one result entry
+#[account]
+#[derive(InitSpace)]
+pub struct Event {
+    pub authority: Pubkey,
+    pub event_index: u64,
+    #[max_len(64)]
+    pub name: String,
+    pub market_count: u32,
+    pub resolved_market_count: u32,
+    /// Combined final pool size of attached markets settled from this event
+    pub total_volume: u64,
+    pub result: Option<EventResult>,
+    pub resolved_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct VestingSchedule {
+    pub market: Pubkey,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub released_amount: u64,
+    pub start_ts: i64,
+    pub duration: i64,
+    pub bump: u8,
+}
+
+impl VestingSchedule {
+    /// Linearly vested amount not yet released at `now`
+    pub fn releasable(&self, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(self.start_ts).clamp(0, self.duration);
+        let vested = (self.total_amount as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(PredictDuelError::MathOverflow)?
+            .checked_div(self.duration as u128)
+            .ok_or(PredictDuelError::MathOverflow)? as u64;
+        Ok(vested.saturating_sub(self.released_amount))
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct OddsHistory {
+    pub market: Pubkey,
+    /// Index the next snapshot will be written to
+    pub head: u8,
+    /// Number of valid snapshots (saturates at ODDS_HISTORY_LEN)
+    pub count: u8,
+    pub snapshots: [OddsSnapshot; ODDS_HISTORY_LEN],
+    pub bump: u8,
+}
+
+impl OddsHistory {
+    /// Append a snapshot, overwriting the oldest once the buffer is full
+    pub fn record(&mut self, timestamp: i64, yes_pool: u64, no_pool: u64) {
+        self.snapshots[self.head as usize] = OddsSnapshot {
+            timestamp,
+            yes_pool,
+            no_pool,
+        };
+        self.head = ((self.head as usize + 1) % ODDS_HISTORY_LEN) as u8;
+        if (self.count as usize) < ODDS_HISTORY_LEN {
+            self.count += 1;
+        }
+    }
+}
+
+/// Live activity feed: the market's last RECENT_BETS_LEN bets
+#[account]
+#[derive(InitSpace)]
+pub struct RecentBets {
+    pub market: Pubkey,
+    /// Index the next bet will be written to
+    pub head: u8,
+    /// Number of valid entries (saturates at RECENT_BETS_LEN)
+    pub count: u8,
+    pub bets: [RecentBet; RECENT_BETS_LEN],
+    pub bump: u8,
+}
+
+impl RecentBets {
+    /// Append a bet, overwriting the oldest once the buffer is full
+    pub fn record(&mut self, bettor: Pubkey, prediction: bool, amount: u64, timestamp: i64) {
+        self.bets[self.head as usize] = RecentBet {
+            bettor,
+            prediction,
+            amount,
+            timestamp,
+        };
+        self.head = ((self.head as usize + 1) % RECENT_BETS_LEN) as u8;
+        if (self.count as usize) < RECENT_BETS_LEN {
+            self.count += 1;
+        }
+    }
+}
+
+/// Exchange-style lay: the layer bets against `outcome` with a capped, escrowed liability
+#[account]
+#[derive(InitSpace)]
+pub struct LayOffer {
+    pub market: Pubkey,
+    pub layer: Pubkey,
+    /// Outcome being laid (the layer wins if it does NOT happen)
+    pub outcome: bool,
+    /// Decimal odds offered to backers, in bps
+    pub odds_bps: u32,
+    /// Liability currently escrowed in the vault
+    pub liability: u64,
+    pub matched_liability: u64,
+    /// Backers' stakes matched against this offer
+    pub matched_stake: u64,
+    pub settled: bool,
+    /// Fee rates snapshotted from the config when the offer was posted
+    pub taker_fee_bps: u16,
+    pub maker_rebate_bps: u16,
+    /// Rebates earned on matched stakes, not yet claimed
+    pub rebates_accrued: u64,
+    /// Time the offer stops matching (None = good until betting closes)
+    pub expires_at: Option<i64>,
+    /// Number of take_lay matches against this offer
+    pub fill_count: u32,
+    pub bump: u8,
+}
+
+impl LayOffer {
+    /// Liability still open to be matched
+    pub fn remaining_liability(&self) -> u64 {
+        self.liability - self.matched_liability
+    }
+
+    pub fn is_expired(&self, now: i64, betting_closes_at: i64) -> bool {
+        now >= self.expires_at.unwrap_or(betting_closes_at)
+    }
+
+    /// What the vault owes the layer once the market has settled
+    pub fn layer_settlement(&self, market: &Market, now: i64) -> Result<u64> {
+        require!(market.lst_tokens == 0, PredictDuelError::LstNotUnwound);
+        match market.status {
+            MarketStatus::Resolved => {
+                market.assert_claims_open(now)?;
+                if market.outcome == Some(self.outcome) {
+                    // Laid outcome happened: backers are paid, layer keeps only unmatched liability
+                    Ok(self.remaining_liability())
+                } else {
+                    // Laid outcome failed: layer keeps the liability and wins the backers' stakes
+                    Ok(self.liability + self.matched_stake)
+                }
+            }
+            MarketStatus::Cancelled | MarketStatus::Voided => Ok(self.liability),
+            _ => err!(PredictDuelError::MarketNotResolved),
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BackPosition {
+    pub offer: Pubkey,
+    pub backer: Pubkey,
+    pub stake: u64,
+    pub matched_liability: u64,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+impl BackPosition {
+    /// What the vault owes the backer once the market has settled: stake plus matched
+    /// liability if the backed outcome won, the stake alone on a refund
+    pub fn settlement(&self, offer: &LayOffer, market: &Market, now: i64) -> Result<u64> {
+        require!(market.lst_tokens == 0, PredictDuelError::LstNotUnwound);
+        match market.status {
+            MarketStatus::Resolved => {
+                market.assert_claims_open(now)?;
+                let outcome = market.outcome.ok_or(PredictDuelError::NoOutcome)?;
+                require!(outcome == offer.outcome, PredictDuelError::NotAWinner);
+                Ok(self.stake + self.matched_liability)
+            }
+            MarketStatus::Cancelled | MarketStatus::Voided => Ok(self.stake),
+            _ => err!(PredictDuelError::MarketNotResolved),
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ExitOrder {
+    pub market: Pubkey,
+    pub participant: Pubkey,
+    pub bettor: Pubkey,
+    pub trigger_yes_prob_bps: u16,
+    pub trigger_above: bool,
+    pub bump: u8,
+    /// The profile's co-signer approved the order, so it executes above the co-sign threshold
+    pub co_signed: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct OddsSnapshot {
+    pub timestamp: i64,
+    pub yes_pool: u64,
+    pub no_pool: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct RecentBet {
+    pub bettor: Pubkey,
+    pub prediction: bool,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TwapQuote {
+    pub twap_yes_prob_bps: u16,
+    pub spot_yes_prob_bps: u16,
+    pub window_start: i64,
+    /// Time of the last pool change folded into the accumulator
+    pub last_update: i64,
+    pub staleness_secs: i64,
+}
+
+/// Reported event result, e.g. home and away score (units are up to the event authority)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct EventResult {
+    pub primary: i64,
+    pub secondary: i64,
+}
+
+// Enums
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum MarketCategory {
+    Crypto,
+    Weather,
+    Sports,
+    Meme,
+    Local,
+    Other,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum MarketType {
+    Public,
+    Challenge,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum MarketStatus {
+    Pending,
+    Active,
     Resolved,
     Cancelled,
+    /// Settled with every stake returned (e.g. a pushed duel)
+    Voided,
+    /// Frozen by the admin; stakes can be force-refunded after EMERGENCY_REFUND_DELAY
+    Halted,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum SettlementMode {
+    /// Winners split the whole pool pro-rata
+    Parimutuel,
+    /// 1v1 duel: the single winner takes the entire pot
+    WinnerTakesAll,
+    /// Creator is the book: payouts are fixed at bet time against locked collateral
+    FixedOdds,
+    /// Randomly drawn correct predictors split the whole pot
+    Raffle,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PayoutMode {
+    /// Winners claim their own payouts
+    Pull,
+    /// A keeper pays winners with settle_batch right after resolution
+    Push,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum OracleProvider {
+    Pyth,
+    Switchboard,
+    Chainlink,
+    /// Wallet key that signs final game results into GameResult accounts
+    SportsReporter,
+}
+
+impl OracleProvider {
+    /// Program that owns this provider's feed accounts
+    pub fn program_id(&self) -> Pubkey {
+        match self {
+            OracleProvider::Pyth => PYTH_RECEIVER_PROGRAM_ID,
+            OracleProvider::Switchboard => SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+            OracleProvider::Chainlink => CHAINLINK_STORE_PROGRAM_ID,
+            OracleProvider::SportsReporter => anchor_lang::system_program::ID,
+        }
+    }
+
+    /// Whether read_oracle_price can decode this provider's accounts
+    pub fn is_price_feed(&self) -> bool {
+        match self {
+            OracleProvider::Pyth | OracleProvider::Switchboard | OracleProvider::Chainlink => true,
+            OracleProvider::SportsReporter => false,
+        }
+    }
+
+    /// Registry max age used when the admin passes 0: a little over the provider's usual
+    /// heartbeat (Chainlink on Solana publishes every few minutes to an hour)
+    pub fn default_max_age(&self) -> i64 {
+        match self {
+            OracleProvider::Pyth => 60,
+            OracleProvider::Switchboard => 10 * 60,
+            OracleProvider::Chainlink => 60 * 60,
+            OracleProvider::SportsReporter => 0,
+        }
+    }
+
+    /// The max age a feed of this provider is registered with, 0 picking the default
+    pub fn feed_max_age(&self, max_age: i64) -> Result<i64> {
+        let max_age = if max_age == 0 {
+            self.default_max_age()
+        } else {
+            max_age
+        };
+        require!(
+            (max_age > 0 || !self.is_price_feed()) && (0..=MAX_FEED_MAX_AGE).contains(&max_age),
+            PredictDuelError::InvalidFeedMaxAge
+        );
+        Ok(max_age)
+    }
+}
+
+/// Sensitive admin operation that must wait out the timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum TimelockAction {
+    SetProtocolFee {
+        protocol_fee_bps: u16,
+        treasury: Pubkey,
+    },
+    WithdrawTreasury {
+        amount: u64,
+        recipient: Pubkey,
+    },
+    RotateAdmin {
+        new_admin: Pubkey,
+    },
+    SetTimelockDelay {
+        delay: i64,
+    },
+    SetCategoryParams {
+        category: MarketCategory,
+        params: CategoryParams,
+    },
+    SetCreationFee {
+        creation_fee: u64,
+        creator_pass_mint: Option<Pubkey>,
+        fee_waiver_score_bps: u16,
+    },
+    SetOrderBookFees {
+        taker_fee_bps: u16,
+        maker_rebate_bps: u16,
+    },
+    SetFrontendFeeShare {
+        share_bps: u16,
+    },
+    /// Change passed by a governance proposal
+    Governance(GovernanceChange),
+}
+
+impl TimelockAction {
+    pub fn validate(&self, config: &Config) -> Result<()> {
+        match *self {
+            TimelockAction::SetProtocolFee {
+                protocol_fee_bps, ..
+            } => {
+                // Once governance is live, the fee only changes through proposals
+                require!(
+                    config.governance_mint == Pubkey::default(),
+                    PredictDuelError::GovernanceActive
+                );
+                require!(
+                    protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS,
+                    PredictDuelError::InvalidProtocolFee
+                );
+            }
+            TimelockAction::SetTimelockDelay { delay } => {
+                require!(
+                    (MIN_TIMELOCK_DELAY..=MAX_TIMELOCK_DELAY).contains(&delay),
+                    PredictDuelError::InvalidTimelockDelay
+                );
+            }
+            TimelockAction::SetCategoryParams { params, .. } => params.validate(config)?,
+            TimelockAction::SetCreationFee {
+                creation_fee,
+                fee_waiver_score_bps,
+                ..
+            } => {
+                require!(
+                    creation_fee <= MAX_CREATION_FEE,
+                    PredictDuelError::InvalidCreationFee
+                );
+                require!(
+                    fee_waiver_score_bps as u64 <= BPS_DENOMINATOR,
+                    PredictDuelError::InvalidCreatorScore
+                );
+            }
+            TimelockAction::SetOrderBookFees {
+                taker_fee_bps,
+                maker_rebate_bps,
+            } => {
+                require!(
+                    taker_fee_bps <= MAX_TAKER_FEE_BPS && maker_rebate_bps <= taker_fee_bps,
+                    PredictDuelError::InvalidOrderBookFees
+                );
+            }
+            TimelockAction::SetFrontendFeeShare { share_bps } => {
+                require!(
+                    share_bps <= MAX_FRONTEND_FEE_SHARE_BPS,
+                    PredictDuelError::InvalidFrontendFeeShare
+                );
+            }
+            TimelockAction::Governance(change) => change.validate(config)?,
+            TimelockAction::WithdrawTreasury { .. } | TimelockAction::RotateAdmin { .. } => {}
+        }
+        Ok(())
+    }
+}
+
+/// Config parameter a governance proposal sets
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum GovernanceChange {
+    ProtocolFeeBps(u16),
+    MinStake(u64),
+    DisputeWindow(i64),
+    /// Publish the snapshot root of an airdrop distributor
+    AirdropRoot {
+        distributor_id: u64,
+        root: [u8; 32],
+    },
+}
+
+impl GovernanceChange {
+    /// Same bounds the admin setters enforce. The dispute window can't drop below the claim
+    /// delay, or winnings would pay out while a dispute can still be opened.
+    pub fn validate(&self, config: &Config) -> Result<()> {
+        let valid = match *self {
+            GovernanceChange::ProtocolFeeBps(value) => value <= MAX_PROTOCOL_FEE_BPS,
+            GovernanceChange::MinStake(value) => value > 0,
+            GovernanceChange::DisputeWindow(value) => {
+                value > 0 && (config.claim_delay..=MAX_DISPUTE_WINDOW).contains(&value)
+            }
+            GovernanceChange::AirdropRoot { root, .. } => root != [0; 32],
+        };
+        require!(valid, PredictDuelError::InvalidGovernanceChange);
+        Ok(())
+    }
+
+    /// Write the change into the config, or publish the airdrop root
+    pub fn apply(
+        &self,
+        config: &mut Config,
+        distributor: Option<&mut MerkleDistributor>,
+    ) -> Result<()> {
+        match *self {
+            GovernanceChange::ProtocolFeeBps(value) => config.protocol_fee_bps = value,
+            GovernanceChange::MinStake(value) => config.min_stake = value,
+            GovernanceChange::DisputeWindow(value) => config.dispute_window = value,
+            GovernanceChange::AirdropRoot {
+                distributor_id,
+                root,
+            } => {
+                let distributor = distributor.ok_or(PredictDuelError::DistributorRequired)?;
+                require!(
+                    distributor.distributor_id == distributor_id,
+                    PredictDuelError::DistributorRequired
+                );
+                distributor.publish_root(root)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum GameStatus {
+    Final,
+    Postponed,
+    Cancelled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PriceComparison {
+    Above,
+    Below,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PriceCondition {
+    pub comparison: PriceComparison,
+    /// Strike as a fixed-point value: strike_price x 10^strike_expo
+    pub strike_price: i64,
+    pub strike_expo: i32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Rounding {
+    HalfUp,
+    Down,
+    Up,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct WeatherCondition {
+    pub comparison: PriceComparison,
+    /// Threshold in units of 10^-decimals (e.g. 255 with 1 decimal = 25.5)
+    pub threshold: i64,
+    /// Rounded readings this close to the threshold void the market
+    pub tolerance: u32,
+    /// Decimal places the reading is rounded to before comparison
+    pub decimals: u8,
+    pub rounding: Rounding,
+    /// Seconds past the deadline the settling reading may be published, for feeds that
+    /// update every few minutes to hours (0 = the feed's max age)
+    pub max_reading_age: i64,
+}
+
+impl WeatherCondition {
+    /// How far past the deadline the settling reading may be published
+    pub fn reading_max_age(&self, oracle_feed: &OracleFeed) -> i64 {
+        if self.max_reading_age > 0 {
+            self.max_reading_age
+        } else {
+            oracle_feed.max_age
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct GameCondition {
+    /// Reporter-assigned game id
+    pub game_id: u64,
+    /// Applied to (home score, away score)
+    pub condition: EventCondition,
+}
+
+/// Rule deriving an attached market's outcome from its event result
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub enum EventCondition {
+    /// YES if primary > threshold (e.g. "home team scores over 24.5")
+    PrimaryAbove { threshold: i64 },
+    /// YES if primary + secondary > threshold (e.g. total points over/under)
+    TotalAbove { threshold: i64 },
+    /// YES if primary - secondary > threshold (e.g. moneyline at 0, spreads otherwise)
+    MarginAbove { threshold: i64 },
+}
+
+impl EventCondition {
+    /// Outcome for the result, or None when it lands exactly on the line (push)
+    pub fn evaluate(&self, result: &EventResult) -> Option<bool> {
+        let (value, threshold) = match self {
+            EventCondition::PrimaryAbove { threshold } => (result.primary, *threshold),
+            EventCondition::TotalAbove { threshold } => {
+                (result.primary.saturating_add(result.secondary), *threshold)
+            }
+            EventCondition::MarginAbove { threshold } => {
+                (result.primary.saturating_sub(result.secondary), *threshold)
+            }
+        };
+        match value.cmp(&threshold) {
+            std::cmp::Ordering::Greater => Some(true),
+            std::cmp::Ordering::Less => Some(false),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+/// Decimal odds must pay back more than the stake and stay under the cap
+pub fn valid_fixed_odds(odds_bps: u32) -> bool {
+    odds_bps as u64 > BPS_DENOMINATOR && odds_bps <= MAX_FIXED_ODDS_BPS
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum FlagReason {
+    Ambiguous,
+    RuleBreaking,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum FlagReview {
+    Pending,
+    Upheld,
+    Dismissed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DisputeStatus {
+    AwaitingJury,
+    Voting,
+    /// Jury kept the resolution; the bond went to the treasury
+    Upheld,
+    /// Jury reversed the resolution; the bond was refunded
+    Overturned,
+    /// No jury was drawn in time; the bond was refunded
+    Lapsed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum SubscriptionKind {
+    Market,
+    Creator,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum QuestObjective {
+    /// Bet in this many distinct categories
+    BetCategories(u8),
+    /// Win this many Challenge markets
+    WinDuels(u8),
+    /// Stake this many lamports in total
+    Volume(u64),
+}
+
+impl QuestObjective {
+    /// Non-zero and reachable within MAX_QUEST_POSITIONS positions
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            QuestObjective::BetCategories(count) => count > 0 && count as usize <= CATEGORY_COUNT,
+            QuestObjective::WinDuels(count) => count > 0 && count as usize <= MAX_QUEST_POSITIONS,
+            QuestObjective::Volume(lamports) => lamports > 0,
+        }
+    }
+}
+
+// Events
+#[event]
+pub struct BuybackExecuted {
+    pub sol_spent: u64,
+    pub tokens_burned: u64,
+    pub spent_this_epoch: u64,
+    pub total_burned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketHalted {
+    pub market: Pubkey,
+    pub admin: Pubkey,
+    pub halted_at: i64,
+    pub refundable_at: i64,
+}
+
+#[event]
+pub struct EmergencyRefund {
+    pub market: Pubkey,
+    pub admin: Pubkey,
+    pub refunded: u32,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AddressBlocked {
+    pub address: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AddressUnblocked {
+    pub address: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketDelisted {
+    pub market: Pubkey,
+    pub moderator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketRelisted {
+    pub market: Pubkey,
+    pub moderator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketVoidedByModerator {
+    pub market: Pubkey,
+    pub moderator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketCancelledByCreator {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub participants: u32,
+    pub bond_forfeited: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketRolledOver {
+    pub previous: Pubkey,
+    pub next: Pubkey,
+    pub deadline: i64,
+    pub migrated_seed: u64,
+}
+
+#[event]
+pub struct DisputeFinalized {
+    pub market: Pubkey,
+    pub status: DisputeStatus,
+    pub outcome: Option<bool>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompressedBetPlaced {
+    pub market: Pubkey,
+    pub bettor: Pubkey,
+    pub leaf_index: u32,
+    pub prediction: bool,
+    pub stake: u64,
+    pub weight: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchSettled {
+    pub market: Pubkey,
+    pub keeper: Pubkey,
+    pub settled: u32,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchRefunded {
+    pub market: Pubkey,
+    pub keeper: Pubkey,
+    pub refunded: u32,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LayFilled {
+    pub market: Pubkey,
+    pub offer: Pubkey,
+    pub backer: Pubkey,
+    pub stake: u64,
+    pub liability: u64,
+    pub remaining_liability: u64,
+    pub timestamp: i64,
+}
+
+// Oracles
+/// Largest decimal exponent accepted for strikes and oracle prices
+pub const MAX_PRICE_EXPO: u32 = 18;
+/// Anchor discriminator of Pyth's PriceUpdateV2 account
+const PYTH_PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+/// Anchor discriminator of Switchboard On-Demand's PullFeedAccountData account
+const SWITCHBOARD_PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+/// PullFeedAccountData offsets (after the discriminator): last_update_timestamp and result.value
+const SWITCHBOARD_LAST_UPDATE_OFFSET: usize = 8 + 2208;
+const SWITCHBOARD_RESULT_VALUE_OFFSET: usize = 8 + 2256;
+/// Switchboard results are fixed-point with 18 decimals
+const SWITCHBOARD_EXPO: i32 = -18;
+/// Anchor discriminator of the Chainlink store's Transmissions account
+const CHAINLINK_TRANSMISSIONS_DISCRIMINATOR: [u8; 8] = [96, 179, 69, 66, 128, 129, 73, 117];
+/// Header version whose layout the offsets below describe
+const CHAINLINK_TRANSMISSIONS_VERSION: u8 = 2;
+/// Chainlink store feed layout: 8-byte discriminator, packed header, then the live ring buffer
+const CHAINLINK_VERSION_OFFSET: usize = 8;
+const CHAINLINK_DECIMALS_OFFSET: usize = 8 + 130;
+const CHAINLINK_LATEST_ROUND_OFFSET: usize = 8 + 135;
+const CHAINLINK_LIVE_LENGTH_OFFSET: usize = 8 + 140;
+const CHAINLINK_LIVE_CURSOR_OFFSET: usize = 8 + 144;
+const CHAINLINK_HEADER_SIZE: usize = 192;
+/// Transmission: slot u64, timestamp u32, padding u32, answer i128, padding [u64; 2]
+const CHAINLINK_TRANSMISSION_SIZE: usize = 48;
+
+/// Price normalized across oracle providers: price x 10^expo
+pub struct OraclePrice {
+    pub price: i128,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+/// Read the latest price from a feed account owned by the provider's program.
+/// Every price backend returns the same normalized reading so resolution logic is shared.
+pub fn read_oracle_price(provider: OracleProvider, feed: &AccountInfo) -> Result<OraclePrice> {
+    require_keys_eq!(
+        *feed.owner,
+        provider.program_id(),
+        PredictDuelError::InvalidOracleFeed
+    );
+    let data = feed.try_borrow_data()?;
+    match provider {
+        OracleProvider::Pyth => read_pyth_price(&data),
+        OracleProvider::Switchboard => read_switchboard_value(&data),
+        OracleProvider::Chainlink => read_chainlink_price(&data),
+        OracleProvider::SportsReporter => err!(PredictDuelError::InvalidOracleFeed),
+    }
+}
+
+/// Decode a Pyth pull-oracle PriceUpdateV2 account, accepting only fully verified updates
+fn read_pyth_price(data: &[u8]) -> Result<OraclePrice> {
+    require!(
+        data.get(..8) == Some(&PYTH_PRICE_UPDATE_V2_DISCRIMINATOR[..]),
+        PredictDuelError::InvalidOracleFeed
+    );
+    // Skip discriminator and write_authority
+    let mut offset = 8 + 32;
+    // VerificationLevel: 0 = Partial { num_signatures }, 1 = Full
+    require!(
+        data.get(offset) == Some(&1),
+        PredictDuelError::OracleNotVerified
+    );
+    offset += 1;
+    // PriceFeedMessage: feed_id, price, conf, exponent, publish_time, ...
+    offset += 32;
+    let price = read_i64(data, offset)?;
+    offset += 8 + 8;
+    let expo = i32::from_le_bytes(
+        data.get(offset..offset + 4)
+            .ok_or(PredictDuelError::InvalidOracleFeed)?
+            .try_into()
+            .unwrap(),
+    );
+    offset += 4;
+    let publish_time = read_i64(data, offset)?;
+
+    require!(
+        expo.unsigned_abs() <= MAX_PRICE_EXPO,
+        PredictDuelError::InvalidOracleFeed
+    );
+
+    Ok(OraclePrice {
+        price: price as i128,
+        expo,
+        publish_time,
+    })
+}
+
+/// Decode the current aggregated result of a Switchboard On-Demand pull feed
+fn read_switchboard_value(data: &[u8]) -> Result<OraclePrice> {
+    require!(
+        data.get(..8) == Some(&SWITCHBOARD_PULL_FEED_DISCRIMINATOR[..]),
+        PredictDuelError::InvalidOracleFeed
+    );
+    let publish_time = read_i64(data, SWITCHBOARD_LAST_UPDATE_OFFSET)?;
+    let value = data
+        .get(SWITCHBOARD_RESULT_VALUE_OFFSET..SWITCHBOARD_RESULT_VALUE_OFFSET + 16)
+        .ok_or(PredictDuelError::InvalidOracleFeed)?;
+
+    Ok(OraclePrice {
+        price: i128::from_le_bytes(value.try_into().unwrap()),
+        expo: SWITCHBOARD_EXPO,
+        publish_time,
+    })
+}
+
+/// Decode the latest round of a Chainlink feed from the store's live ring buffer
+fn read_chainlink_price(data: &[u8]) -> Result<OraclePrice> {
+    require!(
+        data.get(..8) == Some(&CHAINLINK_TRANSMISSIONS_DISCRIMINATOR[..])
+            && data.get(CHAINLINK_VERSION_OFFSET) == Some(&CHAINLINK_TRANSMISSIONS_VERSION),
+        PredictDuelError::InvalidOracleFeed
+    );
+    require!(
+        read_u32(data, CHAINLINK_LATEST_ROUND_OFFSET)? > 0,
+        PredictDuelError::InvalidOracleFeed
+    );
+    let decimals = *data
+        .get(CHAINLINK_DECIMALS_OFFSET)
+        .ok_or(PredictDuelError::InvalidOracleFeed)?;
+    let live_length = read_u32(data, CHAINLINK_LIVE_LENGTH_OFFSET)? as usize;
+    let live_cursor = read_u32(data, CHAINLINK_LIVE_CURSOR_OFFSET)? as usize;
+    require!(
+        live_length > 0 && (decimals as u32) <= MAX_PRICE_EXPO,
+        PredictDuelError::InvalidOracleFeed
+    );
+
+    // The cursor points at the next slot to write, so the latest round sits just behind it
+    let latest = (live_cursor + live_length - 1) % live_length;
+    let offset = 8 + CHAINLINK_HEADER_SIZE + latest * CHAINLINK_TRANSMISSION_SIZE;
+    let timestamp = read_u32(data, offset + 8)?;
+    let answer = data
+        .get(offset + 16..offset + 32)
+        .ok_or(PredictDuelError::InvalidOracleFeed)?;
+
+    Ok(OraclePrice {
+        price: i128::from_le_bytes(answer.try_into().unwrap()),
+        expo: -(decimals as i32),
+        publish_time: timestamp as i64,
+    })
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    let bytes = data
+        .get(offset..offset + 4)
+        .ok_or(PredictDuelError::InvalidOracleFeed)?;
+    Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_i64(data: &[u8], offset: usize) -> Result<i64> {
+    let bytes = data
+        .get(offset..offset + 8)
+        .ok_or(PredictDuelError::InvalidOracleFeed)?;
+    Ok(i64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+/// Compare two fixed-point values with different exponents
+pub fn compare_price(
+    price: i128,
+    expo: i32,
+    strike: i64,
+    strike_expo: i32,
+) -> Result<std::cmp::Ordering> {
+    let min_expo = expo.min(strike_expo);
+    let scale = |value: i128, e: i32| -> Result<i128> {
+        10i128
+            .checked_pow((e - min_expo) as u32)
+            .and_then(|factor| value.checked_mul(factor))
+            .ok_or_else(|| error!(PredictDuelError::MathOverflow))
+    };
+    Ok(scale(price, expo)?.cmp(&scale(strike as i128, strike_expo)?))
+}
+
+/// Round a fixed-point reading (value x 10^expo) to `decimals` places, scaled by 10^decimals
+pub fn round_reading(value: i128, expo: i32, decimals: u8, rounding: Rounding) -> Result<i128> {
+    let shift = expo + decimals as i32;
+    let factor = 10i128
+        .checked_pow(shift.unsigned_abs())
+        .ok_or(PredictDuelError::MathOverflow)?;
+    if shift >= 0 {
+        return value
+            .checked_mul(factor)
+            .ok_or_else(|| error!(PredictDuelError::MathOverflow));
+    }
+
+    let rounded = match rounding {
+        Rounding::HalfUp => (value + factor / 2).div_euclid(factor),
+        Rounding::Down => value.div_euclid(factor),
+        Rounding::Up => -(-value).div_euclid(factor),
+    };
+    Ok(rounded)
+}
+
+// Wormhole
+/// PostedVAA layout: "vaa" magic, version, consistency, vaa_time, signature set,
+/// submission_time, nonce, then the fields below and a length-prefixed payload
+const POSTED_VAA_SEQUENCE_OFFSET: usize = 49;
+const POSTED_VAA_EMITTER_CHAIN_OFFSET: usize = 57;
+const POSTED_VAA_EMITTER_ADDRESS_OFFSET: usize = 59;
+const POSTED_VAA_PAYLOAD_OFFSET: usize = 91;
+/// Payload id of a cross-chain bet message
+const CROSS_CHAIN_BET_PAYLOAD_ID: u8 = 1;
+/// Payload id of a cross-chain payout message
+const CROSS_CHAIN_PAYOUT_PAYLOAD_ID: u8 = 2;
+/// Bridge config offset of the message fee: guardian_set_index u32, last_lamports u64, expiration u32
+const WORMHOLE_BRIDGE_FEE_OFFSET: usize = 4 + 8 + 4;
+/// Only publish once the claim transaction is finalized
+const WORMHOLE_CONSISTENCY_FINALIZED: u8 = 1;
+
+/// Fields of a verified VAA needed to authenticate a message
+pub struct PostedVaa {
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub sequence: u64,
+    pub payload: Vec<u8>,
+}
+
+/// Read a VAA the core bridge has verified and posted
+pub fn read_posted_vaa(account: &AccountInfo) -> Result<PostedVaa> {
+    require_keys_eq!(
+        *account.owner,
+        WORMHOLE_CORE_BRIDGE_PROGRAM_ID,
+        PredictDuelError::InvalidVaa
+    );
+    let data = account.try_borrow_data()?;
+    // Unverified outbound messages share the owner but carry a "msg" magic
+    require!(
+        data.get(..3) == Some(&b"vaa"[..]),
+        PredictDuelError::InvalidVaa
+    );
+
+    let field = |offset: usize, len: usize| -> Result<&[u8]> {
+        data.get(offset..offset + len)
+            .ok_or_else(|| error!(PredictDuelError::InvalidVaa))
+    };
+    let sequence = u64::from_le_bytes(field(POSTED_VAA_SEQUENCE_OFFSET, 8)?.try_into().unwrap());
+    let emitter_chain = u16::from_le_bytes(
+        field(POSTED_VAA_EMITTER_CHAIN_OFFSET, 2)?
+            .try_into()
+            .unwrap(),
+    );
+    let emitter_address: [u8; 32] = field(POSTED_VAA_EMITTER_ADDRESS_OFFSET, 32)?
+        .try_into()
+        .unwrap();
+    let payload_len =
+        u32::from_le_bytes(field(POSTED_VAA_PAYLOAD_OFFSET, 4)?.try_into().unwrap()) as usize;
+    let payload = field(POSTED_VAA_PAYLOAD_OFFSET + 4, payload_len)?.to_vec();
+
+    Ok(PostedVaa {
+        emitter_chain,
+        emitter_address,
+        sequence,
+        payload,
+    })
+}
+
+/// Bet message sent by a foreign deposit contract (big-endian, Wormhole convention):
+/// payload_id u8 | market [32] | bettor [32] | prediction u8 | stake_amount u64 | recipient [32]
+pub struct CrossChainBet {
+    pub market: Pubkey,
+    pub bettor: Pubkey,
+    pub prediction: bool,
+    pub stake_amount: u64,
+    /// Origin-chain address that receives payouts
+    pub recipient: [u8; 32],
+}
+
+impl CrossChainBet {
+    pub const LEN: usize = 1 + 32 + 32 + 1 + 8 + 32;
+
+    pub fn parse(payload: &[u8]) -> Result<Self> {
+        require!(
+            payload.len() == Self::LEN && payload[0] == CROSS_CHAIN_BET_PAYLOAD_ID,
+            PredictDuelError::InvalidVaa
+        );
+        Ok(CrossChainBet {
+            market: Pubkey::new_from_array(payload[1..33].try_into().unwrap()),
+            bettor: Pubkey::new_from_array(payload[33..65].try_into().unwrap()),
+            prediction: payload[65] != 0,
+            stake_amount: u64::from_be_bytes(payload[66..74].try_into().unwrap()),
+            recipient: payload[74..106].try_into().unwrap(),
+        })
+    }
+}
+
+/// Payout message authorizing the origin chain to release funds (big-endian):
+/// payload_id u8 | market [32] | recipient [32] | amount u64
+pub fn cross_chain_payout_payload(market: &Pubkey, recipient: &[u8; 32], amount: u64) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(1 + 32 + 32 + 8);
+    payload.push(CROSS_CHAIN_PAYOUT_PAYLOAD_ID);
+    payload.extend_from_slice(market.as_ref());
+    payload.extend_from_slice(recipient);
+    payload.extend_from_slice(&amount.to_be_bytes());
+    payload
+}
+
+/// Build the core bridge's post_message instruction (instruction index 1)
+pub fn wormhole_post_message_ix(
+    accounts: &ClaimCrossChain,
+    nonce: u32,
+    payload: Vec<u8>,
+) -> anchor_lang::solana_program::instruction::Instruction {
+    use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+    let mut data = Vec::with_capacity(1 + 4 + 4 + payload.len() + 1);
+    data.push(1);
+    data.extend_from_slice(&nonce.to_le_bytes());
+    data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    data.extend_from_slice(&payload);
+    data.push(WORMHOLE_CONSISTENCY_FINALIZED);
+
+    Instruction {
+        program_id: WORMHOLE_CORE_BRIDGE_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(accounts.wormhole_bridge.key(), false),
+            AccountMeta::new(accounts.wormhole_message.key(), true),
+            AccountMeta::new_readonly(accounts.wormhole_emitter.key(), true),
+            AccountMeta::new(accounts.wormhole_sequence.key(), false),
+            AccountMeta::new(accounts.relayer.key(), true),
+            AccountMeta::new(accounts.wormhole_fee_collector.key(), false),
+            AccountMeta::new_readonly(accounts.clock.key(), false),
+            AccountMeta::new_readonly(accounts.rent.key(), false),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data,
+    }
+}
+
+// Bubblegum
+/// Longest receipt URI prefix; leaves room for the appended participant address
+pub const MAX_RECEIPT_BASE_URI_LEN: usize = 150;
+const BUBBLEGUM_MINT_V1_DISCRIMINATOR: [u8; 8] = [145, 98, 192, 118, 184, 147, 118, 104];
+const BUBBLEGUM_BURN_DISCRIMINATOR: [u8; 8] = [116, 110, 29, 56, 107, 219, 42, 93];
+/// TreeConfig: discriminator, tree_creator, tree_delegate, total_mint_capacity, then num_minted
+const BUBBLEGUM_NUM_MINTED_OFFSET: usize = 8 + 32 + 32 + 8;
+const RECEIPT_SYMBOL: &str = "PDPOS";
+const TROPHY_SYMBOL: &str = "PDWIN";
+/// Bubblegum caps NFT names at 32 bytes
+const MAX_NFT_NAME_LEN: usize = 32;
+
+/// Longest prefix of `text` that fits an NFT name without splitting a character
+fn truncate_nft_name(text: &str) -> &str {
+    let mut end = text.len().min(MAX_NFT_NAME_LEN);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+fn push_borsh_string(buf: &mut Vec<u8>, value: &str) {
+    buf.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    buf.extend_from_slice(value.as_bytes());
+}
+
+/// Borsh-encoded Bubblegum MetadataArgs for a non-royalty, immutable NFT with no creators
+pub fn receipt_metadata(name: &str, symbol: &str, uri: &str) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(64 + uri.len());
+    push_borsh_string(&mut buf, name);
+    push_borsh_string(&mut buf, symbol);
+    push_borsh_string(&mut buf, uri);
+    buf.extend_from_slice(&0u16.to_le_bytes()); // seller_fee_basis_points
+    buf.push(0); // primary_sale_happened
+    buf.push(0); // is_mutable
+    buf.push(0); // edition_nonce: None
+    buf.extend_from_slice(&[1, 0]); // token_standard: Some(NonFungible)
+    buf.push(0); // collection: None
+    buf.push(0); // uses: None
+    buf.push(0); // token_program_version: Original
+    buf.extend_from_slice(&0u32.to_le_bytes()); // creators: []
+    buf
+}
+
+/// Mint a leaf owned (and paid for) by `owner`, signed by the receipt_authority tree delegate
+fn bubblegum_mint_v1_ix(
+    tree_config: Pubkey,
+    merkle_tree: Pubkey,
+    owner: Pubkey,
+    tree_delegate: Pubkey,
+    metadata: Vec<u8>,
+) -> anchor_lang::solana_program::instruction::Instruction {
+    use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+    let mut data = BUBBLEGUM_MINT_V1_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&metadata);
+
+    Instruction {
+        program_id: BUBBLEGUM_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(tree_config, false),
+            AccountMeta::new_readonly(owner, false), // leaf_owner
+            AccountMeta::new_readonly(owner, false), // leaf_delegate
+            AccountMeta::new(merkle_tree, false),
+            AccountMeta::new(owner, true), // payer
+            AccountMeta::new_readonly(tree_delegate, true),
+            AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ACCOUNT_COMPRESSION_PROGRAM_ID, false),
+            AccountMeta::new_readonly(anchor_lang::system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+fn bubblegum_burn_ix(
+    accounts: &ClaimWithReceipt,
+    proof: &[AccountInfo],
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+) -> anchor_lang::solana_program::instruction::Instruction {
+    use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+    let mut data = BUBBLEGUM_BURN_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&root);
+    data.extend_from_slice(&data_hash);
+    data.extend_from_slice(&creator_hash);
+    data.extend_from_slice(&nonce.to_le_bytes());
+    // mint_v1 leaves sit at index == nonce
+    data.extend_from_slice(&(nonce as u32).to_le_bytes());
+
+    let mut metas = vec![
+        AccountMeta::new_readonly(accounts.tree_config.key(), false),
+        AccountMeta::new_readonly(accounts.holder.key(), true), // leaf_owner
+        AccountMeta::new_readonly(accounts.leaf_delegate.key(), false),
+        AccountMeta::new(accounts.merkle_tree.key(), false),
+        AccountMeta::new_readonly(accounts.log_wrapper.key(), false),
+        AccountMeta::new_readonly(accounts.compression_program.key(), false),
+        AccountMeta::new_readonly(accounts.system_program.key(), false),
+    ];
+    metas.extend(
+        proof
+            .iter()
+            .map(|node| AccountMeta::new_readonly(node.key(), false)),
+    );
+
+    Instruction {
+        program_id: BUBBLEGUM_PROGRAM_ID,
+        accounts: metas,
+        data,
+    }
+}
+
+// Account compression
+const COMPRESSION_INIT_TREE_DISCRIMINATOR: [u8; 8] = [191, 11, 119, 7, 180, 107, 220, 110];
+const COMPRESSION_APPEND_DISCRIMINATOR: [u8; 8] = [149, 120, 18, 222, 236, 225, 88, 203];
+const COMPRESSION_REPLACE_LEAF_DISCRIMINATOR: [u8; 8] = [204, 165, 76, 100, 73, 147, 0, 128];
+
+/// Compressed position leaf: hash(market, bettor, side, stake, weight, claimed)
+pub fn position_leaf(
+    market: &Pubkey,
+    bettor: &Pubkey,
+    prediction: bool,
+    stake: u64,
+    weight: u64,
+    claimed: bool,
+) -> [u8; 32] {
+    hashv(&[
+        market.as_ref(),
+        bettor.as_ref(),
+        &[prediction as u8],
+        &stake.to_le_bytes(),
+        &weight.to_le_bytes(),
+        &[claimed as u8],
+    ])
+    .to_bytes()
+}
+
+fn compression_init_tree_ix(
+    merkle_tree: Pubkey,
+    authority: Pubkey,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> anchor_lang::solana_program::instruction::Instruction {
+    use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+    let mut data = COMPRESSION_INIT_TREE_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&max_depth.to_le_bytes());
+    data.extend_from_slice(&max_buffer_size.to_le_bytes());
+
+    Instruction {
+        program_id: ACCOUNT_COMPRESSION_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(merkle_tree, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+fn compression_append_ix(
+    merkle_tree: Pubkey,
+    authority: Pubkey,
+    leaf: [u8; 32],
+) -> anchor_lang::solana_program::instruction::Instruction {
+    use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+    let mut data = COMPRESSION_APPEND_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&leaf);
+
+    Instruction {
+        program_id: ACCOUNT_COMPRESSION_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(merkle_tree, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+/// Swap a leaf at `index`; fails unless `previous_leaf` is there under `root` and the proof
+fn compression_replace_leaf_ix(
+    merkle_tree: Pubkey,
+    authority: Pubkey,
+    proof: &[AccountInfo],
+    root: [u8; 32],
+    previous_leaf: [u8; 32],
+    new_leaf: [u8; 32],
+    index: u32,
+) -> anchor_lang::solana_program::instruction::Instruction {
+    use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+    let mut data = COMPRESSION_REPLACE_LEAF_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&root);
+    data.extend_from_slice(&previous_leaf);
+    data.extend_from_slice(&new_leaf);
+    data.extend_from_slice(&index.to_le_bytes());
+
+    let mut metas = vec![
+        AccountMeta::new(merkle_tree, false),
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+    ];
+    metas.extend(
+        proof
+            .iter()
+            .map(|node| AccountMeta::new_readonly(node.key(), false)),
+    );
+
+    Instruction {
+        program_id: ACCOUNT_COMPRESSION_PROGRAM_ID,
+        accounts: metas,
+        data,
+    }
+}
+
+// Stake pool
+const STAKE_POOL_DEPOSIT_SOL: u8 = 14;
+const STAKE_POOL_WITHDRAW_SOL: u8 = 16;
+
+/// DepositSol from the market vault; the market's own token account takes the referral fee
+fn stake_pool_deposit_sol_ix(
+    accounts: &DepositToLst,
+    lamports: u64,
+) -> anchor_lang::solana_program::instruction::Instruction {
+    use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![STAKE_POOL_DEPOSIT_SOL];
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    Instruction {
+        program_id: STAKE_POOL_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(accounts.stake_pool.key(), false),
+            AccountMeta::new_readonly(accounts.stake_pool_withdraw_authority.key(), false),
+            AccountMeta::new(accounts.reserve_stake.key(), false),
+            AccountMeta::new(accounts.market_vault.key(), true), // lamports_from
+            AccountMeta::new(accounts.market_lst.key(), false),  // pool_tokens_to
+            AccountMeta::new(accounts.manager_fee_account.key(), false),
+            AccountMeta::new(accounts.market_lst.key(), false), // referrer_pool_tokens
+            AccountMeta::new(accounts.pool_mint.key(), false),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+            AccountMeta::new_readonly(accounts.token_program.key(), false),
+        ],
+        data,
+    }
+}
+
+/// WithdrawSol of `pool_tokens` back into the market vault, which signs as token owner
+fn stake_pool_withdraw_sol_ix(
+    accounts: &UnwindLst,
+    pool_tokens: u64,
+) -> anchor_lang::solana_program::instruction::Instruction {
+    use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![STAKE_POOL_WITHDRAW_SOL];
+    data.extend_from_slice(&pool_tokens.to_le_bytes());
+
+    Instruction {
+        program_id: STAKE_POOL_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(accounts.stake_pool.key(), false),
+            AccountMeta::new_readonly(accounts.stake_pool_withdraw_authority.key(), false),
+            AccountMeta::new_readonly(accounts.market_vault.key(), true), // user_transfer_authority
+            AccountMeta::new(accounts.market_lst.key(), false),           // pool_tokens_from
+            AccountMeta::new(accounts.reserve_stake.key(), false),
+            AccountMeta::new(accounts.market_vault.key(), false), // lamports_to
+            AccountMeta::new(accounts.manager_fee_account.key(), false),
+            AccountMeta::new(accounts.pool_mint.key(), false),
+            AccountMeta::new_readonly(accounts.clock.key(), false),
+            AccountMeta::new_readonly(accounts.stake_history.key(), false),
+            AccountMeta::new_readonly(accounts.stake_program.key(), false),
+            AccountMeta::new_readonly(accounts.token_program.key(), false),
+        ],
+        data,
+    }
+}
+
+// Randomness
+/// Anchor discriminator of Switchboard On-Demand's RandomnessAccountData account
+const SWITCHBOARD_RANDOMNESS_DISCRIMINATOR: [u8; 8] = [10, 66, 229, 135, 220, 239, 217, 114];
+/// RandomnessAccountData offsets (after the discriminator): authority, queue and
+/// seed_slothash precede seed_slot; oracle sits between seed_slot and reveal_slot
+const RANDOMNESS_SEED_SLOT_OFFSET: usize = 8 + 96;
+const RANDOMNESS_REVEAL_SLOT_OFFSET: usize = 8 + 136;
+const RANDOMNESS_VALUE_OFFSET: usize = 8 + 144;
+
+/// Check a randomness account was committed in the previous slot and is not yet revealed,
+/// so its value was unknown to everyone when the caller bound it
+pub fn assert_randomness_committed(randomness: &AccountInfo, slot: u64) -> Result<()> {
+    let data = randomness_data(randomness)?;
+    require!(
+        read_randomness_slot(&data, RANDOMNESS_SEED_SLOT_OFFSET)? == slot.saturating_sub(1)
+            && read_randomness_slot(&data, RANDOMNESS_REVEAL_SLOT_OFFSET)? == 0,
+        PredictDuelError::RandomnessStale
+    );
+    Ok(())
+}
+
+/// Revealed value of a randomness account; like the Switchboard SDK, only readable in the
+/// reveal slot so it can't be inspected before being consumed
+pub fn read_randomness(randomness: &AccountInfo, slot: u64) -> Result<[u8; 32]> {
+    let data = randomness_data(randomness)?;
+    require!(
+        read_randomness_slot(&data, RANDOMNESS_REVEAL_SLOT_OFFSET)? == slot,
+        PredictDuelError::RandomnessNotRevealed
+    );
+    let value = data
+        .get(RANDOMNESS_VALUE_OFFSET..RANDOMNESS_VALUE_OFFSET + 32)
+        .ok_or(PredictDuelError::InvalidRandomness)?;
+    Ok(value.try_into().unwrap())
+}
+
+/// Uniform-enough index below `len` derived from a random value and a per-draw nonce
+pub fn random_index(value: &[u8; 32], nonce: u64, len: usize) -> usize {
+    let digest = hashv(&[value, &nonce.to_le_bytes()]).to_bytes();
+    (u64::from_le_bytes(digest[..8].try_into().unwrap()) % len as u64) as usize
+}
+
+fn randomness_data<'a>(randomness: &'a AccountInfo) -> Result<std::cell::Ref<'a, &'a mut [u8]>> {
+    require_keys_eq!(
+        *randomness.owner,
+        SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+        PredictDuelError::InvalidRandomness
+    );
+    let data = randomness.try_borrow_data()?;
+    require!(
+        data.get(..8) == Some(&SWITCHBOARD_RANDOMNESS_DISCRIMINATOR[..]),
+        PredictDuelError::InvalidRandomness
+    );
+    Ok(data)
+}
+
+fn read_randomness_slot(data: &[u8], offset: usize) -> Result<u64> {
+    let bytes = data
+        .get(offset..offset + 8)
+        .ok_or(PredictDuelError::InvalidRandomness)?;
+    Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+// Attestations
+/// Native ed25519 signature verification program
+pub const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+/// Attestation message: bettor (32) | status (1) | issued_at (i64 LE)
+pub const ATTESTATION_MESSAGE_LEN: usize = 32 + 1 + 8;
+/// Status the verifier signs for bettors cleared to bet (jurisdiction/KYC passed)
+pub const ATTESTATION_APPROVED: u8 = 1;
+/// Longest attestation lifetime the admin can allow - 1 day
+pub const MAX_ATTESTATION_AGE: i64 = 24 * 60 * 60;
+
+/// When the config demands attestations, the instruction before this one must be an
+/// ed25519 verification of a recent approval for `bettor`, signed by the verifier.
+/// The ed25519 program has already checked the signature by the time we run.
+pub fn verify_attestation(
+    config: &Config,
+    bettor: &Pubkey,
+    instructions: Option<&AccountInfo>,
+    now: i64,
+) -> Result<()> {
+    use anchor_lang::solana_program::sysvar::instructions::{
+        load_current_index_checked, load_instruction_at_checked,
+    };
+
+    if config.attestation_verifier == Pubkey::default() {
+        return Ok(());
+    }
+
+    let instructions = instructions.ok_or(PredictDuelError::AttestationRequired)?;
+    let current = load_current_index_checked(instructions)?;
+    require!(current > 0, PredictDuelError::AttestationRequired);
+    let ix = load_instruction_at_checked(current as usize - 1, instructions)?;
+    require!(
+        ix.program_id == ED25519_PROGRAM_ID,
+        PredictDuelError::AttestationRequired
+    );
+
+    let (signer, message) =
+        parse_ed25519_instruction(&ix.data).ok_or(PredictDuelError::InvalidAttestation)?;
+    require!(
+        signer == config.attestation_verifier.as_ref()
+            && message.len() == ATTESTATION_MESSAGE_LEN
+            && &message[..32] == bettor.as_ref()
+            && message[32] == ATTESTATION_APPROVED,
+        PredictDuelError::InvalidAttestation
+    );
+
+    let issued_at = i64::from_le_bytes(message[33..].try_into().unwrap());
+    require!(
+        issued_at <= now && now - issued_at <= config.attestation_max_age,
+        PredictDuelError::AttestationExpired
+    );
+    Ok(())
+}
+
+/// Public key and message of a single-signature ed25519 program instruction whose
+/// data all lives in that instruction
+fn parse_ed25519_instruction(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    // num_signatures (1) | padding (1) | signature_offset, signature_ix, public_key_offset,
+    // public_key_ix, message_offset, message_size, message_ix (u16 LE each)
+    if data.len() < 16 || data[0] != 1 {
+        return None;
+    }
+    let offset = |field: usize| u16::from_le_bytes([data[2 + 2 * field], data[3 + 2 * field]]);
+    if offset(1) != u16::MAX || offset(3) != u16::MAX || offset(6) != u16::MAX {
+        return None;
+    }
+
+    let public_key_start = offset(2) as usize;
+    let message_start = offset(4) as usize;
+    let public_key = data.get(public_key_start..public_key_start + 32)?;
+    let message = data.get(message_start..message_start + offset(5) as usize)?;
+    Some((public_key, message))
+}
+
+// Errors
+#[error_code]
+pub enum PredictDuelError {
+    #[msg("Question exceeds 200 characters")]
+    QuestionTooLong,
+    #[msg("Stake amount must be at least 0.01 SOL")]
+    StakeTooLow,
+    #[msg("Deadline must be in the future")]
+    InvalidDeadline,
+    #[msg("Market is not active")]
+    MarketNotActive,
+    #[msg("Market has expired")]
+    MarketExpired,
+    #[msg("Only the creator can resolve this market")]
+    UnauthorizedResolver,
+    #[msg("Market has not expired yet")]
+    MarketNotExpired,
+    #[msg("Market is not resolved yet")]
+    MarketNotResolved,
+    #[msg("Winnings already claimed")]
+    AlreadyClaimed,
+    #[msg("No outcome set for market")]
+    NoOutcome,
+    #[msg("You did not win this market")]
+    NotAWinner,
+    #[msg("Cannot cancel market with active participants")]
+    CannotCancel,
+    #[msg("Market is not cancelled")]
+    MarketNotCancelled,
+    #[msg("Only the market creator can perform this action")]
+    NotMarketCreator,
+    #[msg("Market has already been seeded")]
+    AlreadySeeded,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Payouts above the vesting threshold must be vested on this market")]
+    VestingRequired,
+    #[msg("Payout is below the vesting threshold")]
+    PayoutBelowVestingThreshold,
+    #[msg("Vesting duration is out of range")]
+    InvalidVestingDuration,
+    #[msg("Nothing to claim yet")]
+    NothingToClaim,
+    #[msg("Exit trigger must be between 0 and 10000 bps")]
+    InvalidExitTrigger,
+    #[msg("Exit condition has not been met")]
+    ExitNotTriggered,
+    #[msg("This market only accepts sealed bets")]
+    SealedMarket,
+    #[msg("This market does not use sealed bets")]
+    NotSealedMarket,
+    #[msg("Reveal window is out of range")]
+    InvalidRevealWindow,
+    #[msg("Reveal window has closed")]
+    RevealWindowClosed,
+    #[msg("Bet already revealed")]
+    AlreadyRevealed,
+    #[msg("Revealed bet does not match commitment")]
+    CommitmentMismatch,
+    #[msg("Early bonus exceeds the maximum")]
+    InvalidEarlyBonus,
+    #[msg("Additional bets must be on the same side as your position")]
+    SideMismatch,
+    #[msg("Settlement mode is not valid for this market")]
+    InvalidSettlementMode,
+    #[msg("This side of the duel is already taken")]
+    DuelSideTaken,
+    #[msg("Fixed odds must be above 1.0x and at most 100x")]
+    InvalidOdds,
+    #[msg("Bet exceeds the book's available collateral")]
+    BookExposureExceeded,
+    #[msg("Lay offer does not have enough unmatched liability")]
+    InsufficientLayLiability,
+    #[msg("Spread markets must be resolved from the final score")]
+    SpreadMarket,
+    #[msg("Market is not a Sports spread market")]
+    NotSpreadMarket,
+    #[msg("Spread is out of range")]
+    InvalidSpread,
+    #[msg("Event name exceeds 64 characters")]
+    EventNameTooLong,
+    #[msg("Market resolves from its event")]
+    EventMarket,
+    #[msg("Market is not attached to this event")]
+    EventMismatch,
+    #[msg("Event has already been resolved")]
+    EventAlreadyResolved,
+    #[msg("Event has not been resolved yet")]
+    EventNotResolved,
+    #[msg("Signer is not authorized for this action")]
+    Unauthorized,
+    #[msg("Asset name exceeds 32 characters")]
+    AssetNameTooLong,
+    #[msg("Oracle feed is not registered, inactive, or owned by the wrong program")]
+    InvalidOracleFeed,
+    #[msg("Market resolves from its oracle feed")]
+    OracleMarket,
+    #[msg("Market is not a price market")]
+    NotPriceMarket,
+    #[msg("Strike exponent is out of range")]
+    InvalidStrike,
+    #[msg("Oracle price was published before expiry")]
+    StalePrice,
+    #[msg("Oracle update is not fully verified")]
+    OracleNotVerified,
+    #[msg("Market is not a weather market")]
+    NotWeatherMarket,
+    #[msg("Market category does not support this resolution")]
+    InvalidCategory,
+    #[msg("Market is not bound to a game")]
+    NotGameMarket,
+    #[msg("Game result does not match the market's game")]
+    GameMismatch,
+    #[msg("Wormhole message is invalid or from an untrusted emitter")]
+    InvalidVaa,
+    #[msg("Position is settled on its origin chain")]
+    CrossChainPosition,
+    #[msg("Position was not opened cross-chain")]
+    NotCrossChainPosition,
+    #[msg("Position is represented by a receipt NFT; claim with the receipt")]
+    ReceiptHeld,
+    #[msg("Position has no valid receipt")]
+    InvalidReceipt,
+    #[msg("Receipt URI prefix is too long")]
+    ReceiptUriTooLong,
+    #[msg("Market is not featured")]
+    MarketNotFeatured,
+    #[msg("Trophy already minted")]
+    TrophyAlreadyMinted,
+    #[msg("Protocol fee exceeds the maximum")]
+    InvalidProtocolFee,
+    #[msg("Copy caps are invalid")]
+    InvalidCopyCaps,
+    #[msg("Bet already copied on this market")]
+    AlreadyCopied,
+    #[msg("Performance fee exceeds the maximum")]
+    InvalidPerformanceFee,
+    #[msg("Insufficient managed account balance")]
+    InsufficientManagedBalance,
+    #[msg("Syndicate bet has already been placed")]
+    SyndicateBetPlaced,
+    #[msg("Parameter is controlled by governance")]
+    GovernanceActive,
+    #[msg("Governance is not enabled")]
+    GovernanceInactive,
+    #[msg("Proposed parameter value is out of range")]
+    InvalidGovernanceChange,
+    #[msg("Voting has closed")]
+    VotingClosed,
+    #[msg("Voting is still open")]
+    VotingOpen,
+    #[msg("Proposal already executed")]
+    ProposalExecuted,
+    #[msg("Proposal did not pass")]
+    ProposalRejected,
+    #[msg("Epoch is not the current reward epoch")]
+    EpochMismatch,
+    #[msg("Reward epoch has not ended")]
+    EpochNotEnded,
+    #[msg("No new volume to record")]
+    NoNewVolume,
+    #[msg("Reward epoch is too short")]
+    InvalidEpochLength,
+    #[msg("Insufficient staked balance")]
+    InsufficientStake,
+    #[msg("Stake is locked until a full epoch has passed")]
+    StakeLocked,
+    #[msg("Buyback exceeds this epoch's cap")]
+    BuybackCapExceeded,
+    #[msg("Not enough fees in the buyback vault")]
+    InsufficientBuybackFunds,
+    #[msg("Buyback returned fewer tokens than the minimum")]
+    BuybackSlippage,
+    #[msg("Timelock delay has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Timelock delay is out of range")]
+    InvalidTimelockDelay,
+    #[msg("Accounts do not match the queued action")]
+    TimelockAccountMismatch,
+    #[msg("Not enough lamports in the treasury")]
+    InsufficientTreasuryFunds,
+    #[msg("Market is not halted")]
+    MarketNotHalted,
+    #[msg("Refund accounts must be (participant, bettor) pairs of this market")]
+    InvalidRefundBatch,
+    #[msg("Address is blocked")]
+    AddressBlocked,
+    #[msg("Bet requires a compliance attestation")]
+    AttestationRequired,
+    #[msg("Invalid compliance attestation")]
+    InvalidAttestation,
+    #[msg("Compliance attestation has expired")]
+    AttestationExpired,
+    #[msg("Too many bets from this wallet, try again shortly")]
+    RateLimited,
+    #[msg("Market requires a personhood credential")]
+    PersonhoodRequired,
+    #[msg("Only one bet per person on this market")]
+    AlreadyBet,
+    #[msg("Protocol fee can't be collected until the claim window has passed")]
+    FeeCollectionTooEarly,
+    #[msg("Market pool is at its cap")]
+    PoolCapExceeded,
+    #[msg("Creator's reputation requires a third-party resolver")]
+    ResolverRequired,
+    #[msg("Creator score must be at most 10000 bps")]
+    InvalidCreatorScore,
+    #[msg("Creation bond exceeds the maximum")]
+    InvalidCreationBond,
+    #[msg("Creation bond was forfeited")]
+    BondForfeited,
+    #[msg("Creation bond is not forfeited")]
+    BondNotForfeited,
+    #[msg("No creation bond held")]
+    NoBond,
+    #[msg("Creation fee exceeds the maximum")]
+    InvalidCreationFee,
+    #[msg("Treasury account required")]
+    TreasuryRequired,
+    #[msg("Market is frozen pending moderator review")]
+    MarketFrozen,
+    #[msg("Flags have already been reviewed")]
+    FlagsReviewed,
+    #[msg("Flags are awaiting moderator review")]
+    FlagsPending,
+    #[msg("Market is already delisted")]
+    AlreadyDelisted,
+    #[msg("Market is not delisted")]
+    NotDelisted,
+    #[msg("Too many featured markets")]
+    TooManyFeatured,
+    #[msg("Featured list must match the market accounts passed, without duplicates")]
+    InvalidFeaturedList,
+    #[msg("This category must be settled by an oracle")]
+    OracleRequired,
+    #[msg("Market extension field too long")]
+    ExtensionFieldTooLong,
+    #[msg("Series interval must be at least an hour")]
+    InvalidSeriesInterval,
+    #[msg("Market is not part of a series")]
+    NotSeriesMarket,
+    #[msg("Market has already been rolled over")]
+    AlreadyRolledOver,
+    #[msg("Schedule must have at least one run")]
+    InvalidSchedule,
+    #[msg("Schedule has no runs left")]
+    ScheduleFinished,
+    #[msg("Schedule's next run is not due yet")]
+    ScheduleNotDue,
+    #[msg("LST yield is not enabled")]
+    LstDisabled,
+    #[msg("Deposit would exceed the LST share of the escrow")]
+    LstDepositCapExceeded,
+    #[msg("Market has no LST position")]
+    NoLstPosition,
+    #[msg("Market's LST position must be unwound first")]
+    LstNotUnwound,
+    #[msg("Payout splits must total at most 100% and match the recipients passed")]
+    InvalidPayoutSplit,
+    #[msg("Charity share must be at most 100%")]
+    InvalidCharityBps,
+    #[msg("Charity account required")]
+    CharityRequired,
+    #[msg("Market is not resolved from an oracle")]
+    NotOracleMarket,
+    #[msg("Bounty must be paid to the bounty hunter, or the creator if there is none")]
+    InvalidBountyRecipient,
+    #[msg("Market resolution is under dispute")]
+    DisputeOpen,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Not enough jurors in the pool")]
+    JurorPoolTooSmall,
+    #[msg("Juror pool is full")]
+    JurorPoolFull,
+    #[msg("Already in the juror pool")]
+    AlreadyJuror,
+    #[msg("Not in the juror pool")]
+    NotJuror,
+    #[msg("Not on this dispute's jury")]
+    NotOnJury,
+    #[msg("Already voted")]
+    AlreadyVoted,
+    #[msg("Dispute is not in the required state")]
+    InvalidDisputeStatus,
+    #[msg("Stake positions do not match the jury")]
+    JurorPositionMismatch,
+    #[msg("Stake is locked by a pending jury vote")]
+    JurorLocked,
+    #[msg("Invalid randomness account")]
+    InvalidRandomness,
+    #[msg("Randomness must be committed in the previous slot and not yet revealed")]
+    RandomnessStale,
+    #[msg("Randomness is not revealed in this slot")]
+    RandomnessNotRevealed,
+    #[msg("Raffle winner count is out of range")]
+    InvalidRaffleWinners,
+    #[msg("Raffle has not been drawn")]
+    RaffleNotDrawn,
+    #[msg("Raffle has already been drawn")]
+    RaffleDrawn,
+    #[msg("Committed randomness is still awaiting reveal")]
+    RandomnessPending,
+    #[msg("No tickets on the winning side")]
+    NoWinningTickets,
+    #[msg("Side pot share is out of range or not enabled")]
+    InvalidSidePot,
+    #[msg("Booster multiplier is out of range")]
+    InvalidBoosterMultiplier,
+    #[msg("Position already carries a booster")]
+    AlreadyBoosted,
+    #[msg("Booster fund account required")]
+    BoosterFundRequired,
+    #[msg("Points halving period cannot be negative")]
+    InvalidPointsSchedule,
+    #[msg("No points to accrue")]
+    NoPointsToAccrue,
+    #[msg("Points redemption is disabled")]
+    RedemptionDisabled,
+    #[msg("Not enough points")]
+    InsufficientPoints,
+    #[msg("Quest objective or window is invalid")]
+    InvalidQuest,
+    #[msg("Bet was not placed during the quest")]
+    OutsideQuestWindow,
+    #[msg("Position already counted")]
+    AlreadyCounted,
+    #[msg("Quest progress is full")]
+    QuestProgressFull,
+    #[msg("Position does not advance this quest")]
+    NoQuestProgress,
+    #[msg("Quest is not complete")]
+    QuestIncomplete,
+    #[msg("Booster reward accounts required")]
+    BoosterAccountsRequired,
+    #[msg("Already checked in today")]
+    AlreadyCheckedIn,
+    #[msg("Reward accounts required")]
+    RewardAccountsRequired,
+    #[msg("Distributor account required")]
+    DistributorRequired,
+    #[msg("Airdrop root already published")]
+    RootAlreadyPublished,
+    #[msg("Airdrop root not published yet")]
+    RootNotPublished,
+    #[msg("Invalid merkle proof")]
+    InvalidMerkleProof,
+    #[msg("Market settles through its merkle root; use claim_settlement")]
+    MerkleSettled,
+    #[msg("Market is not merkle-settled")]
+    NotMerkleSettled,
+    #[msg("Invalid settlement root")]
+    InvalidSettlementRoot,
+    #[msg("Market stores positions in a compressed tree")]
+    CompressedMarket,
+    #[msg("Market does not use compressed positions")]
+    NotCompressedMarket,
+    #[msg("Settle accounts must be at most 10 (participant, bettor) pairs of this market")]
+    InvalidSettleBatch,
+    #[msg(
+        "Winnings on this market are pushed by the keeper; claiming opens after the fallback delay"
+    )]
+    PushPayoutPending,
+    #[msg("Position must be paid in full to the wallet to claim in installments")]
+    InstallmentUnavailable,
+    #[msg("Installment must be positive and within the unclaimed payout")]
+    InvalidInstallment,
+    #[msg("Market vault lacks the liquidity for this installment")]
+    InsufficientVaultLiquidity,
+    #[msg("Only a 1v1 Challenge can be mutually cancelled")]
+    NotChallenge,
+    #[msg("Resolution is not overdue yet")]
+    ResolutionNotOverdue,
+    #[msg("Creation bond must be reclaimed or forfeited first")]
+    BondOutstanding,
+    #[msg("Market still has payouts outstanding")]
+    PayoutsOutstanding,
+    #[msg("Maker rebate cannot exceed the taker fee, which is capped at 5%")]
+    InvalidOrderBookFees,
+    #[msg("Order expiry must be in the future and no later than the deadline")]
+    InvalidOrderExpiry,
+    #[msg("Closing auction must be 1 hour or less and start in the future")]
+    InvalidAuctionWindow,
+    #[msg("Positions are locked during the closing auction")]
+    ClosingAuctionOpen,
+    #[msg("Betting cutoff must leave betting open until after now")]
+    InvalidBettingCutoff,
+    #[msg(
+        "Late discount must be at most 90% over at most the whole lifetime, both set or both zero"
+    )]
+    InvalidLateDiscount,
+    #[msg("Resolver's accuracy is below the required threshold")]
+    ResolverAccuracyTooLow,
+    #[msg("Markets are not linked to each other")]
+    MarketsNotLinked,
+    #[msg("Linked markets' deadlines are too far apart")]
+    HedgeDeadlineMismatch,
+    #[msg("Order has expired")]
+    OrderExpired,
+    #[msg("Order has not expired")]
+    OrderNotExpired,
+    #[msg("Frontend fee share exceeds the maximum")]
+    InvalidFrontendFeeShare,
+    #[msg("Claim needs the profile's co-signer")]
+    CoSignerRequired,
+    #[msg("Co-signer must be a different key from the wallet")]
+    InvalidCoSigner,
+    #[msg("Recovery delay must be between 0 and 30 days")]
+    InvalidRecoveryDelay,
+    #[msg("No profile recovery is pending")]
+    NoPendingRecovery,
+    #[msg("Profile recovery delay has not passed")]
+    RecoveryDelayActive,
+    #[msg("Loyalty points must be moved along with the profile")]
+    InvalidProfileRotation,
+    #[msg("Profile has already been moved to another wallet")]
+    ProfileMoved,
+    #[msg("The third-party resolver's profile must be passed to record the resolution")]
+    ResolverProfileRequired,
+    #[msg("Handles are 3-20 characters of lowercase letters, digits and underscores")]
+    InvalidHandle,
+    #[msg("Profile metadata URI too long")]
+    ProfileUriTooLong,
+    #[msg("Cannot follow yourself")]
+    CannotFollowSelf,
+    #[msg("Only wallets with a position in the market can boost its resolution")]
+    NotParticipant,
+    #[msg("Claim delay must be positive and at most the dispute window")]
+    InvalidClaimDelay,
+    #[msg("Claims are locked until the claim delay after resolution has passed")]
+    ClaimLocked,
+    #[msg("Deadline slot must be in the future")]
+    InvalidDeadlineSlot,
+    #[msg("Settlement root was posted for an outcome that has since been overturned")]
+    StaleSettlement,
+    #[msg("Oracle price was published too long after expiry to settle the market")]
+    PriceTooLate,
+    #[msg("Price feed max age must be positive and at most 24 hours")]
+    InvalidFeedMaxAge,
+    #[msg("Prepaid balance doesn't cover the creation fee and bond")]
+    PrepaidBalanceTooLow,
+    #[msg("Too few governance tokens voted for the proposal to pass")]
+    QuorumNotReached,
+    #[msg("Proposer holds too few governance tokens")]
+    ProposalThresholdNotMet,
+    #[msg("Queued action is not a governance change")]
+    NotGovernanceAction,
+    #[msg("Duel already has both sides taken")]
+    DuelMatched,
+    #[msg("Market does not offer vesting")]
+    VestingDisabled,
 }
 
-// Errors
-#[error_code]
-pub enum PredictDuelError {
-    #[msg("Question exceeds 200 characters")]
-    QuestionTooLong,
-    #[msg("Stake amount must be at least 0.01 SOL")]
-    StakeTooLow,
-    #[msg("Deadline must be in the future")]
-    InvalidDeadline,
-    #[msg("Market is not active")]
-    MarketNotActive,
-    #[msg("Market has expired")]
-    MarketExpired,
-    #[msg("Only the creator can resolve this market")]
-    UnauthorizedResolver,
-    #[msg("Market has not expired yet")]
-    MarketNotExpired,
-    #[msg("Market is not resolved yet")]
-    MarketNotResolved,
-    #[msg("Winnings already claimed")]
-    AlreadyClaimed,
-    #[msg("No outcome set for market")]
-    NoOutcome,
-    #[msg("You did not win this market")]
-    NotAWinner,
-    #[msg("Cannot cancel market with active participants")]
-    CannotCancel,
-    #[msg("Market is not cancelled")]
-    MarketNotCancelled,
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account_info(
+        key: Pubkey,
+        owner: Pubkey,
+        data: Vec<u8>,
+        is_signer: bool,
+        executable: bool,
+    ) -> &'static AccountInfo<'static> {
+        Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(key)),
+            is_signer,
+            true,
+            Box::leak(Box::new(1_000_000_000)),
+            Box::leak(data.into_boxed_slice()),
+            Box::leak(Box::new(owner)),
+            executable,
+            0,
+        )))
+    }
+
+    fn unchecked(key: Pubkey) -> UncheckedAccount<'static> {
+        UncheckedAccount::try_from(account_info(key, Pubkey::default(), vec![], false, false))
+    }
+
+    /// Zeroed program account: every field decodes to its zero value
+    fn program_account<T: AccountSerialize + AccountDeserialize + Owner + Clone>(
+        key: Pubkey,
+        discriminator: &[u8],
+    ) -> Box<Account<'static, T>> {
+        let mut data = discriminator.to_vec();
+        data.resize(10_000, 0);
+        Box::new(Account::try_from(account_info(key, crate::ID, data, false, false)).unwrap())
+    }
+
+    fn posted_vaa(magic: &[u8; 3], payload: &[u8]) -> Vec<u8> {
+        let mut data = vec![0; POSTED_VAA_PAYLOAD_OFFSET + 4 + payload.len()];
+        data[..3].copy_from_slice(magic);
+        data[POSTED_VAA_SEQUENCE_OFFSET..POSTED_VAA_SEQUENCE_OFFSET + 8]
+            .copy_from_slice(&42u64.to_le_bytes());
+        data[POSTED_VAA_EMITTER_CHAIN_OFFSET..POSTED_VAA_EMITTER_CHAIN_OFFSET + 2]
+            .copy_from_slice(&2u16.to_le_bytes());
+        data[POSTED_VAA_EMITTER_ADDRESS_OFFSET..POSTED_VAA_EMITTER_ADDRESS_OFFSET + 32]
+            .copy_from_slice(&[7; 32]);
+        data[POSTED_VAA_PAYLOAD_OFFSET..POSTED_VAA_PAYLOAD_OFFSET + 4]
+            .copy_from_slice(&(payload.len() as u32).to_le_bytes());
+        data[POSTED_VAA_PAYLOAD_OFFSET + 4..].copy_from_slice(payload);
+        data
+    }
+
+    fn cross_chain_bet_payload(market: &Pubkey, bettor: &Pubkey) -> Vec<u8> {
+        let mut payload = vec![CROSS_CHAIN_BET_PAYLOAD_ID];
+        payload.extend_from_slice(market.as_ref());
+        payload.extend_from_slice(bettor.as_ref());
+        payload.push(1);
+        payload.extend_from_slice(&500_000_000u64.to_be_bytes());
+        payload.extend_from_slice(&[9; 32]);
+        payload
+    }
+
+    /// Transmissions account holding `rounds` as (timestamp, answer) in ring order
+    fn chainlink_feed(
+        version: u8,
+        latest_round: u32,
+        live_cursor: u32,
+        rounds: &[(u32, i128)],
+    ) -> Vec<u8> {
+        let mut data =
+            vec![0; 8 + CHAINLINK_HEADER_SIZE + rounds.len() * CHAINLINK_TRANSMISSION_SIZE];
+        data[..8].copy_from_slice(&CHAINLINK_TRANSMISSIONS_DISCRIMINATOR);
+        data[CHAINLINK_VERSION_OFFSET] = version;
+        data[CHAINLINK_DECIMALS_OFFSET] = 8;
+        data[CHAINLINK_LATEST_ROUND_OFFSET..CHAINLINK_LATEST_ROUND_OFFSET + 4]
+            .copy_from_slice(&latest_round.to_le_bytes());
+        data[CHAINLINK_LIVE_LENGTH_OFFSET..CHAINLINK_LIVE_LENGTH_OFFSET + 4]
+            .copy_from_slice(&(rounds.len() as u32).to_le_bytes());
+        data[CHAINLINK_LIVE_CURSOR_OFFSET..CHAINLINK_LIVE_CURSOR_OFFSET + 4]
+            .copy_from_slice(&live_cursor.to_le_bytes());
+        for (i, (timestamp, answer)) in rounds.iter().enumerate() {
+            let offset = 8 + CHAINLINK_HEADER_SIZE + i * CHAINLINK_TRANSMISSION_SIZE;
+            data[offset + 8..offset + 12].copy_from_slice(&timestamp.to_le_bytes());
+            data[offset + 16..offset + 32].copy_from_slice(&answer.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn read_posted_vaa_decodes_a_verified_vaa() {
+        let payload = [1, 2, 3, 4, 5];
+        let account = account_info(
+            Pubkey::new_unique(),
+            WORMHOLE_CORE_BRIDGE_PROGRAM_ID,
+            posted_vaa(b"vaa", &payload),
+            false,
+            false,
+        );
+
+        let vaa = read_posted_vaa(account).unwrap();
+        assert_eq!(vaa.sequence, 42);
+        assert_eq!(vaa.emitter_chain, 2);
+        assert_eq!(vaa.emitter_address, [7; 32]);
+        assert_eq!(vaa.payload, payload);
+    }
+
+    #[test]
+    fn read_posted_vaa_rejects_unverified_foreign_and_truncated_accounts() {
+        let message = account_info(
+            Pubkey::new_unique(),
+            WORMHOLE_CORE_BRIDGE_PROGRAM_ID,
+            posted_vaa(b"msg", &[1]),
+            false,
+            false,
+        );
+        assert_eq!(
+            read_posted_vaa(message).err(),
+            Some(PredictDuelError::InvalidVaa.into())
+        );
+
+        let foreign = account_info(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            posted_vaa(b"vaa", &[1]),
+            false,
+            false,
+        );
+        assert!(read_posted_vaa(foreign).is_err());
+
+        let mut data = posted_vaa(b"vaa", &[1, 2, 3]);
+        data.pop();
+        let truncated = account_info(
+            Pubkey::new_unique(),
+            WORMHOLE_CORE_BRIDGE_PROGRAM_ID,
+            data,
+            false,
+            false,
+        );
+        assert_eq!(
+            read_posted_vaa(truncated).err(),
+            Some(PredictDuelError::InvalidVaa.into())
+        );
+    }
+
+    #[test]
+    fn cross_chain_bet_parses_a_bet_payload() {
+        let market = Pubkey::new_unique();
+        let bettor = Pubkey::new_unique();
+
+        let bet = CrossChainBet::parse(&cross_chain_bet_payload(&market, &bettor)).unwrap();
+        assert_eq!(bet.market, market);
+        assert_eq!(bet.bettor, bettor);
+        assert!(bet.prediction);
+        assert_eq!(bet.stake_amount, 500_000_000);
+        assert_eq!(bet.recipient, [9; 32]);
+    }
+
+    #[test]
+    fn cross_chain_bet_rejects_other_payloads() {
+        let market = Pubkey::new_unique();
+        let bettor = Pubkey::new_unique();
+
+        let mut payout = cross_chain_bet_payload(&market, &bettor);
+        payout[0] = CROSS_CHAIN_PAYOUT_PAYLOAD_ID;
+        assert_eq!(
+            CrossChainBet::parse(&payout).err(),
+            Some(PredictDuelError::InvalidVaa.into())
+        );
+
+        let mut long = cross_chain_bet_payload(&market, &bettor);
+        long.push(0);
+        assert_eq!(
+            CrossChainBet::parse(&long).err(),
+            Some(PredictDuelError::InvalidVaa.into())
+        );
+        assert_eq!(
+            CrossChainBet::parse(&[]).err(),
+            Some(PredictDuelError::InvalidVaa.into())
+        );
+    }
+
+    #[test]
+    fn cross_chain_payout_payload_is_big_endian() {
+        let market = Pubkey::new_unique();
+
+        let payload = cross_chain_payout_payload(&market, &[9; 32], 0x0102_0304_0506_0708);
+        assert_eq!(payload.len(), 1 + 32 + 32 + 8);
+        assert_eq!(payload[0], CROSS_CHAIN_PAYOUT_PAYLOAD_ID);
+        assert_eq!(&payload[1..33], market.as_ref());
+        assert_eq!(&payload[33..65], &[9; 32]);
+        assert_eq!(&payload[65..], &[1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn wormhole_post_message_ix_matches_the_core_bridge_layout() {
+        let mut clock = vec![0; 40];
+        clock[..8].copy_from_slice(&1u64.to_le_bytes());
+        let accounts = ClaimCrossChain {
+            market: program_account(Pubkey::new_unique(), Market::DISCRIMINATOR),
+            participant: program_account(Pubkey::new_unique(), Participant::DISCRIMINATOR),
+            market_vault: unchecked(Pubkey::new_unique()),
+            bridge_vault: unchecked(Pubkey::new_unique()),
+            wormhole_bridge: unchecked(Pubkey::new_unique()),
+            wormhole_fee_collector: unchecked(Pubkey::new_unique()),
+            wormhole_emitter: unchecked(Pubkey::new_unique()),
+            wormhole_sequence: unchecked(Pubkey::new_unique()),
+            wormhole_message: unchecked(Pubkey::new_unique()),
+            wormhole_program: unchecked(WORMHOLE_CORE_BRIDGE_PROGRAM_ID),
+            relayer: Signer::try_from(account_info(
+                Pubkey::new_unique(),
+                anchor_lang::system_program::ID,
+                vec![],
+                true,
+                false,
+            ))
+            .unwrap(),
+            clock: Sysvar::from_account_info(account_info(
+                anchor_lang::solana_program::sysvar::clock::ID,
+                Pubkey::default(),
+                clock,
+                false,
+                false,
+            ))
+            .unwrap(),
+            rent: Sysvar::from_account_info(account_info(
+                anchor_lang::solana_program::sysvar::rent::ID,
+                Pubkey::default(),
+                vec![0; 17],
+                false,
+                false,
+            ))
+            .unwrap(),
+            system_program: Program::try_from(account_info(
+                anchor_lang::system_program::ID,
+                Pubkey::default(),
+                vec![],
+                false,
+                true,
+            ))
+            .unwrap(),
+        };
+
+        let ix = wormhole_post_message_ix(&accounts, 7, vec![0xaa, 0xbb]);
+        assert_eq!(ix.program_id, WORMHOLE_CORE_BRIDGE_PROGRAM_ID);
+        assert_eq!(
+            ix.data,
+            [
+                1,
+                7,
+                0,
+                0,
+                0,
+                2,
+                0,
+                0,
+                0,
+                0xaa,
+                0xbb,
+                WORMHOLE_CONSISTENCY_FINALIZED
+            ]
+        );
+
+        let expected = [
+            (accounts.wormhole_bridge.key(), true, false),
+            (accounts.wormhole_message.key(), true, true),
+            (accounts.wormhole_emitter.key(), false, true),
+            (accounts.wormhole_sequence.key(), true, false),
+            (accounts.relayer.key(), true, true),
+            (accounts.wormhole_fee_collector.key(), true, false),
+            (accounts.clock.key(), false, false),
+            (accounts.rent.key(), false, false),
+            (accounts.system_program.key(), false, false),
+        ];
+        assert_eq!(ix.accounts.len(), expected.len());
+        for (meta, (key, is_writable, is_signer)) in ix.accounts.iter().zip(expected) {
+            assert_eq!(
+                (meta.pubkey, meta.is_writable, meta.is_signer),
+                (key, is_writable, is_signer)
+            );
+        }
+    }
+
+    fn switchboard_feed(last_update: i64, value: i128) -> Vec<u8> {
+        let mut data = vec![0; SWITCHBOARD_RESULT_VALUE_OFFSET + 16];
+        data[..8].copy_from_slice(&SWITCHBOARD_PULL_FEED_DISCRIMINATOR);
+        data[SWITCHBOARD_LAST_UPDATE_OFFSET..SWITCHBOARD_LAST_UPDATE_OFFSET + 8]
+            .copy_from_slice(&last_update.to_le_bytes());
+        data[SWITCHBOARD_RESULT_VALUE_OFFSET..].copy_from_slice(&value.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn read_switchboard_value_decodes_a_pull_feed() {
+        let mut data = switchboard_feed(1_700_000_000, -25_500_000_000_000_000_000);
+
+        let price = read_switchboard_value(&data).unwrap();
+        assert_eq!(price.price, -25_500_000_000_000_000_000);
+        assert_eq!(price.expo, SWITCHBOARD_EXPO);
+        assert_eq!(price.publish_time, 1_700_000_000);
+
+        data[0] ^= 1;
+        assert_eq!(
+            read_switchboard_value(&data).err(),
+            Some(PredictDuelError::InvalidOracleFeed.into())
+        );
+        assert!(read_switchboard_value(&SWITCHBOARD_PULL_FEED_DISCRIMINATOR).is_err());
+    }
+
+    #[test]
+    fn read_chainlink_price_reads_the_round_behind_the_cursor() {
+        let rounds = [(100, 1_000), (300, 3_000), (200, 2_000)];
+
+        let price = read_chainlink_price(&chainlink_feed(
+            CHAINLINK_TRANSMISSIONS_VERSION,
+            3,
+            2,
+            &rounds,
+        ))
+        .unwrap();
+        assert_eq!(price.price, 3_000);
+        assert_eq!(price.expo, -8);
+        assert_eq!(price.publish_time, 300);
+
+        // The cursor wraps, so a cursor at zero means the last slot is the latest
+        let price = read_chainlink_price(&chainlink_feed(
+            CHAINLINK_TRANSMISSIONS_VERSION,
+            3,
+            0,
+            &rounds,
+        ))
+        .unwrap();
+        assert_eq!(price.price, 2_000);
+        assert_eq!(price.publish_time, 200);
+    }
+
+    #[test]
+    fn read_chainlink_price_rejects_other_accounts_and_empty_feeds() {
+        let rounds = [(100, 1_000)];
+
+        let mut foreign = chainlink_feed(CHAINLINK_TRANSMISSIONS_VERSION, 1, 1, &rounds);
+        foreign[0] ^= 1;
+        assert_eq!(
+            read_chainlink_price(&foreign).err(),
+            Some(PredictDuelError::InvalidOracleFeed.into())
+        );
+
+        let old_layout = chainlink_feed(1, 1, 1, &rounds);
+        assert_eq!(
+            read_chainlink_price(&old_layout).err(),
+            Some(PredictDuelError::InvalidOracleFeed.into())
+        );
+
+        let no_rounds = chainlink_feed(CHAINLINK_TRANSMISSIONS_VERSION, 0, 1, &rounds);
+        assert_eq!(
+            read_chainlink_price(&no_rounds).err(),
+            Some(PredictDuelError::InvalidOracleFeed.into())
+        );
+
+        let truncated = &chainlink_feed(CHAINLINK_TRANSMISSIONS_VERSION, 1, 1, &rounds)
+            [..8 + CHAINLINK_HEADER_SIZE];
+        assert!(read_chainlink_price(truncated).is_err());
+    }
+
+    /// Market set up the way the creation paths do: creation fields first, then init_state
+    fn new_market(
+        created_at: i64,
+        created_slot: u64,
+        deadline: i64,
+    ) -> Box<Account<'static, Market>> {
+        let mut market = program_account::<Market>(Pubkey::new_unique(), Market::DISCRIMINATOR);
+        market.deadline = deadline;
+        market.created_at = created_at;
+        market.created_slot = created_slot;
+        market.init_state(created_at);
+        market
+    }
+
+    fn clock_at(unix_timestamp: i64, slot: u64) -> Clock {
+        Clock {
+            slot,
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    /// Market resolved to `outcome` at t=20,000 with an hour's claim delay
+    fn resolved_market(outcome: bool) -> Box<Account<'static, Market>> {
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.status = MarketStatus::Resolved;
+        market.outcome = Some(outcome);
+        market.resolved_at = 20_000;
+        market.claim_delay = 3_600;
+        market
+    }
+
+    #[test]
+    fn init_state_keeps_the_creation_slot() {
+        let market = new_market(1_000, 5_000, 1_000 + 3_600);
+
+        assert_eq!(market.created_slot, 5_000);
+        // An hour is 9,000 slots at 400ms; the deadline needs 80% of them
+        assert_eq!(market.min_deadline_slot(), 5_000 + 7_200);
+        assert!(market.min_deadline_slot() > market.created_slot);
+    }
+
+    #[test]
+    fn deadline_needs_slot_progress_as_well_as_time() {
+        let market = new_market(1_000, 5_000, 1_000 + 3_600);
+
+        assert!(!market.deadline_reached(&clock_at(4_599, 20_000)));
+        assert!(!market.deadline_reached(&clock_at(1_000_000, 5_001)));
+        assert!(!market.deadline_reached(&clock_at(4_600, 12_199)));
+        assert!(market.deadline_reached(&clock_at(4_600, 12_200)));
+    }
+
+    #[test]
+    fn slot_deadline_closes_betting_and_unsealed_markets() {
+        let mut market = new_market(1_000, 5_000, 1_000 + 3_600);
+        market.betting_cutoff_secs = 60;
+        market.deadline_slot = 8_000;
+
+        assert!(market.betting_open(&clock_at(4_539, 7_999)));
+        assert!(!market.betting_open(&clock_at(4_540, 7_999)));
+        assert!(!market.betting_open(&clock_at(2_000, 8_000)));
+
+        // A slot deadline resolves unsealed markets early, but never sealed ones
+        assert!(market.deadline_reached(&clock_at(2_000, 8_000)));
+        market.sealed = true;
+        assert!(!market.deadline_reached(&clock_at(2_000, 8_000)));
+    }
+
+    #[test]
+    fn settlement_reading_accepts_updates_up_to_the_feed_max_age() {
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.status = MarketStatus::Active;
+        let max_age = 120;
+        let read = |publish_time: i64, now: i64| {
+            let feed = account_info(
+                Pubkey::new_unique(),
+                SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+                switchboard_feed(publish_time, 1),
+                false,
+                false,
+            );
+            market.settlement_reading(OracleProvider::Switchboard, max_age, feed, now)
+        };
+
+        assert_eq!(
+            read(10_000, 9_999).err(),
+            Some(PredictDuelError::MarketNotExpired.into())
+        );
+        assert_eq!(
+            read(9_999, 10_000).err(),
+            Some(PredictDuelError::StalePrice.into())
+        );
+        assert_eq!(read(10_000, 10_000).unwrap().unwrap().publish_time, 10_000);
+        assert_eq!(read(10_120, 10_200).unwrap().unwrap().publish_time, 10_120);
+        assert_eq!(
+            read(10_121, 10_200).err(),
+            Some(PredictDuelError::PriceTooLate.into())
+        );
+
+        // Resolution stays open for the window after the max age, then the market voids
+        let closes = 10_000 + max_age + PRICE_RESOLUTION_WINDOW;
+        assert!(read(10_120, closes).unwrap().is_some());
+        assert!(read(10_120, closes + 1).unwrap().is_none());
+    }
+
+    #[test]
+    fn feed_max_age_defaults_per_provider() {
+        assert_eq!(OracleProvider::Chainlink.feed_max_age(0).unwrap(), 60 * 60);
+        assert_eq!(OracleProvider::Pyth.feed_max_age(0).unwrap(), 60);
+        assert_eq!(OracleProvider::Chainlink.feed_max_age(90).unwrap(), 90);
+        assert_eq!(OracleProvider::SportsReporter.feed_max_age(0).unwrap(), 0);
+        assert_eq!(
+            OracleProvider::Switchboard
+                .feed_max_age(MAX_FEED_MAX_AGE + 1)
+                .err(),
+            Some(PredictDuelError::InvalidFeedMaxAge.into())
+        );
+        assert!(OracleProvider::Pyth.feed_max_age(-1).is_err());
+    }
+
+    #[test]
+    fn chainlink_heartbeat_after_expiry_settles_with_the_default_max_age() {
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.status = MarketStatus::Active;
+        // First round lands 20 minutes after expiry, as a slow heartbeat would
+        let feed = account_info(
+            Pubkey::new_unique(),
+            CHAINLINK_STORE_PROGRAM_ID,
+            chainlink_feed(
+                CHAINLINK_TRANSMISSIONS_VERSION,
+                2,
+                0,
+                &[(9_000, 1), (11_200, 2)],
+            ),
+            false,
+            false,
+        );
+        let max_age = OracleProvider::Chainlink.default_max_age();
+
+        let price = market
+            .settlement_reading(OracleProvider::Chainlink, max_age, feed, 11_300)
+            .unwrap()
+            .unwrap();
+        assert_eq!(price.price, 2);
+    }
+
+    #[test]
+    fn init_space_matches_the_largest_serialization() {
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.question = "q".repeat(200);
+        market.raffle_winners = vec![u32::MAX; MAX_RAFFLE_WINNERS];
+        market.outcome = Some(true);
+        market.spread_x10 = Some(0);
+        market.event_condition = Some(EventCondition::MarginAbove { threshold: 0 });
+        market.price_condition = Some(PriceCondition {
+            comparison: PriceComparison::Above,
+            strike_price: 0,
+            strike_expo: 0,
+        });
+        market.weather_condition = Some(WeatherCondition {
+            comparison: PriceComparison::Above,
+            threshold: 0,
+            tolerance: 0,
+            decimals: 0,
+            rounding: Rounding::HalfUp,
+            max_reading_age: 0,
+        });
+        market.game_condition = Some(GameCondition {
+            game_id: 0,
+            condition: EventCondition::PrimaryAbove { threshold: 0 },
+        });
+        let fields: &mut Market = &mut market;
+        for key in [
+            &mut fields.event,
+            &mut fields.oracle_feed,
+            &mut fields.personhood_mint,
+            &mut fields.resolver,
+            &mut fields.bounty_hunter,
+            &mut fields.raffle_randomness,
+            &mut fields.position_tree,
+            &mut fields.linked_market,
+            &mut fields.resolved_by,
+        ] {
+            *key = Some(Pubkey::default());
+        }
+        let mut data = Vec::new();
+        market.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), 8 + Market::INIT_SPACE);
+
+        let mut participant = Participant {
+            payout_splits: vec![
+                PayoutSplit {
+                    recipient: Pubkey::default(),
+                    bps: 0
+                };
+                MAX_PAYOUT_SPLITS
+            ],
+            entries: vec![BetEntry::default(); MAX_BET_ENTRIES],
+            receipt_nonce: Some(0),
+            frontend: Some(Pubkey::default()),
+            ..Participant::default()
+        };
+        participant.stake = 1;
+        let mut data = Vec::new();
+        participant.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), 8 + Participant::INIT_SPACE);
+    }
+
+    #[test]
+    fn weather_readings_use_the_condition_max_age_over_the_feed() {
+        let feed = OracleFeed {
+            feed: Pubkey::new_unique(),
+            provider: OracleProvider::Switchboard,
+            asset_name: "NYC-TEMP".to_string(),
+            active: true,
+            added_at: 0,
+            bump: 0,
+            max_age: 10 * 60,
+        };
+        let mut condition = WeatherCondition {
+            comparison: PriceComparison::Above,
+            threshold: 255,
+            tolerance: 0,
+            decimals: 1,
+            rounding: Rounding::HalfUp,
+            max_reading_age: 0,
+        };
+        assert_eq!(condition.reading_max_age(&feed), 10 * 60);
+
+        // An hourly station reading 40 minutes after the deadline still settles
+        condition.max_reading_age = 60 * 60;
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.status = MarketStatus::Active;
+        let reading = account_info(
+            Pubkey::new_unique(),
+            SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+            switchboard_feed(12_400, 26_000_000_000_000_000_000),
+            false,
+            false,
+        );
+        let max_age = condition.reading_max_age(&feed);
+        assert!(market
+            .settlement_reading(OracleProvider::Switchboard, max_age, reading, 12_500)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn rotated_profile_keeps_its_co_signer_for_the_old_wallet() {
+        let co_signer = Pubkey::new_unique();
+        let mut profile =
+            program_account::<UserProfile>(Pubkey::new_unique(), UserProfile::DISCRIMINATOR);
+        profile.wallet = Pubkey::new_unique();
+        profile.co_signer = Some(co_signer);
+        profile.co_sign_threshold = 1_000;
+        profile.recovery_key = Some(Pubkey::new_unique());
+
+        let new_wallet = Pubkey::new_unique();
+        profile.tombstone(new_wallet);
+        assert_eq!(profile.moved_to, Some(new_wallet));
+        assert_eq!(profile.recovery_key, None);
+
+        let mut data = Vec::new();
+        profile.try_serialize(&mut data).unwrap();
+        let tombstone = account_info(Pubkey::new_unique(), crate::ID, data, false, false);
+        assert_eq!(
+            assert_claim_co_signed(tombstone, None, 1_001).err(),
+            Some(PredictDuelError::CoSignerRequired.into())
+        );
+        let signer = Signer::try_from(account_info(
+            co_signer,
+            Pubkey::default(),
+            vec![],
+            true,
+            false,
+        ))
+        .unwrap();
+        assert!(assert_claim_co_signed(tombstone, Some(&signer), 1_001).is_ok());
+        assert!(assert_claim_co_signed(tombstone, None, 1_000).is_ok());
+    }
+
+    #[test]
+    fn resolutions_and_verdicts_count_against_the_hand_resolver() {
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.creator = Pubkey::new_unique();
+        let resolver = Pubkey::new_unique();
+        market.resolver = Some(resolver);
+        let mut creator_profile =
+            program_account::<UserProfile>(Pubkey::new_unique(), UserProfile::DISCRIMINATOR);
+        let mut resolver_profile =
+            program_account::<UserProfile>(Pubkey::new_unique(), UserProfile::DISCRIMINATOR);
+
+        assert_eq!(
+            market.credit_resolution(&mut creator_profile, None).err(),
+            Some(PredictDuelError::ResolverProfileRequired.into())
+        );
+        market
+            .credit_resolution(&mut creator_profile, Some(&mut resolver_profile))
+            .unwrap();
+        assert_eq!(
+            (creator_profile.resolutions, resolver_profile.resolutions),
+            (0, 1)
+        );
+
+        market.resolved_by = Some(resolver);
+        assert_eq!(market.accountable_resolver(), resolver);
+
+        // Oracle markets are answered by their feed; a hunter who relays it isn't on the hook
+        market.price_condition = Some(PriceCondition {
+            comparison: PriceComparison::Above,
+            strike_price: 1,
+            strike_expo: 0,
+        });
+        market.resolved_by = Some(Pubkey::new_unique());
+        assert_eq!(market.accountable_resolver(), market.creator);
+
+        market.resolver = None;
+        market
+            .credit_resolution(&mut creator_profile, None)
+            .unwrap();
+        assert_eq!(creator_profile.resolutions, 1);
+    }
+
+    #[test]
+    fn cashing_out_a_topped_up_position_takes_it_off_the_side_counts() {
+        let mut market = new_market(1_000, 5_000, 10_000);
+        let mut participant = Participant {
+            prediction: true,
+            ..Participant::default()
+        };
+        let mut other = Participant {
+            prediction: true,
+            ..Participant::default()
+        };
+
+        market
+            .credit_bet(&mut participant, true, true, 100_000_000, 2_000)
+            .unwrap();
+        market
+            .credit_bet(&mut participant, false, true, 50_000_000, 2_000)
+            .unwrap();
+        market
+            .credit_bet(&mut other, true, true, 10_000_000, 2_000)
+            .unwrap();
+        assert_eq!((market.yes_count, market.no_count), (2, 0));
+
+        let refund = market
+            .remove_position(&participant, &clock_at(2_000, 6_000))
+            .unwrap();
+        assert_eq!(
+            refund,
+            150_000_000 - 150_000_000 * CASH_OUT_FEE_BPS / BPS_DENOMINATOR
+        );
+        assert_eq!((market.yes_count, market.no_count), (1, 0));
+        assert_eq!(market.yes_pool, 10_000_000);
+        assert_eq!(market.pool_size, 10_000_000 + 150_000_000 - refund);
+    }
+
+    #[test]
+    fn duelists_cannot_cash_out_once_matched() {
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.settlement_mode = SettlementMode::WinnerTakesAll;
+        let mut challenger = Participant {
+            prediction: true,
+            ..Participant::default()
+        };
+        let mut opponent = Participant {
+            prediction: false,
+            ..Participant::default()
+        };
+        market
+            .credit_bet(&mut challenger, true, true, 100_000_000, 2_000)
+            .unwrap();
+
+        // Unmatched, the challenger may still back out
+        let mut unmatched = market.clone();
+        unmatched
+            .remove_position(&challenger, &clock_at(2_000, 6_000))
+            .unwrap();
+
+        market
+            .credit_bet(&mut opponent, true, false, 100_000_000, 2_000)
+            .unwrap();
+        for duelist in [&challenger, &opponent] {
+            assert_eq!(
+                market
+                    .remove_position(duelist, &clock_at(2_000, 6_000))
+                    .err(),
+                Some(PredictDuelError::DuelMatched.into())
+            );
+        }
+    }
+
+    #[test]
+    fn winners_split_the_pool_net_of_the_fee_and_side_pot() {
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.protocol_fee_bps = 250;
+        let mut first = Participant {
+            prediction: true,
+            ..Participant::default()
+        };
+        let mut second = Participant {
+            prediction: true,
+            ..Participant::default()
+        };
+        let mut loser = Participant {
+            prediction: false,
+            ..Participant::default()
+        };
+
+        market
+            .credit_bet(&mut first, true, true, 300_000_000, 2_000)
+            .unwrap();
+        market
+            .credit_bet(&mut second, true, true, 100_000_000, 2_000)
+            .unwrap();
+        market
+            .credit_bet(&mut loser, true, false, 600_000_000, 2_000)
+            .unwrap();
+        assert_eq!(market.pool_size, 1_000_000_000);
+        assert_eq!(market.protocol_fee(), 25_000_000);
+        assert_eq!(market.distributable_pool(), 975_000_000);
+
+        let first_payout = market
+            .calculate_payout(first.weight, market.yes_weight)
+            .unwrap();
+        let second_payout = market
+            .calculate_payout(second.weight, market.yes_weight)
+            .unwrap();
+        assert_eq!((first_payout, second_payout), (731_250_000, 243_750_000));
+        assert_eq!(first_payout + second_payout, market.distributable_pool());
+
+        // The side pot comes out of the winners' share, not the fee
+        market.side_pot_bps = 100;
+        assert_eq!(market.side_pot(), 10_000_000);
+        assert_eq!(market.distributable_pool(), 965_000_000);
+        assert_eq!(
+            market
+                .calculate_payout(second.weight, market.yes_weight)
+                .unwrap(),
+            241_250_000
+        );
+    }
+
+    #[test]
+    fn fixed_odds_payouts_are_locked_at_the_quote_and_fee_free() {
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.settlement_mode = SettlementMode::FixedOdds;
+        market.protocol_fee_bps = 250;
+        market.yes_odds_bps = 25_000;
+        market.no_odds_bps = 16_000;
+        market.pool_size = 1_000_000_000;
+
+        assert_eq!(
+            market.fixed_odds_payout(40_000_000, true).unwrap(),
+            100_000_000
+        );
+        assert_eq!(
+            market.fixed_odds_payout(40_000_000, false).unwrap(),
+            64_000_000
+        );
+        assert_eq!(market.protocol_fee(), 0);
+        assert!(market.fixed_odds_payout(u64::MAX, true).is_err());
+    }
+
+    #[test]
+    fn book_returns_collateral_and_stakes_net_of_the_winning_liability() {
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.settlement_mode = SettlementMode::FixedOdds;
+        market.yes_odds_bps = 25_000;
+        market.no_odds_bps = 16_000;
+        market.book_collateral = 1_000_000_000;
+        let mut backer = Participant {
+            prediction: true,
+            ..Participant::default()
+        };
+        let mut layer = Participant {
+            prediction: false,
+            ..Participant::default()
+        };
+        market
+            .credit_bet(&mut backer, true, true, 200_000_000, 2_000)
+            .unwrap();
+        market
+            .credit_bet(&mut layer, true, false, 300_000_000, 2_000)
+            .unwrap();
+        assert_eq!(
+            (market.yes_liability, market.no_liability),
+            (500_000_000, 480_000_000)
+        );
+
+        market.status = MarketStatus::Resolved;
+        market.outcome = Some(true);
+        market.resolved_at = 20_000;
+        assert_eq!(market.book_surplus(20_000).unwrap(), 1_000_000_000);
+        market.outcome = Some(false);
+        assert_eq!(market.book_surplus(20_000).unwrap(), 1_020_000_000);
+
+        // Bettors are refunded their stakes separately; the book only gets its collateral back
+        market.status = MarketStatus::Cancelled;
+        assert_eq!(market.book_surplus(20_000).unwrap(), 1_000_000_000);
+        market.status = MarketStatus::Voided;
+        assert_eq!(market.book_surplus(20_000).unwrap(), 1_000_000_000);
+    }
+
+    #[test]
+    fn book_surplus_waits_for_the_claim_lock() {
+        let mut market = resolved_market(true);
+        market.settlement_mode = SettlementMode::FixedOdds;
+        market.book_collateral = 500;
+        market.pool_size = 300;
+        market.yes_liability = 400;
+        market.no_liability = 200;
+
+        assert_eq!(
+            market.book_surplus(23_599).err(),
+            Some(PredictDuelError::ClaimLocked.into())
+        );
+        market.disputed = true;
+        assert_eq!(
+            market.book_surplus(23_600).err(),
+            Some(PredictDuelError::DisputeOpen.into())
+        );
+
+        // The verdict flips the outcome, so the surplus is what's left after NO's liability
+        market.disputed = false;
+        market.outcome = Some(false);
+        assert_eq!(market.book_surplus(23_600).unwrap(), 600);
+    }
+
+    fn seat(vote: Option<bool>, weight: u64) -> JurorSeat {
+        JurorSeat {
+            juror: Pubkey::new_unique(),
+            vote,
+            weight,
+        }
+    }
+
+    #[test]
+    fn dispute_majority_is_weighted_by_stake() {
+        let mut dispute = program_account::<Dispute>(Pubkey::new_unique(), Dispute::DISCRIMINATOR);
+        assert_eq!(dispute.majority(), None);
+
+        dispute.panel = vec![
+            seat(Some(true), 100),
+            seat(Some(false), 60),
+            seat(Some(false), 50),
+            seat(None, 500),
+        ];
+        assert_eq!(dispute.majority(), Some(false));
+        dispute.panel[0].weight = 110;
+        assert_eq!(dispute.majority(), None);
+    }
+
+    #[test]
+    fn minority_and_absent_jurors_are_slashed_to_the_majority() {
+        let panel = [
+            seat(Some(true), 1_000),
+            seat(Some(true), 3_000),
+            seat(Some(false), 2_000),
+            seat(None, 0),
+        ];
+        let stakes = [1_000, 3_000, 2_000, 500];
+        let positions: Vec<AccountInfo<'static>> = panel
+            .iter()
+            .zip(stakes)
+            .map(|(seat, amount)| {
+                let position = StakePosition {
+                    staker: seat.juror,
+                    amount,
+                    reward_debt: 0,
+                    unclaimed: 0,
+                    staked_epoch: 0,
+                    bump: 0,
+                    jury_votes: seat.vote.is_some() as u16,
+                };
+                let mut data = Vec::new();
+                position.try_serialize(&mut data).unwrap();
+                account_info(Pubkey::new_unique(), crate::ID, data, false, false).clone()
+            })
+            .collect();
+        let positions: &'static [AccountInfo<'static>] = positions.leak();
+        let pool = StakingPool {
+            stake_mint: Pubkey::new_unique(),
+            total_staked: 6_500,
+            acc_fee_per_share: 0,
+            owed: 0,
+            epoch_length: 0,
+            epoch_started_at: 0,
+            current_epoch: 0,
+            bump: 0,
+        };
+
+        settle_jurors(&panel, Some(true), &pool, positions).unwrap();
+
+        let settled: Vec<(u64, u16)> = positions
+            .iter()
+            .map(|info| {
+                let position =
+                    StakePosition::try_deserialize(&mut &info.data.borrow()[..]).unwrap();
+                (position.amount, position.jury_votes)
+            })
+            .collect();
+        // 200 + 50 slashed; the majority splits it 1:3 and the rounding dust goes to the first seat
+        assert_eq!(settled, vec![(1_063, 0), (3_187, 0), (1_800, 0), (450, 0)]);
+        assert_eq!(settled.iter().map(|(amount, _)| amount).sum::<u64>(), 6_500);
+    }
+
+    fn lay_offer(outcome: bool) -> LayOffer {
+        LayOffer {
+            market: Pubkey::new_unique(),
+            layer: Pubkey::new_unique(),
+            outcome,
+            odds_bps: 30_000,
+            liability: 300,
+            matched_liability: 200,
+            matched_stake: 100,
+            settled: false,
+            taker_fee_bps: 0,
+            maker_rebate_bps: 0,
+            rebates_accrued: 0,
+            expires_at: None,
+            fill_count: 1,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn lay_settlements_wait_for_the_claim_lock() {
+        let offer = lay_offer(true);
+        let back = BackPosition {
+            offer: Pubkey::new_unique(),
+            backer: Pubkey::new_unique(),
+            stake: 100,
+            matched_liability: 200,
+            claimed: false,
+            bump: 0,
+        };
+        let mut market = resolved_market(true);
+
+        let locked = Some(PredictDuelError::ClaimLocked.into());
+        assert_eq!(offer.layer_settlement(&market, 23_599).err(), locked);
+        assert_eq!(back.settlement(&offer, &market, 23_599).err(), locked);
+        assert_eq!(offer.layer_settlement(&market, 23_600).unwrap(), 100);
+        assert_eq!(back.settlement(&offer, &market, 23_600).unwrap(), 300);
+
+        // An open dispute holds both sides until the verdict, which may flip the winner
+        market.disputed = true;
+        let disputed = Some(PredictDuelError::DisputeOpen.into());
+        assert_eq!(offer.layer_settlement(&market, 30_000).err(), disputed);
+        assert_eq!(back.settlement(&offer, &market, 30_000).err(), disputed);
+        market.disputed = false;
+        market.outcome = Some(false);
+        assert_eq!(offer.layer_settlement(&market, 30_000).unwrap(), 400);
+        assert_eq!(
+            back.settlement(&offer, &market, 30_000).err(),
+            Some(PredictDuelError::NotAWinner.into())
+        );
+
+        // Refunds skip the claim lock but still wait for staked escrow to come back
+        market.status = MarketStatus::Voided;
+        assert_eq!(offer.layer_settlement(&market, 20_000).unwrap(), 300);
+        assert_eq!(back.settlement(&offer, &market, 20_000).unwrap(), 100);
+        market.lst_tokens = 1;
+        assert_eq!(
+            offer.layer_settlement(&market, 20_000).err(),
+            Some(PredictDuelError::LstNotUnwound.into())
+        );
+        assert_eq!(
+            back.settlement(&offer, &market, 20_000).err(),
+            Some(PredictDuelError::LstNotUnwound.into())
+        );
+    }
+
+    #[test]
+    fn winning_seed_waits_for_the_claim_lock() {
+        let mut market = resolved_market(true);
+        market.add_seed(100_000_000, 100_000_000, 2_000);
+        let mut bettor = Participant {
+            prediction: false,
+            ..Participant::default()
+        };
+        market
+            .credit_bet(&mut bettor, true, false, 200_000_000, 2_000)
+            .unwrap();
+
+        assert_eq!(
+            market.seed_payout(20_000).err(),
+            Some(PredictDuelError::ClaimLocked.into())
+        );
+        market.disputed = true;
+        assert_eq!(
+            market.seed_payout(23_600).err(),
+            Some(PredictDuelError::DisputeOpen.into())
+        );
+
+        // The verdict flips the outcome: the seed's share is recomputed for the new winner
+        market.disputed = false;
+        market.outcome = Some(false);
+        let payout = market.seed_payout(23_600).unwrap();
+        assert_eq!(
+            payout,
+            market
+                .calculate_payout(100_000_000, market.no_weight)
+                .unwrap()
+        );
+
+        // A cancelled market refunds the whole seed straight away
+        market.status = MarketStatus::Cancelled;
+        assert_eq!(market.seed_payout(20_000).unwrap(), 200_000_000);
+    }
+
+    #[test]
+    fn syndicate_loss_is_not_final_while_a_dispute_can_flip_it() {
+        let mut market = resolved_market(true);
+        let mut winner = Participant {
+            prediction: true,
+            ..Participant::default()
+        };
+        let mut syndicate = Participant {
+            prediction: false,
+            ..Participant::default()
+        };
+        market
+            .credit_bet(&mut winner, true, true, 100_000_000, 2_000)
+            .unwrap();
+        market
+            .credit_bet(&mut syndicate, true, false, 300_000_000, 2_000)
+            .unwrap();
+
+        assert_eq!(
+            market.pooled_settlement(&syndicate, 20_000).err(),
+            Some(PredictDuelError::ClaimLocked.into())
+        );
+        market.disputed = true;
+        assert_eq!(
+            market.pooled_settlement(&syndicate, 23_600).err(),
+            Some(PredictDuelError::DisputeOpen.into())
+        );
+
+        // Overturned: the syndicate's side now wins the pool net of the fee
+        market.disputed = false;
+        market.outcome = Some(false);
+        assert_eq!(
+            market.pooled_settlement(&syndicate, 23_600).unwrap(),
+            market.distributable_pool()
+        );
+        assert_eq!(market.pooled_settlement(&winner, 23_600).unwrap(), 0);
+
+        // Refunds return the stake once staked escrow is back in the vault
+        market.status = MarketStatus::Voided;
+        market.lst_tokens = 1;
+        assert_eq!(
+            market.pooled_settlement(&syndicate, 20_000).err(),
+            Some(PredictDuelError::LstNotUnwound.into())
+        );
+        market.lst_tokens = 0;
+        assert_eq!(
+            market.pooled_settlement(&syndicate, 20_000).unwrap(),
+            300_000_000
+        );
+    }
+
+    #[test]
+    fn raffle_draw_waits_until_the_winning_side_is_final() {
+        let mut market = resolved_market(true);
+        market.settlement_mode = SettlementMode::Raffle;
+
+        assert_eq!(
+            market.assert_raffle_drawable(23_599).err(),
+            Some(PredictDuelError::ClaimLocked.into())
+        );
+        market.disputed = true;
+        assert_eq!(
+            market.assert_raffle_drawable(23_600).err(),
+            Some(PredictDuelError::DisputeOpen.into())
+        );
+        market.disputed = false;
+        market.assert_raffle_drawable(23_600).unwrap();
+
+        market.raffle_winners = vec![0];
+        assert_eq!(
+            market.assert_raffle_drawable(23_600).err(),
+            Some(PredictDuelError::RaffleDrawn.into())
+        );
+    }
+
+    #[test]
+    fn proposals_need_quorum_and_a_majority() {
+        let mut proposal =
+            program_account::<Proposal>(Pubkey::new_unique(), Proposal::DISCRIMINATOR);
+        let supply = 1_000_000;
+        assert_eq!(Proposal::threshold(supply), 5_000);
+
+        // One token in favour with no opposition is not a mandate
+        proposal.yes_votes = 1;
+        assert_eq!(
+            proposal.assert_passed(supply).err(),
+            Some(PredictDuelError::QuorumNotReached.into())
+        );
+        proposal.yes_votes = 39_999;
+        assert_eq!(
+            proposal.assert_passed(supply).err(),
+            Some(PredictDuelError::QuorumNotReached.into())
+        );
+        proposal.yes_votes = 20_000;
+        proposal.no_votes = 20_000;
+        assert_eq!(
+            proposal.assert_passed(supply).err(),
+            Some(PredictDuelError::ProposalRejected.into())
+        );
+        proposal.yes_votes = 20_001;
+        proposal.assert_passed(supply).unwrap();
+    }
+
+    #[test]
+    fn governance_keeps_the_dispute_window_above_the_claim_delay() {
+        let mut config = program_account::<Config>(Pubkey::new_unique(), Config::DISCRIMINATOR);
+        config.claim_delay = 3_600;
+
+        let invalid = Some(PredictDuelError::InvalidGovernanceChange.into());
+        assert_eq!(
+            GovernanceChange::DisputeWindow(0).validate(&config).err(),
+            invalid
+        );
+        assert_eq!(
+            GovernanceChange::DisputeWindow(3_599)
+                .validate(&config)
+                .err(),
+            invalid
+        );
+        assert_eq!(
+            GovernanceChange::DisputeWindow(MAX_DISPUTE_WINDOW + 1)
+                .validate(&config)
+                .err(),
+            invalid
+        );
+        GovernanceChange::DisputeWindow(3_600)
+            .validate(&config)
+            .unwrap();
+    }
+
+    #[test]
+    fn passed_proposals_queue_behind_the_timelock() {
+        let mut config = program_account::<Config>(Pubkey::new_unique(), Config::DISCRIMINATOR);
+        config.claim_delay = 3_600;
+        config.governance_mint = Pubkey::new_unique();
+
+        // The fee can't skip the timelock through the admin path once governance is live...
+        let admin_fee = TimelockAction::SetProtocolFee {
+            protocol_fee_bps: 100,
+            treasury: Pubkey::new_unique(),
+        };
+        assert_eq!(
+            admin_fee.validate(&config).err(),
+            Some(PredictDuelError::GovernanceActive.into())
+        );
+        // ...and a governance change is queued as an action like any other
+        let action = TimelockAction::Governance(GovernanceChange::ProtocolFeeBps(100));
+        action.validate(&config).unwrap();
+        assert_eq!(
+            TimelockAction::Governance(GovernanceChange::DisputeWindow(0))
+                .validate(&config)
+                .err(),
+            Some(PredictDuelError::InvalidGovernanceChange.into())
+        );
+
+        // The largest change still fits the queued action's space
+        let root = TimelockAction::Governance(GovernanceChange::AirdropRoot {
+            distributor_id: 1,
+            root: [1; 32],
+        });
+        assert!(root.try_to_vec().unwrap().len() <= 1 + 43);
+
+        let TimelockAction::Governance(change) = action else {
+            unreachable!()
+        };
+        change.apply(&mut config, None).unwrap();
+        assert_eq!(config.protocol_fee_bps, 100);
+        assert_eq!(
+            GovernanceChange::AirdropRoot {
+                distributor_id: 1,
+                root: [1; 32]
+            }
+            .apply(&mut config, None)
+            .err(),
+            Some(PredictDuelError::DistributorRequired.into())
+        );
+    }
+
+    #[test]
+    fn vesting_starts_from_the_same_payout_a_claim_pays() {
+        let mut config = program_account::<Config>(Pubkey::new_unique(), Config::DISCRIMINATOR);
+        config.protocol_fee_bps = 250;
+        let mut market = resolved_market(true);
+        market.protocol_fee_bps = 250;
+        let mut winner = Participant {
+            prediction: true,
+            ..Participant::default()
+        };
+        let mut loser = Participant {
+            prediction: false,
+            ..Participant::default()
+        };
+        market
+            .credit_bet(&mut winner, true, true, 100_000_000, 2_000)
+            .unwrap();
+        market
+            .credit_bet(&mut loser, true, false, 100_000_000, 2_000)
+            .unwrap();
+        let gross = market.winner_payout(&winner, 23_600).unwrap();
+
+        let mut profile =
+            program_account::<UserProfile>(Pubkey::new_unique(), UserProfile::DISCRIMINATOR);
+        profile.fee_credit = 100_000_000;
+        profile.fee_credit_expires_at = i64::MAX;
+        let payout = market
+            .claim_payout(&config, &winner, Some(&mut profile), 23_600)
+            .unwrap();
+        // The fee-free credit is rebated and booked, exactly as claim_winnings books it
+        assert!(payout > gross);
+        assert_eq!(market.fee_rebated, payout - gross);
+        assert_eq!(profile.fee_credit, 0);
+
+        market.vesting_threshold = payout;
+        assert_eq!(
+            market.assert_vestable(payout).err(),
+            Some(PredictDuelError::PayoutBelowVestingThreshold.into())
+        );
+        market.vesting_threshold = payout - 1;
+        market.assert_vestable(payout).unwrap();
+        market.vesting_duration = 0;
+        assert_eq!(
+            market.assert_vestable(payout).err(),
+            Some(PredictDuelError::VestingDisabled.into())
+        );
+    }
+
+    #[test]
+    fn rotated_profile_leaves_no_balances_behind() {
+        let mut config = program_account::<Config>(Pubkey::new_unique(), Config::DISCRIMINATOR);
+        config.protocol_fee_bps = 250;
+        let mut market = resolved_market(true);
+        market.protocol_fee_bps = 250;
+        let mut winner = Participant {
+            prediction: true,
+            ..Participant::default()
+        };
+        let mut loser = Participant {
+            prediction: false,
+            ..Participant::default()
+        };
+        market
+            .credit_bet(&mut winner, true, true, 100_000_000, 2_000)
+            .unwrap();
+        market
+            .credit_bet(&mut loser, true, false, 100_000_000, 2_000)
+            .unwrap();
+        let payout = market.winner_payout(&winner, 23_600).unwrap();
+
+        let mut profile =
+            program_account::<UserProfile>(Pubkey::new_unique(), UserProfile::DISCRIMINATOR);
+        profile.fee_credit = 100_000_000;
+        profile.fee_credit_expires_at = i64::MAX;
+        profile.volume_30d = 1_000_000_000_000;
+        profile.resolved_on_time = 10;
+        let mut moved = profile.clone();
+
+        profile.tombstone(Pubkey::new_unique());
+        assert_eq!(
+            (
+                profile.fee_credit,
+                profile.volume_30d,
+                profile.creator_score_bps()
+            ),
+            (0, 0, 0)
+        );
+
+        // Even a tombstone that still had credit couldn't spend it
+        let mut stale = moved.clone();
+        stale.moved_to = Some(Pubkey::new_unique());
+        assert_eq!(
+            market.fee_rebate(&config, &winner, Some(&mut stale), payout, 23_600),
+            0
+        );
+        assert_eq!(stale.fee_credit, 100_000_000);
+        assert!(market.fee_rebate(&config, &winner, Some(&mut moved), payout, 23_600) > 0);
+        assert_eq!(moved.fee_credit, 0);
+
+        let mut next = new_market(1_000, 5_000, 10_000);
+        assert_eq!(
+            register_market(&mut next, &config, &mut profile, None, None).err(),
+            Some(PredictDuelError::ProfileMoved.into())
+        );
+    }
+
+    #[test]
+    fn points_accrue_only_on_settled_markets() {
+        let mut schedule =
+            program_account::<PointsSchedule>(Pubkey::new_unique(), PointsSchedule::DISCRIMINATOR);
+        schedule.bet_points_per_sol = 100;
+        schedule.win_points = 50;
+        let mut participant = Participant {
+            prediction: true,
+            stake: 2_000_000_000,
+            ..Participant::default()
+        };
+
+        let mut voided = resolved_market(true);
+        voided.status = MarketStatus::Voided;
+        assert_eq!(
+            schedule.accrue(&voided, &mut participant, 30_000).err(),
+            Some(PredictDuelError::MarketNotResolved.into())
+        );
+        let market = resolved_market(true);
+        assert!(schedule.accrue(&market, &mut participant, 23_599).is_err());
+        assert_eq!(participant.points_credited, 0);
+
+        assert_eq!(
+            schedule.accrue(&market, &mut participant, 23_600).unwrap(),
+            250
+        );
+        assert_eq!(
+            schedule.accrue(&market, &mut participant, 23_600).err(),
+            Some(PredictDuelError::NoPointsToAccrue.into())
+        );
+    }
+
+    #[test]
+    fn creator_cancelling_a_live_market_forfeits_the_bond() {
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.status = MarketStatus::Active;
+        market.creation_bond = 50_000_000;
+        assert!(!market.bond_forfeited());
+
+        assert_eq!(
+            market.clone().cancel_with_refunds(10_000).err(),
+            Some(PredictDuelError::MarketExpired.into())
+        );
+        market.cancel_with_refunds(9_999).unwrap();
+        assert!(market.status == MarketStatus::Cancelled);
+        assert!(market.bond_forfeited());
+        assert_eq!(
+            market.cancel_with_refunds(9_999).err(),
+            Some(PredictDuelError::CannotCancel.into())
+        );
+    }
+
+    #[test]
+    fn reported_result_markets_cannot_be_pushed_by_hand() {
+        let mut duel = new_market(1_000, 5_000, 10_000);
+        duel.settlement_mode = SettlementMode::WinnerTakesAll;
+        assert!(duel.assert_manual_outcome().is_ok());
+
+        let mut spread = duel.clone();
+        spread.spread_x10 = Some(-35);
+        assert_eq!(
+            spread.assert_manual_outcome().err(),
+            Some(PredictDuelError::SpreadMarket.into())
+        );
+        let mut event = duel.clone();
+        event.event = Some(Pubkey::new_unique());
+        assert_eq!(
+            event.assert_manual_outcome().err(),
+            Some(PredictDuelError::EventMarket.into())
+        );
+        let mut priced = duel.clone();
+        priced.price_condition = Some(PriceCondition {
+            comparison: PriceComparison::Above,
+            strike_price: 100,
+            strike_expo: 0,
+        });
+        assert_eq!(
+            priced.assert_manual_outcome().err(),
+            Some(PredictDuelError::OracleMarket.into())
+        );
+    }
+
+    #[test]
+    fn settlement_root_leaves_room_for_the_seed() {
+        let mut market = resolved_market(true);
+        market.merkle_settlement = true;
+        market.add_seed(100_000_000, 100_000_000, 2_000);
+        let mut bettor = Participant {
+            prediction: true,
+            ..Participant::default()
+        };
+        market
+            .credit_bet(&mut bettor, true, true, 100_000_000, 2_000)
+            .unwrap();
+
+        let seed = market.seed_payout(23_600).unwrap();
+        assert!(seed > 0);
+        let cap = market.settlement_cap(23_600).unwrap();
+        assert_eq!(cap + seed, market.distributable_pool());
+    }
+
+    #[test]
+    fn installments_wait_for_escrow_to_unwind() {
+        let mut market = resolved_market(true);
+        let mut winner = Participant {
+            prediction: true,
+            ..Participant::default()
+        };
+        let mut loser = Participant {
+            prediction: false,
+            ..Participant::default()
+        };
+        market
+            .credit_bet(&mut winner, true, true, 100_000_000, 2_000)
+            .unwrap();
+        market
+            .credit_bet(&mut loser, true, false, 100_000_000, 2_000)
+            .unwrap();
+        market.lst_tokens = 95_000_000;
+
+        assert_eq!(
+            market.installment_gross(&winner, 1, 23_600).err(),
+            Some(PredictDuelError::LstNotUnwound.into())
+        );
+        market.lst_tokens = 0;
+        let gross = market.installment_gross(&winner, 1, 23_600).unwrap();
+        assert_eq!(
+            market.installment_gross(&winner, gross + 1, 23_600).err(),
+            Some(PredictDuelError::InvalidInstallment.into())
+        );
+    }
+
+    #[test]
+    fn boosts_close_with_betting() {
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.status = MarketStatus::Active;
+        market.betting_cutoff_secs = 600;
+        market.deadline_slot = 8_000;
+        assert!(market.assert_boostable(&clock_at(9_399, 7_999)).is_ok());
+
+        // Past the betting cutoff or the slot deadline, though before the unix deadline
+        for clock in [clock_at(9_400, 6_000), clock_at(2_000, 8_000)] {
+            assert_eq!(
+                market.assert_boostable(&clock).err(),
+                Some(PredictDuelError::MarketExpired.into())
+            );
+        }
+        market.frozen = true;
+        assert_eq!(
+            market.assert_boostable(&clock_at(2_000, 6_000)).err(),
+            Some(PredictDuelError::MarketFrozen.into())
+        );
+    }
+
+    #[test]
+    fn lay_stakes_pass_the_market_stake_checks() {
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.max_pool = 1_000_000_000;
+        let min_stake = market.min_stake;
+        market.assert_accepting_stake(min_stake, 2_000).unwrap();
+
+        // A flag freezes the lay book along with the pools
+        market.frozen = true;
+        assert_eq!(
+            market.assert_accepting_stake(min_stake, 2_000).err(),
+            Some(PredictDuelError::MarketFrozen.into())
+        );
+        market.frozen = false;
+        assert_eq!(
+            market.assert_accepting_stake(min_stake - 1, 2_000).err(),
+            Some(PredictDuelError::StakeTooLow.into())
+        );
+        assert_eq!(
+            market.assert_accepting_stake(1_000_000_001, 2_000).err(),
+            Some(PredictDuelError::PoolCapExceeded.into())
+        );
+        market.oracle_required = true;
+        assert_eq!(
+            market.assert_accepting_stake(min_stake, 2_000).err(),
+            Some(PredictDuelError::OracleRequired.into())
+        );
+    }
+
+    #[test]
+    fn sealed_commitments_pass_the_market_stake_checks() {
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.max_pool = 1_000_000_000;
+        let min_stake = market.min_stake;
+        assert_eq!(
+            market.assert_accepting_commitment(min_stake, 2_000).err(),
+            Some(PredictDuelError::NotSealedMarket.into())
+        );
+
+        market.sealed = true;
+        market
+            .assert_accepting_commitment(min_stake, 2_000)
+            .unwrap();
+        assert_eq!(
+            market.assert_accepting_stake(min_stake, 2_000).err(),
+            Some(PredictDuelError::SealedMarket.into())
+        );
+        assert_eq!(
+            market
+                .assert_accepting_commitment(1_000_000_001, 2_000)
+                .err(),
+            Some(PredictDuelError::PoolCapExceeded.into())
+        );
+        market.oracle_required = true;
+        assert_eq!(
+            market.assert_accepting_commitment(min_stake, 2_000).err(),
+            Some(PredictDuelError::OracleRequired.into())
+        );
+    }
+
+    #[test]
+    fn closing_auction_only_takes_parimutuel_stakes() {
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.auction_window = 600;
+        let mut bettor = Participant {
+            prediction: true,
+            ..Participant::default()
+        };
+        market
+            .credit_bet(&mut bettor, true, true, 100_000_000, 9_500)
+            .unwrap();
+        assert_eq!((market.auction_yes, market.yes_pool), (100_000_000, 0));
+
+        // A market switched to fixed odds after the auction was set can't take unquoted stakes
+        market.settlement_mode = SettlementMode::FixedOdds;
+        market.yes_odds_bps = 20_000;
+        market.book_collateral = 1_000_000_000;
+        let mut late = Participant {
+            prediction: true,
+            ..Participant::default()
+        };
+        assert_eq!(
+            market
+                .credit_bet(&mut late, true, true, 100_000_000, 9_500)
+                .err(),
+            Some(PredictDuelError::InvalidSettlementMode.into())
+        );
+        market
+            .credit_bet(&mut late, true, true, 100_000_000, 9_000)
+            .unwrap();
+        assert_eq!(late.locked_payout, 200_000_000);
+    }
+
+    #[test]
+    fn commitments_are_sha256_of_their_fields() {
+        assert_eq!(hash(b"abc").to_bytes()[..4], [0xba, 0x78, 0x16, 0xbf]);
+
+        let (market, bettor, salt) = (Pubkey::new_unique(), Pubkey::new_unique(), [3; 32]);
+        let preimage = [market.as_ref(), bettor.as_ref(), &[1], &salt].concat();
+        assert_eq!(
+            bet_commitment(&market, &bettor, true, &salt),
+            hash(&preimage).to_bytes()
+        );
+    }
+
+    #[test]
+    fn spread_pushes_still_record_the_resolver() {
+        let resolver = Pubkey::new_unique();
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.spread_x10 = Some(-35);
+        assert_eq!(
+            market.settle_spread(24, 20, resolver, 20_000).unwrap(),
+            Some(true)
+        );
+        assert!(market.status == MarketStatus::Resolved);
+        assert_eq!(market.resolved_by, Some(resolver));
+
+        let mut pushed = new_market(1_000, 5_000, 10_000);
+        pushed.spread_x10 = Some(-30);
+        assert_eq!(
+            pushed.settle_spread(23, 20, resolver, 20_000).unwrap(),
+            None
+        );
+        assert!(pushed.status == MarketStatus::Voided);
+        assert_eq!((pushed.outcome, pushed.settled_at), (None, 20_000));
+        assert_eq!(pushed.resolved_by, Some(resolver));
+    }
+
+    #[test]
+    fn oracle_voids_close_the_market_in_its_category_stats() {
+        let mut stats =
+            program_account::<CategoryStats>(Pubkey::new_unique(), CategoryStats::DISCRIMINATOR);
+        let mut market = new_market(1_000, 5_000, 10_000);
+        market.status = MarketStatus::Active;
+        market.pool_size = 300_000_000;
+        market.sync_category_stats(&mut stats);
+        assert_eq!((stats.open_markets, stats.closed_markets), (1, 0));
+
+        // A price market with no settling reading voids, the way resolve_price_market does
+        market.status = MarketStatus::Voided;
+        market.settled_at = 20_000;
+        market.sync_category_stats(&mut stats);
+        market.sync_category_stats(&mut stats);
+        assert_eq!((stats.open_markets, stats.closed_markets), (0, 1));
+        assert_eq!(stats.closed_pool_total, 300_000_000);
+        assert_eq!(stats.total_volume, 300_000_000);
+    }
+
+    #[test]
+    fn duel_pushes_still_record_the_resolver() {
+        let resolver = Pubkey::new_unique();
+        let mut market = new_market(1_000, 5_000, 10_000);
+        assert!(market.push_duel(resolver, 20_000).is_err());
+
+        market.settlement_mode = SettlementMode::WinnerTakesAll;
+        market.push_duel(resolver, 20_000).unwrap();
+        assert!(market.status == MarketStatus::Voided);
+        assert_eq!((market.outcome, market.settled_at), (None, 20_000));
+        assert_eq!(market.resolved_by, Some(resolver));
+    }
+}
@@ -0,0 +1,273 @@
+import * as anchor from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+  Keypair,
+  PublicKey,
+  TransactionInstruction,
+  TransactionMessage,
+} from "@solana/web3.js";
+import * as multisig from "@sqds/multisig";
+
+// Runs against the Squads v4 program cloned from mainnet (`make test-multisig`); skipped
+// on validators without it
+describe("multisig admin", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program: any = anchor.workspace.PredictDuel;
+  const connection = provider.connection;
+  const feePayer = (provider.wallet as anchor.Wallet).payer;
+
+  const memberA = Keypair.generate();
+  const memberB = Keypair.generate();
+  const createKey = Keypair.generate();
+
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+  const [multisigPda] = multisig.getMultisigPda({
+    createKey: createKey.publicKey,
+  });
+  const [vaultPda] = multisig.getVaultPda({ multisigPda, index: 0 });
+
+  let transactionIndex = BigInt(0);
+
+  // Next queued action PDA in the program's timelock
+  async function nextQueuedActionPda() {
+    const config = await program.account.config.fetch(configPda);
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("queued_action"),
+        config.queuedCount.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+  }
+
+  // Propose, approve by both members, and execute instructions signed by the vault
+  async function executeViaMultisig(instructions: TransactionInstruction[]) {
+    transactionIndex += BigInt(1);
+
+    const transactionMessage = new TransactionMessage({
+      payerKey: vaultPda,
+      recentBlockhash: (await connection.getLatestBlockhash()).blockhash,
+      instructions,
+    });
+
+    await connection.confirmTransaction(
+      await multisig.rpc.vaultTransactionCreate({
+        connection,
+        feePayer,
+        multisigPda,
+        transactionIndex,
+        creator: memberA.publicKey,
+        vaultIndex: 0,
+        ephemeralSigners: 0,
+        transactionMessage,
+        signers: [memberA],
+      })
+    );
+    await connection.confirmTransaction(
+      await multisig.rpc.proposalCreate({
+        connection,
+        feePayer,
+        multisigPda,
+        transactionIndex,
+        creator: memberA,
+      })
+    );
+    for (const member of [memberA, memberB]) {
+      await connection.confirmTransaction(
+        await multisig.rpc.proposalApprove({
+          connection,
+          feePayer,
+          multisigPda,
+          transactionIndex,
+          member,
+        })
+      );
+    }
+    await connection.confirmTransaction(
+      await multisig.rpc.vaultTransactionExecute({
+        connection,
+        feePayer,
+        multisigPda,
+        transactionIndex,
+        member: memberA.publicKey,
+        signers: [memberA],
+      })
+    );
+  }
+
+  before(async function () {
+    if (!(await connection.getAccountInfo(multisig.PROGRAM_ID))) {
+      console.log("Squads v4 is not deployed on this validator: skipping");
+      this.skip();
+    }
+
+    // The vault pays rent for the actions it queues
+    for (const account of [memberA.publicKey, memberB.publicKey, vaultPda]) {
+      await connection.confirmTransaction(
+        await connection.requestAirdrop(
+          account,
+          anchor.web3.LAMPORTS_PER_SOL
+        )
+      );
+    }
+
+    if (!(await connection.getAccountInfo(configPda))) {
+      const [programDataPda] = PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      );
+      await program.methods
+        .initializeConfig()
+        .accounts({
+          admin: provider.wallet.publicKey,
+          programData: programDataPda,
+        })
+        .rpc();
+    }
+
+    // 2-of-2 multisig whose default vault will hold the admin role
+    const [programConfigPda] = multisig.getProgramConfigPda({});
+    const programConfig =
+      await multisig.accounts.ProgramConfig.fromAccountAddress(
+        connection,
+        programConfigPda
+      );
+    await connection.confirmTransaction(
+      await multisig.rpc.multisigCreateV2({
+        connection,
+        treasury: programConfig.treasury,
+        createKey,
+        creator: feePayer,
+        multisigPda,
+        configAuthority: null,
+        timeLock: 0,
+        threshold: 2,
+        rentCollector: null,
+        members: [
+          {
+            key: memberA.publicKey,
+            permissions: multisig.types.Permissions.all(),
+          },
+          {
+            key: memberB.publicKey,
+            permissions: multisig.types.Permissions.all(),
+          },
+        ],
+      })
+    );
+  });
+
+  it("Hands the admin role to the multisig vault", async () => {
+    const queuedAction = await nextQueuedActionPda();
+    await program.methods
+      .queueAction({ rotateAdmin: { newAdmin: vaultPda } })
+      .accounts({
+        admin: provider.wallet.publicKey,
+        payer: provider.wallet.publicKey,
+        queuedAction,
+      })
+      .rpc();
+    await program.methods
+      .executeQueued()
+      .accounts({
+        admin: provider.wallet.publicKey,
+        queuedAction,
+        treasury: null,
+        recipient: null,
+      })
+      .rpc();
+
+    const acceptIx = await program.methods
+      .acceptAdmin()
+      .accounts({ newAdmin: vaultPda })
+      .instruction();
+    await executeViaMultisig([acceptIx]);
+
+    const config = await program.account.config.fetch(configPda);
+    assert.equal(config.admin.toString(), vaultPda.toString());
+    assert.equal(config.pendingAdmin.toString(), PublicKey.default.toString());
+  });
+
+  it("Updates the protocol fee through a multisig proposal", async () => {
+    const queuedAction = await nextQueuedActionPda();
+    const queueIx = await program.methods
+      .queueAction({
+        setProtocolFee: { protocolFeeBps: 100, treasury: vaultPda },
+      })
+      .accounts({ admin: vaultPda, payer: vaultPda, queuedAction })
+      .instruction();
+    const executeIx = await program.methods
+      .executeQueued()
+      .accounts({ admin: vaultPda, queuedAction, treasury: null, recipient: null })
+      .instruction();
+    await executeViaMultisig([queueIx, executeIx]);
+
+    const config = await program.account.config.fetch(configPda);
+    assert.equal(config.protocolFeeBps, 100);
+    assert.equal(config.treasury.toString(), vaultPda.toString());
+  });
+
+  it("Holds queued actions for the timelock delay and lets the admin cancel them", async () => {
+    const delayAction = await nextQueuedActionPda();
+    await executeViaMultisig([
+      await program.methods
+        .queueAction({ setTimelockDelay: { delay: new anchor.BN(3600) } })
+        .accounts({ admin: vaultPda, payer: vaultPda, queuedAction: delayAction })
+        .instruction(),
+      await program.methods
+        .executeQueued()
+        .accounts({
+          admin: vaultPda,
+          queuedAction: delayAction,
+          treasury: null,
+          recipient: null,
+        })
+        .instruction(),
+    ]);
+
+    const feeAction = await nextQueuedActionPda();
+    await executeViaMultisig([
+      await program.methods
+        .queueAction({
+          setProtocolFee: { protocolFeeBps: 500, treasury: vaultPda },
+        })
+        .accounts({ admin: vaultPda, payer: vaultPda, queuedAction: feeAction })
+        .instruction(),
+    ]);
+
+    const queued = await program.account.queuedAction.fetch(feeAction);
+    assert.isTrue(queued.eta.toNumber() >= Math.floor(Date.now() / 1000) + 3000);
+
+    await executeViaMultisig([
+      await program.methods
+        .cancelQueued()
+        .accounts({ admin: vaultPda, queuedAction: feeAction })
+        .instruction(),
+    ]);
+
+    assert.isNull(await connection.getAccountInfo(feeAction));
+    const config = await program.account.config.fetch(configPda);
+    assert.equal(config.protocolFeeBps, 100);
+  });
+
+  it("Rejects actions queued by the old admin key", async () => {
+    try {
+      await program.methods
+        .queueAction({ rotateAdmin: { newAdmin: provider.wallet.publicKey } })
+        .accounts({
+          admin: provider.wallet.publicKey,
+          payer: provider.wallet.publicKey,
+          queuedAction: await nextQueuedActionPda(),
+        })
+        .rpc();
+      assert.fail("Should have thrown an error");
+    } catch (error) {
+      assert.include(error.toString(), "Unauthorized");
+    }
+  });
+});